@@ -0,0 +1,107 @@
+//! Compares moving a single updated network into place against sorting every network again,
+//! for tables of increasing size, and times the handling of a batch of events end to end.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use eventsource_client::Event;
+use roller::{Data, Network, Tui};
+
+/// A Tui with `count` networks, sorted by the default strategy.
+fn tui(count: usize) -> Tui {
+    let networks = (0..count)
+        .map(|i| {
+            let mut network = Network::new(
+                format!("network-{i}"),
+                format!("Network {i}"),
+                String::from("ethereum"),
+                String::from("ethereum"),
+                String::from("op"),
+                None,
+            );
+            network.update_data(Some(data(i)));
+            network
+        })
+        .collect();
+    let mut tui = Tui::new(networks);
+    tui.sort_networks();
+    tui
+}
+
+/// Data spreading the gas per second of the networks, varying with each update.
+fn data(seed: usize) -> Data {
+    Data {
+        gps: (seed.wrapping_mul(7919) % 1000) as f32,
+        ..Data::default()
+    }
+}
+
+fn update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update");
+    for count in [50, 500, 5000] {
+        let mut seed = 0;
+        let mut incremental = tui(count);
+        group.bench_function(BenchmarkId::new("reposition", count), |b| {
+            b.iter(|| {
+                seed += 1;
+                let index = seed % count;
+                let name = format!("network-{index}");
+                incremental.network_mut(&name).unwrap().data = Some(data(seed));
+                let index = incremental.position(&name).unwrap();
+                incremental.reposition(index)
+            })
+        });
+
+        let mut full = tui(count);
+        group.bench_function(BenchmarkId::new("sort_networks", count), |b| {
+            b.iter(|| {
+                seed += 1;
+                let index = seed % count;
+                full.network_mut(&format!("network-{index}")).unwrap().data = Some(data(seed));
+                full.sort_networks()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// An event updating every one of `count` networks, as received from the stream.
+fn events(count: usize, seed: usize) -> Vec<Event> {
+    (0..count)
+        .map(|i| Event {
+            event_type: format!("network-{i}"),
+            data: format!(
+                r#"{{"blockNumber": {seed}, "tps": "{}", "gps": "{}", "dps": "1.5"}}"#,
+                (i + seed) % 100,
+                data(i + seed).gps
+            ),
+            id: Some(seed.to_string()),
+            retry: None,
+        })
+        .collect()
+}
+
+fn batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch");
+    for count in [10, 100, 1000] {
+        let mut seed = 0;
+        let mut tui = tui(count);
+        group.bench_function(BenchmarkId::new("update_networks", count), |b| {
+            b.iter_batched(
+                || {
+                    seed += 1;
+                    events(count, seed)
+                },
+                |events| {
+                    for event in events {
+                        tui.update_networks(event);
+                    }
+                    tui.sort_networks()
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, update, batch);
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+/// The number of past updates of a network kept to detect anomalies, see `Network::history`.
+pub const HISTORY_LEN: usize = 30;
+
+/// The number of past updates required before a value can be flagged, so the first few
+/// updates of a network aren't compared against a meaningless window.
+pub const MIN_SAMPLES: usize = 5;
+
+/// The marker prepended to the label of a network with an anomalous metric.
+pub const ANOMALY_MARKER: &str = "⚡";
+
+/// Computes the mean and the population standard deviation of values.
+///
+/// ### Arguments
+/// * `values` - The values, such as the past samples of a metric.
+///
+/// ### Returns
+/// The `(mean, standard deviation)`, or `None` without values.
+pub fn mean_stddev(values: &[f32]) -> Option<(f32, f32)> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let count = values.len() as f32;
+    let mean = values.iter().sum::<f32>() / count;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / count;
+    Some((mean, variance.sqrt()))
+}
+
+/// Computes how many standard deviations a value is away from the mean of past values.
+///
+/// ### Arguments
+/// * `history` - The past values, the value excluded.
+/// * `value` - The value to compare against them.
+///
+/// ### Returns
+/// The signed z-score, positive for a spike and negative for a drop, or `None` with fewer than
+/// `MIN_SAMPLES` past values or when they're all equal.
+pub fn z_score(history: &[f32], value: f32) -> Option<f32> {
+    if history.len() < MIN_SAMPLES {
+        return None;
+    }
+
+    let (mean, stddev) = mean_stddev(history)?;
+    (stddev > f32::EPSILON).then(|| (value - mean) / stddev)
+}
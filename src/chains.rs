@@ -0,0 +1,85 @@
+use crate::config_file::Section;
+use std::collections::HashMap;
+
+/// The chains known without a `--chains` file, as `(id, name)` pairs.
+const BUILTIN_CHAINS: [(u64, &str); 3] = [(1, "ethereum"), (8453, "base"), (42161, "arbitrum")];
+
+/// Maps chain IDs to the names displayed in the "Settlement" column.
+///
+/// The registry starts with the built-in chains, which can be overridden or extended
+/// by a user-supplied file through `--chains` or the `ROLLER_CHAINS` environment variable.
+#[derive(Debug, Clone)]
+pub struct ChainRegistry {
+    names: HashMap<u64, String>,
+}
+
+impl Default for ChainRegistry {
+    fn default() -> Self {
+        Self {
+            names: BUILTIN_CHAINS
+                .iter()
+                .map(|(id, name)| (*id, name.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Section for ChainRegistry {
+    /// Adds the mappings of a chains file, overriding the existing names of their ids.
+    ///
+    /// The file holds one `id = "name"` mapping per line, like a flat TOML document.
+    /// Blank lines and lines starting with `#` are ignored.
+    ///
+    /// ### Arguments
+    /// * `contents` - The contents of the chains file.
+    ///
+    /// ### Returns
+    /// `Ok` if every mapping is valid, or an error prefixed by the line number of the first invalid one.
+    fn extend_from_str(&mut self, contents: &str) -> Result<(), String> {
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let line_number = i + 1;
+            let Some((id, name)) = line.split_once('=') else {
+                return Err(format!(
+                    "{line_number}: expected `id = \"name\"`, got `{line}`"
+                ));
+            };
+            let id = id.trim();
+            let id: u64 = id
+                .parse()
+                .map_err(|_| format!("{line_number}: invalid chain id `{id}`"))?;
+            let name = name
+                .trim()
+                .strip_prefix('"')
+                .and_then(|name| name.strip_suffix('"'))
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| {
+                    format!("{line_number}: the name of chain id {id} must be a non-empty quoted string")
+                })?;
+
+            self.names.insert(id, name.to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl ChainRegistry {
+    /// Returns the name of a chain.
+    ///
+    /// ### Arguments
+    /// * `id` - The chain ID.
+    ///
+    /// ### Returns
+    /// The chain name corresponding to the chain ID, or "unknown" if the ID is not listed.
+    pub fn name(&self, id: u64) -> String {
+        self.names
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| String::from("unknown"))
+    }
+}
@@ -1,17 +1,164 @@
-use crate::Network;
+use crate::{ChainRegistry, Network, RollerError};
 use eventsource_client::{self as es, Client as EventSourceClient, SSE};
 use futures::Stream;
-use std::{collections::HashMap, pin::Pin};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde_json::Value;
+use std::{collections::HashMap, fmt, pin::Pin, sync::Mutex, time::Duration};
+
+/// The default maximum time to establish a connection to the API.
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// The default maximum time for a request to the API to complete.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// The default number of times a failed request is retried.
+const DEFAULT_RETRIES: u32 = 3;
+/// The default time to wait before retrying a failed request.
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
 
 /// The client provides methods for retrieving network metadata and establishing
 /// a connection to a Server-Sent Events (SSE) stream for receiving real-time updates.
 pub struct Client {
-    url: String,
+    /// The base URLs of the API, the first one followed by its fallbacks.
+    urls: Vec<String>,
+    /// The index of the URL that last served the metadata, used by every other request.
+    active: Mutex<usize>,
+    http: reqwest::Client,
+    settings: ClientSettings,
+    auth_token: Option<String>,
+    /// The custom headers sent with every request, applied to the SSE stream separately.
+    headers: HeaderMap,
+    /// The ID of the last event received, sent as `Last-Event-ID` to resume the next stream.
+    last_event_id: Mutex<Option<String>>,
+}
+
+/// The timeout and retry behavior of the `Client`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientSettings {
+    /// The maximum time to establish a connection.
+    pub connect_timeout: Duration,
+    /// The maximum time for a request to complete, from connecting to reading the whole body.
+    /// Doesn't apply to the SSE stream, which stays open indefinitely.
+    pub request_timeout: Duration,
+    /// The number of times a request is retried after a connection error or a server error response,
+    /// with a linear back-off of `retry_delay` per attempt.
+    pub retries: u32,
+    /// The time to wait before retrying a request, or reconnecting to the SSE stream.
+    pub retry_delay: Duration,
+}
+
+impl Default for ClientSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            retries: DEFAULT_RETRIES,
+            retry_delay: DEFAULT_RETRY_DELAY,
+        }
+    }
+}
+
+/// Builds a `Client` with custom timeout and retry behavior.
+pub struct ClientBuilder {
+    urls: Vec<String>,
+    settings: ClientSettings,
+    auth_token: Option<String>,
+    headers: HeaderMap,
+}
+
+impl ClientBuilder {
+    /// Creates a new builder for the specified URL, with the default settings.
+    ///
+    /// ### Arguments
+    /// * `url` - The base URL of the conduit.xyz API.
+    pub fn new<T: Into<String>>(url: T) -> Self {
+        Self {
+            urls: vec![url.into()],
+            settings: ClientSettings::default(),
+            auth_token: None,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// Adds a fallback URL, tried in the order added when fetching the metadata fails.
+    pub fn fallback<T: Into<String>>(mut self, url: T) -> Self {
+        self.urls.push(url.into());
+        self
+    }
+
+    /// Sets the maximum time to establish a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.settings.connect_timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum time for a request to complete.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.settings.request_timeout = timeout;
+        self
+    }
+
+    /// Sets the number of times a failed request is retried, `0` disables retries.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.settings.retries = retries;
+        self
+    }
+
+    /// Sets the time to wait before retrying a failed request.
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.settings.retry_delay = delay;
+        self
+    }
+
+    /// Sets the token sent as a bearer `Authorization` header with every request and the SSE stream.
+    pub fn auth_token<T: Into<String>>(mut self, token: T) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Adds a custom header sent with every request and the SSE stream.
+    /// A header added more than once is sent with every value.
+    pub fn header(mut self, header: Header) -> Self {
+        self.headers.append(header.name, header.value);
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// ### Returns
+    /// The `Client`, or an error if the underlying HTTP client can't be initialized.
+    pub fn build(self) -> reqwest::Result<Client> {
+        let http = reqwest::Client::builder()
+            .connect_timeout(self.settings.connect_timeout)
+            .timeout(self.settings.request_timeout)
+            .default_headers(self.headers.clone())
+            .build()?;
+        if !self.headers.is_empty() {
+            let names: Vec<&str> = self.headers.keys().map(HeaderName::as_str).collect();
+            tracing::info!(headers = ?names, "sending custom headers, values redacted");
+        }
+
+        Ok(Client {
+            urls: self.urls,
+            active: Mutex::new(0),
+            http,
+            settings: self.settings,
+            auth_token: self.auth_token,
+            headers: self.headers,
+            last_event_id: Mutex::new(None),
+        })
+    }
+}
+
+/// The response of the `/health` endpoint of the API.
+#[derive(Debug, serde::Deserialize)]
+pub struct Health {
+    /// The version of the API, e.g. `1.4.2`.
+    pub version: String,
 }
 
 /// The response we're expecting from the EventSourceClient after connection.
 /// This is a stream of Server-Sent Events (SSE) or an error.
-type StreamResponse = Pin<Box<(dyn Stream<Item = Result<SSE, es::Error>> + Send + Sync + 'static)>>;
+pub(crate) type StreamResponse =
+    Pin<Box<dyn Stream<Item = Result<SSE, es::Error>> + Send + Sync + 'static>>;
 
 impl Client {
     /// Creates a new instance of the client with the specified URL.
@@ -20,43 +167,432 @@ impl Client {
     /// * `url` - The base URL of the conduit.xyz API.
     ///
     /// ### Returns
-    /// A new instance of the `Client`.
+    /// A new instance of the `Client`, with the default timeout and retry settings.
+    ///
+    /// ### Panics
+    /// If the underlying HTTP client can't be initialized, see `ClientBuilder::build`.
     pub fn new<T: Into<String>>(url: T) -> Self {
-        Self { url: url.into() }
+        ClientBuilder::new(url)
+            .build()
+            .expect("failed to initialize the HTTP client")
+    }
+
+    /// Creates a builder for a client with custom timeout and retry settings.
+    ///
+    /// ### Arguments
+    /// * `url` - The base URL of the conduit.xyz API.
+    pub fn builder<T: Into<String>>(url: T) -> ClientBuilder {
+        ClientBuilder::new(url)
+    }
+
+    /// Returns the timeout and retry settings of the client.
+    pub fn settings(&self) -> &ClientSettings {
+        &self.settings
+    }
+
+    /// Returns the base URL the client uses, the one that last served the metadata.
+    /// Until the metadata is fetched, this is the first URL.
+    pub fn active_endpoint(&self) -> &str {
+        let active = *self.active.lock().unwrap_or_else(|e| e.into_inner());
+        &self.urls[active]
+    }
+
+    /// Returns the ID of the last event received, the stream resumes from, if any.
+    pub fn last_event_id(&self) -> Option<String> {
+        self.last_event_id
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Sets the ID of the last event received, so that the next stream resumes after it
+    /// rather than replaying the events already processed.
+    ///
+    /// ### Arguments
+    /// * `id` - The ID of the last event, `None` to start from the live events.
+    pub fn set_last_event_id(&self, id: Option<String>) {
+        *self.last_event_id.lock().unwrap_or_else(|e| e.into_inner()) = id;
     }
 
-    /// Retrieves the network metadata from the API, without the Data field, which will be populated 
+    /// Retrieves the network metadata from the API, without the Data field, which will be populated
     /// through SSE (server-side event) data after initialization.
     ///
-    /// This method sends a GET request to the `/networkMetadata` endpoint
-    /// of the API. The response is expected to be a JSON object with network IDs as keys.
+    /// ### Arguments
+    /// * `chains` - The registry resolving the names of the parent chains.
     ///
     /// ### Returns
     /// A vector of `Network` structs representing the retrieved network metadata,
     /// or an error if the request fails or the response cannot be parsed.
-    pub async fn get_networks(&self) -> Result<Vec<Network>, Box<dyn std::error::Error>> {
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_networks(
+        &self,
+        chains: &ChainRegistry,
+    ) -> Result<Vec<Network>, Box<dyn std::error::Error>> {
+        Ok(networks_from_metadata(self.get_metadata().await?, chains)?)
+    }
+
+    /// Retrieves the raw, validated network metadata document from the API.
+    ///
+    /// This method sends a GET request to the `/networkMetadata` endpoint of each URL in order,
+    /// falling back to the next one on error. The first successful response wins, and its URL
+    /// becomes the active endpoint used by the other requests and the SSE stream.
+    /// The response is expected to be a JSON object with network IDs as keys.
+    ///
+    /// ### Returns
+    /// The metadata document, or the error of the last URL if every one of them fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_metadata(&self) -> Result<Value, Box<dyn std::error::Error>> {
+        let mut result = Err("no API endpoint configured".into());
+        for (i, url) in self.urls.iter().enumerate() {
+            result = self.get_metadata_from(url).await;
+            match &result {
+                Ok(_) => {
+                    *self.active.lock().unwrap_or_else(|e| e.into_inner()) = i;
+                    break;
+                }
+                Err(e) if i + 1 < self.urls.len() => {
+                    tracing::warn!(endpoint = url, error = %e, "falling back to the next endpoint");
+                }
+                Err(_) => {}
+            }
+        }
+        result
+    }
+
+    /// Retrieves the network metadata from a single base URL, see `get_metadata`.
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn get_metadata_from(&self, url: &str) -> Result<Value, Box<dyn std::error::Error>> {
         // Construct the endpoint URL by appending `/networkMetadata` to the base URL.
-        let endpoint = format!("{}/networkMetadata", self.url);
-        let response = reqwest::get(&endpoint).await?;
+        let endpoint = format!("{url}/networkMetadata");
+        let response = self.get(&endpoint).await?;
+        // Report the status of an error response, rather than failing to parse its body
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            return Err(RollerError::HttpError { endpoint, status }.into());
+        }
         // Collect the body bytes into a vector
         let body = response.bytes().await?.to_vec();
-        // We're expecting the response to be a JSON object with network IDs as keys
-        let networks: HashMap<String, Network> = serde_json::from_slice(&body)?;
-        // The keys/IDs are the same as the `name` field of the Network,
-        // therefor we can discard them.
-        Ok(networks.values().cloned().collect())
+        let metadata = parse_metadata(&body, &endpoint)?;
+        tracing::info!(
+            endpoint,
+            networks = metadata.as_object().map_or(0, |n| n.len()),
+            "fetched network metadata"
+        );
+        Ok(metadata)
+    }
+
+    /// Checks the health of the API through the `/health` endpoint.
+    ///
+    /// ### Returns
+    /// The `Health` of the API, or an error if the request fails, the API responds
+    /// with an error status or the response cannot be parsed.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn check_api_health(&self) -> Result<Health, Box<dyn std::error::Error>> {
+        let endpoint = format!("{}/health", self.active_endpoint());
+        let response = self.get(&endpoint).await?.error_for_status()?;
+        let body = response.bytes().await?.to_vec();
+        Ok(serde_json::from_slice(&body)?)
     }
 
     /// Establishes a connection to the Server-Sent Events (SSE) stream of the rollup API.
     ///
     /// This will create an `EventSourceClient` using the `/sse` endpoint of the API
-    /// and return a `Stream` of `SSE` events.
+    /// and return a `Stream` of `SSE` events. If an event was received before, the stream
+    /// resumes after it through the `Last-Event-ID` header, see `set_last_event_id`.
     ///
     /// ### Returns
     /// A `StreamResponse` representing the SSE stream, or an error if the connection fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn get_stream(&self) -> Result<StreamResponse, es::Error> {
-        Ok(es::ClientBuilder::for_url(&format!("{}/sse", self.url))?
+        let url = self.active_endpoint();
+        tracing::info!(endpoint = url, "connecting to the event stream");
+        let reconnect = es::ReconnectOptions::reconnect(true)
+            .delay(self.settings.retry_delay)
+            .build();
+        let mut builder = es::ClientBuilder::for_url(&format!("{url}/sse"))?;
+        if let Some(token) = &self.auth_token {
+            builder = builder.header("Authorization", &format!("Bearer {token}"))?;
+        }
+        for (name, value) in &self.headers {
+            let value = value
+                .to_str()
+                .map_err(|e| es::Error::InvalidParameter(Box::new(e)))?;
+            builder = builder.header(name.as_str(), value)?;
+        }
+        if let Some(id) = self.last_event_id() {
+            tracing::info!(id, "resuming the event stream");
+            builder = builder.last_event_id(id);
+        }
+        Ok(builder
+            .connect_timeout(self.settings.connect_timeout)
+            .reconnect(reconnect)
             .build()
             .stream())
     }
+
+    /// Sends a GET request, retrying up to `retries` times after a connection error,
+    /// a timeout or a server error response.
+    ///
+    /// The back-off is linear, the n-th retry waits `retry_delay * n`.
+    ///
+    /// ### Arguments
+    /// * `endpoint` - The URL to request.
+    ///
+    /// ### Returns
+    /// The response of the last attempt, or its error.
+    async fn get(&self, endpoint: &str) -> reqwest::Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            let mut request = self.http.get(endpoint);
+            if let Some(token) = &self.auth_token {
+                request = request.bearer_auth(token);
+            }
+            let result = request.send().await;
+            let retryable = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(_) => true,
+            };
+            if !retryable || attempt >= self.settings.retries {
+                return result;
+            }
+
+            attempt += 1;
+            tracing::warn!(endpoint, attempt, "request failed, retrying");
+            tokio::time::sleep(self.settings.retry_delay * attempt).await;
+        }
+    }
+}
+
+/// An API endpoint validated and normalized by `parse_endpoint`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// The normalized URL, without trailing slashes.
+    pub url: String,
+    /// A notice about an assumption made while normalizing, such as a missing scheme.
+    pub notice: Option<String>,
+}
+
+/// Validates and normalizes the URL of the API.
+///
+/// A bare host is assumed to use https, and trailing slashes are removed so that the
+/// endpoint paths can be appended without producing double slashes.
+///
+/// ### Arguments
+/// * `value` - The endpoint, as passed to `--api-endpoint`.
+///
+/// ### Returns
+/// The normalized endpoint, or a human-readable description of every problem found.
+pub fn parse_endpoint(value: &str) -> Result<Endpoint, String> {
+    let (url, notice) = match value.contains("://") {
+        true => (value.to_string(), None),
+        false => {
+            let url = format!("https://{value}");
+            let notice = format!("The API endpoint has no scheme, assuming {url}");
+            (url, Some(notice))
+        }
+    };
+
+    let mut problems = Vec::new();
+    if url.chars().any(char::is_whitespace) {
+        problems.push(String::from("it contains whitespace"));
+    }
+    match url::Url::parse(&url) {
+        Ok(parsed) if !matches!(parsed.scheme(), "http" | "https") => problems.push(format!(
+            "the scheme `{}` is not supported, use http or https",
+            parsed.scheme()
+        )),
+        Ok(_) => {}
+        Err(e) => problems.push(e.to_string()),
+    }
+    if !problems.is_empty() {
+        return Err(format!(
+            "invalid API endpoint `{value}`: {}",
+            problems.join(", ")
+        ));
+    }
+
+    Ok(Endpoint {
+        url: url.trim_end_matches('/').to_string(),
+        notice,
+    })
+}
+
+/// A custom HTTP header sent to the API, parsed by `parse_header`.
+///
+/// Header values often are credentials, so the value is redacted whenever the header is
+/// formatted or serialized, only its name is shown.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Header {
+    /// The name of the header, lowercase.
+    pub name: HeaderName,
+    /// The value of the header, marked as sensitive.
+    pub value: HeaderValue,
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: <redacted>", self.name)
+    }
+}
+
+impl fmt::Debug for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Header({:?})", self.to_string())
+    }
+}
+
+impl serde::Serialize for Header {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A secret passed on the command line, such as the `--auth-token`.
+///
+/// The value is redacted whenever the secret is formatted, use `expose` to read it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Returns the value of the secret.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl std::str::FromStr for Secret {
+    type Err = std::convert::Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Self(value.to_string()))
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(<redacted>)")
+    }
+}
+
+/// Parses a custom HTTP header, as passed to `--header`.
+///
+/// ### Arguments
+/// * `value` - The header, in the `Name: value` form.
+///
+/// ### Returns
+/// The header, or a description of the problem naming the offending string.
+pub fn parse_header(value: &str) -> Result<Header, String> {
+    let (name, header_value) = value
+        .split_once(':')
+        .ok_or_else(|| format!("invalid header `{value}`: expected `Name: value`"))?;
+    let name = HeaderName::from_bytes(name.trim().as_bytes()).map_err(|_| {
+        format!(
+            "invalid header `{value}`: `{}` is not a valid header name",
+            name.trim()
+        )
+    })?;
+    let mut header_value = HeaderValue::from_str(header_value.trim())
+        .ok()
+        .filter(|v| v.to_str().is_ok())
+        .ok_or_else(|| format!("invalid header `{value}`: the value must be visible ASCII"))?;
+    header_value.set_sensitive(true);
+    Ok(Header {
+        name,
+        value: header_value,
+    })
+}
+
+/// Parses and validates a network metadata document, as returned by the `/networkMetadata` endpoint.
+///
+/// ### Arguments
+/// * `body` - The raw JSON document.
+/// * `source` - The URL or path the document was read from, used in the error messages.
+///
+/// ### Returns
+/// The parsed document, or a human-readable description of the problems found.
+pub fn parse_metadata(body: &[u8], source: &str) -> Result<Value, String> {
+    // Validate the shape of the document first, serde's errors don't point to the offending network
+    let value: Value =
+        serde_json::from_slice(body).map_err(|e| format!("{source} is not valid JSON: {e}"))?;
+    validate_metadata(&value).map_err(|e| format!("unexpected contents of {source}: {e}"))?;
+    Ok(value)
+}
+
+/// Converts a validated network metadata document into the list of networks.
+///
+/// ### Arguments
+/// * `metadata` - The document, see `parse_metadata`.
+/// * `chains` - The registry resolving the names of the parent chains.
+///
+/// ### Returns
+/// The networks of the document, in no particular order.
+pub fn networks_from_metadata(
+    metadata: Value,
+    chains: &ChainRegistry,
+) -> serde_json::Result<Vec<Network>> {
+    // We're expecting the document to be a JSON object with network IDs as keys
+    let networks: HashMap<String, Network> = serde_json::from_value(metadata)?;
+    // The keys/IDs are the same as the `name` field of the Network,
+    // therefor we can discard them.
+    Ok(networks
+        .into_values()
+        .map(|mut network| {
+            network.resolve_parent_chain(chains);
+            network
+        })
+        .collect())
+}
+
+/// The fields every network of the `/networkMetadata` response must provide as strings.
+const REQUIRED_METADATA_FIELDS: [&str; 5] = ["name", "label", "parentChain", "da", "stack"];
+
+/// Validates the shape of the `/networkMetadata` response before deserializing it.
+///
+/// ### Arguments
+/// * `value` - The parsed JSON response.
+///
+/// ### Returns
+/// `Ok` if the response is an object of networks with all the required fields,
+/// or a human-readable description of every problem found.
+pub(crate) fn validate_metadata(value: &Value) -> Result<(), String> {
+    let networks = match value {
+        Value::Object(networks) => networks,
+        Value::Array(_) => {
+            return Err("expected an object keyed by network name, got an array".into())
+        }
+        other => {
+            return Err(format!(
+                "expected an object keyed by network name, got `{other}`"
+            ))
+        }
+    };
+
+    let mut problems = Vec::new();
+    for (id, network) in networks {
+        let Value::Object(fields) = network else {
+            problems.push(format!("network `{id}` is not an object"));
+            continue;
+        };
+
+        for field in REQUIRED_METADATA_FIELDS {
+            match fields.get(field) {
+                Some(Value::String(_)) => {}
+                Some(_) => {
+                    problems.push(format!("network `{id}` has a non-string `{field}` field"))
+                }
+                None => problems.push(format!("network `{id}` is missing the `{field}` field")),
+            }
+        }
+    }
+
+    match problems.is_empty() {
+        true => Ok(()),
+        false => Err(problems.join("; ")),
+    }
 }
@@ -0,0 +1,78 @@
+use crate::Network;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// The file written in place of the clipboard when it can't be accessed, in the home directory.
+pub const FALLBACK_FILE: &str = ".roller_clipboard";
+
+/// The field of the selected network copied by the `copy` key.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum CopyField {
+    /// The label/human-readable name of the network.
+    Label,
+    /// The name of the network, as used by the API and the filters.
+    #[default]
+    Name,
+    /// The block explorer URL of the network.
+    Explorer,
+    /// The network and its latest data as a JSON object, like the items of `--format json`.
+    Json,
+}
+
+impl CopyField {
+    /// Returns the text copied for a network.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to copy the field of.
+    ///
+    /// ### Returns
+    /// The text, or `None` if the network has no explorer URL.
+    pub fn value(&self, network: &Network) -> Option<String> {
+        match self {
+            CopyField::Label => Some(network.label.clone()),
+            CopyField::Name => Some(network.name.clone()),
+            CopyField::Explorer => network.explorer.clone().filter(|url| !url.is_empty()),
+            CopyField::Json => serde_json::to_string(network).ok(),
+        }
+    }
+}
+
+/// Where copied text ended up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Copied {
+    /// The system clipboard.
+    Clipboard,
+    /// The fallback file, since the clipboard couldn't be accessed.
+    File(PathBuf),
+}
+
+/// Copies text to the system clipboard, falling back to `~/.roller_clipboard` where the clipboard
+/// can't be accessed, such as on a headless Linux without a display server.
+///
+/// ### Arguments
+/// * `text` - The text to copy.
+///
+/// ### Returns
+/// Where the text was copied to, or an error if neither the clipboard nor the file could be written.
+pub fn copy(text: &str) -> Result<Copied, String> {
+    let copied = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text));
+    match copied {
+        Ok(()) => Ok(Copied::Clipboard),
+        Err(e) => {
+            tracing::debug!(error = %e, "failed to access the clipboard, writing the fallback file");
+            let path = fallback_path().ok_or("no clipboard or home directory available")?;
+            std::fs::write(&path, text)
+                .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+            Ok(Copied::File(path))
+        }
+    }
+}
+
+/// Returns the path of the fallback file, in the home directory of the user.
+pub fn fallback_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(FALLBACK_FILE))
+}
@@ -0,0 +1,457 @@
+use crate::{
+    chain_emoji,
+    format::{self, RateUnit, Units},
+    stack_color, DaRegistry, HeaderStyle, Headers, Level, Network, Theme, Thresholds,
+};
+use clap::ValueEnum;
+use ratatui::{
+    layout::Constraint,
+    style::Style,
+    widgets::{BorderType, Cell},
+};
+use std::time::Duration;
+
+/// The width of the bar of the "Age" column, in characters.
+const AGE_BAR_WIDTH: u8 = 8;
+
+/// Options affecting how the cells of the network table are formatted and styled.
+#[derive(Debug, Clone)]
+pub struct DisplayOptions {
+    /// The pending transaction count above which the `Pending` cell is highlighted.
+    pub pending_warn: u64,
+    /// The styles used by the cells.
+    pub theme: Theme,
+    /// The time after which the last update of a network is considered stale, dimming its row.
+    /// `None` disables the staleness handling.
+    pub stale_after: Option<Duration>,
+    /// The border drawn around the blocks of the interface.
+    pub border_type: BorderType,
+    /// The text style of the header row of the table.
+    pub header_style: HeaderStyle,
+    /// The number of decimal places of the metrics.
+    pub precision: usize,
+    /// How the metrics and totals are written, toggled with `u`.
+    pub units: Units,
+    /// The time unit the rates are displayed per, toggled with `m`, see `Column::is_rate`.
+    pub rate_unit: RateUnit,
+    /// The number of updates with an unchanged block number after which the `Block` cell is
+    /// flagged, and highlighted as a warning after three times as many.
+    pub stale_blocks: u32,
+    /// The number of blank characters between the columns of the table.
+    pub column_spacing: u16,
+    /// Whether the detail pane shows a QR code of the explorer URL.
+    pub qr_code: bool,
+    /// Whether the `Settlement` cells are prefixed with the emoji of the chain.
+    pub emoji: bool,
+    /// Whether the `Stack` cells are colored by stack, see `stack_color`.
+    pub stack_colors: bool,
+    /// The absolute z-score from which a metric is flagged as an anomaly, `None` disabling the check.
+    pub anomaly_z: Option<f32>,
+    /// Whether the labels too long for the `Network` column wrap onto more lines rather than
+    /// being truncated.
+    pub wrap_labels: bool,
+    /// The display names of the data availability layers.
+    pub da: DaRegistry,
+    /// The labels of the header row, see `Headers::label`.
+    pub headers: Headers,
+    /// The thresholds from which the metrics are styled as a caution, then as a warning.
+    pub thresholds: Thresholds,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            pending_warn: 100_000,
+            theme: Theme::default(),
+            stale_after: Some(Duration::from_secs(60)),
+            border_type: BorderType::Rounded,
+            header_style: HeaderStyle::default(),
+            precision: format::DEFAULT_PRECISION,
+            units: Units::default(),
+            rate_unit: RateUnit::default(),
+            stale_blocks: 10,
+            column_spacing: 1,
+            qr_code: true,
+            emoji: true,
+            stack_colors: true,
+            anomaly_z: Some(3.0),
+            wrap_labels: false,
+            da: DaRegistry::default(),
+            headers: Headers::default(),
+            thresholds: Thresholds::default(),
+        }
+    }
+}
+
+impl DisplayOptions {
+    /// Returns the header of a column in the table.
+    ///
+    /// ### Arguments
+    /// * `column` - The column.
+    ///
+    /// ### Returns
+    /// The label of the `[headers]` section, or the built-in header of the column in the
+    /// `rate_unit`, see `Column::rate_header`.
+    pub fn header(&self, column: Column) -> &str {
+        self.headers
+            .get(column)
+            .unwrap_or(column.rate_header(self.rate_unit))
+    }
+}
+
+/// The named column layouts of `--preset`, in place of a `--columns` list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, serde::Serialize)]
+pub enum ColumnPreset {
+    /// The network, its throughput and its block.
+    Minimal,
+    /// The network and its gas usage.
+    Economic,
+    /// Every column.
+    Full,
+}
+
+/// The columns of the presets, in order, see `ColumnPreset::columns`.
+const COLUMN_PRESETS: [(ColumnPreset, &[Column]); 3] = [
+    (
+        ColumnPreset::Minimal,
+        &[Column::Network, Column::Tps, Column::Block],
+    ),
+    (
+        ColumnPreset::Economic,
+        &[Column::Network, Column::Gps, Column::GasPerTx],
+    ),
+    (
+        ColumnPreset::Full,
+        &[
+            Column::Network,
+            Column::Name,
+            Column::Block,
+            Column::Tps,
+            Column::Gps,
+            Column::Dps,
+            Column::Stack,
+            Column::Da,
+            Column::Settlement,
+            Column::Pending,
+            Column::GasPerTx,
+            Column::GasCost,
+            Column::Age,
+        ],
+    ),
+];
+
+impl ColumnPreset {
+    /// Returns the columns of the preset, in order.
+    pub fn columns(&self) -> Vec<Column> {
+        COLUMN_PRESETS
+            .iter()
+            .find(|(preset, _)| preset == self)
+            .map(|(_, columns)| columns.to_vec())
+            .unwrap_or_default()
+    }
+}
+
+/// Describes a single column of the network table.
+///
+/// The same descriptors are used by the TUI and the plain-text output modes,
+/// so the headers, order and cell values stay consistent between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum, serde::Serialize)]
+pub enum Column {
+    /// The label/human-readable name of the network.
+    Network,
+    /// The name of the network, as used by the API and the `--include` and `--exclude` filters.
+    Name,
+    /// The current block number of the network.
+    Block,
+    /// Transactions Per Second (TPS).
+    Tps,
+    /// Gas Per Second (GPS).
+    Gps,
+    /// Data Per Second (DPS).
+    Dps,
+    /// The stack used by the network.
+    Stack,
+    /// The data availability layer of the network.
+    Da,
+    /// The parent chain, used as a Settlement Layer.
+    Settlement,
+    /// The number of transactions waiting in the mempool.
+    Pending,
+    /// The average gas used by a transaction, computed from the GPS and TPS.
+    GasPerTx,
+    /// The estimated cost of the gas used per second in USD, from the static `[prices]` of the
+    /// `--config` file.
+    GasCost,
+    /// The time since the last update, drawn as a bar turning from green to red over 30 seconds.
+    Age,
+}
+
+impl Column {
+    /// The default set and order of columns, matching the original table layout.
+    pub const DEFAULT: [Column; 8] = [
+        Column::Network,
+        Column::Block,
+        Column::Tps,
+        Column::Gps,
+        Column::Dps,
+        Column::Stack,
+        Column::Da,
+        Column::Settlement,
+    ];
+
+    /// Returns the header label of the column.
+    pub fn header(&self) -> &'static str {
+        match self {
+            Column::Network => "Network",
+            Column::Name => "Name",
+            Column::Block => "Block",
+            Column::Tps => "TPS",
+            Column::Gps => "MGas/s",
+            Column::Dps => "KB/s",
+            Column::Stack => "Stack",
+            Column::Da => "DA",
+            Column::Settlement => "Settlement",
+            Column::Pending => "Pending",
+            Column::GasPerTx => "KGas/tx",
+            Column::GasCost => "USD/s",
+            Column::Age => "Age",
+        }
+    }
+
+    /// Returns the header of the column with its rate in a time unit, see `is_rate`.
+    ///
+    /// ### Arguments
+    /// * `rate` - The time unit the rates are displayed per.
+    ///
+    /// ### Returns
+    /// The header, e.g. "TPM" for the `Tps` column per minute, or `header` for other columns.
+    pub fn rate_header(&self, rate: RateUnit) -> &'static str {
+        match (self, rate) {
+            (Column::Tps, RateUnit::Minute) => "TPM",
+            (Column::Gps, RateUnit::Minute) => "MGas/min",
+            (Column::Dps, RateUnit::Minute) => "KB/min",
+            (Column::GasCost, RateUnit::Minute) => "USD/min",
+            _ => self.header(),
+        }
+    }
+
+    /// Checks whether the column displays a rate, received per second and displayed per `RateUnit`.
+    pub fn is_rate(&self) -> bool {
+        matches!(
+            self,
+            Column::Tps | Column::Gps | Column::Dps | Column::GasCost
+        )
+    }
+
+    /// Checks whether the column displays a metric received through SSE, rather than metadata.
+    pub fn is_metric(&self) -> bool {
+        matches!(
+            self,
+            Column::Block
+                | Column::Tps
+                | Column::Gps
+                | Column::Dps
+                | Column::Pending
+                | Column::GasPerTx
+                | Column::GasCost
+        )
+    }
+
+    /// Returns the width constraint of the column when rendered in the TUI.
+    pub fn width(&self) -> Constraint {
+        match self {
+            Column::Network | Column::Name | Column::Stack => Constraint::Percentage(15),
+            Column::Age => Constraint::Length(AGE_BAR_WIDTH as u16),
+            _ => Constraint::Percentage(10),
+        }
+    }
+
+    /// Returns the cell value of the column for the provided network, formatted for display.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to extract the value from.
+    /// * `precision` - The number of decimal places of the metrics.
+    /// * `units` - How the metrics are written, in full or abbreviated.
+    ///
+    /// ### Returns
+    /// The formatted value of the cell.
+    pub fn value(&self, network: &Network, precision: usize, units: Units) -> String {
+        let data = network.data.to_owned().unwrap_or_default();
+        match self {
+            Column::Tps => format::number(data.tps as f64, precision, units),
+            Column::Gps => format::number(data.gps as f64, precision, units),
+            Column::Dps => format::number(data.dps as f64, precision, units),
+            Column::GasPerTx => format::number(data.gas_per_tx() as f64, precision, units),
+            Column::Pending if Self::pending(network).is_none() => String::from("-"),
+            Column::GasCost => match network.gas_cost_usd() {
+                Some(cost) => format::number(cost, precision, units),
+                None => String::from("—"),
+            },
+            Column::Age => match Self::age(network) {
+                Some(age) => format::age_bar(age, AGE_BAR_WIDTH),
+                None => String::from("-"),
+            },
+            // L1s settle and publish their data on their own chain
+            Column::Settlement | Column::Da if network.is_l1() => String::from("—"),
+            _ => self.raw(network),
+        }
+    }
+
+    /// Returns the cell value of the column like `value`, with the rates converted to a time unit.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to extract the value from.
+    /// * `precision` - The number of decimal places of the metrics.
+    /// * `units` - How the metrics are written, in full or abbreviated.
+    /// * `rate` - The time unit the rates are displayed per, see `is_rate`.
+    ///
+    /// ### Returns
+    /// The formatted value of the cell.
+    pub fn rate_value(
+        &self,
+        network: &Network,
+        precision: usize,
+        units: Units,
+        rate: RateUnit,
+    ) -> String {
+        if rate == RateUnit::Second || !self.is_rate() {
+            return self.value(network, precision, units);
+        }
+
+        let data = network.data.to_owned().unwrap_or_default();
+        let per_second = match self {
+            Column::Tps => Some(data.tps as f64),
+            Column::Gps => Some(data.gps as f64),
+            Column::Dps => Some(data.dps as f64),
+            _ => network.gas_cost_usd(),
+        };
+        match per_second {
+            Some(value) => format::number(value * rate.factor(), precision, units),
+            None => String::from("—"),
+        }
+    }
+
+    /// Returns the styled table cell of the column for the provided network.
+    ///
+    /// The `Block` cell is prefixed with "⚠" when the block number hasn't changed for more than
+    /// `stale_blocks` updates, styled as a caution and as a warning past three times as many.
+    /// The `DA` cell displays the name of the layer in the registry, colored by layer, and the
+    /// `Settlement` cell is prefixed with the emoji of known chains unless disabled. The `Stack`
+    /// cell is colored by stack unless disabled or without colors.
+    /// The metrics are styled as a caution from their warn threshold, and as a warning from their high one.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to extract the value from.
+    /// * `options` - The display options used to style the cell.
+    ///
+    /// ### Returns
+    /// The `Cell` containing the formatted value.
+    pub fn cell(&self, network: &Network, options: &DisplayOptions) -> Cell<'static> {
+        let (content, style) = self.styled_value(network, options);
+        Cell::from(content).style(style)
+    }
+
+    /// Returns the content and the style of the table cell of the column, see `cell`.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to extract the value from.
+    /// * `options` - The display options used to style the cell.
+    pub(crate) fn styled_value(
+        &self,
+        network: &Network,
+        options: &DisplayOptions,
+    ) -> (String, Style) {
+        let value = self.rate_value(network, options.precision, options.units, options.rate_unit);
+        let unchanged = network.block_unchanged_count;
+        match self {
+            Column::Block if unchanged > options.stale_blocks.saturating_mul(3) => {
+                return (format!("⚠ {value}"), options.theme.warning)
+            }
+            Column::Block if unchanged > options.stale_blocks => {
+                return (format!("⚠ {value}"), options.theme.caution)
+            }
+            _ => {}
+        }
+
+        let emoji = match self {
+            Column::Settlement if options.emoji && !network.is_l1() => {
+                chain_emoji(&network.parent_chain)
+            }
+            _ => "",
+        };
+        let content = match emoji {
+            "" => value,
+            emoji => format!("{emoji} {value}"),
+        };
+        match self {
+            Column::Da if !network.is_l1() => {
+                let name = options.da.name(&network.da);
+                let style = options.theme.da(&name);
+                (name, style)
+            }
+            Column::Stack if options.stack_colors && options.theme.color => {
+                (content, Style::new().fg(stack_color(&network.stack)))
+            }
+            Column::Pending if Self::pending(network) > Some(options.pending_warn) => {
+                (content, options.theme.warning)
+            }
+            Column::Age => match Self::age(network) {
+                Some(age) => (content, options.theme.age(age / format::AGE_BAR_SECS)),
+                None => (content, Style::new()),
+            },
+            Column::Tps | Column::Gps | Column::Dps | Column::GasPerTx => {
+                match options.thresholds.level(network, *self) {
+                    Level::High => (content, options.theme.warning),
+                    Level::Warn => (content, options.theme.caution),
+                    Level::Normal => (content, Style::new()),
+                }
+            }
+            _ => (content, Style::new()),
+        }
+    }
+
+    /// Returns the pending transaction count of the network, if known.
+    fn pending(network: &Network) -> Option<u64> {
+        network.data.as_ref().and_then(|d| d.pending_tx_count)
+    }
+
+    /// Returns the time since the last update of the network in seconds, if it was ever updated.
+    fn age(network: &Network) -> Option<f32> {
+        network.updated_at.map(|t| t.elapsed().as_secs_f32())
+    }
+
+    /// Returns the raw value of the column for the provided network, with metrics written
+    /// in full precision. Used by the machine-readable outputs.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to extract the value from.
+    ///
+    /// ### Returns
+    /// The raw value of the cell.
+    pub fn raw(&self, network: &Network) -> String {
+        let data = network.data.to_owned().unwrap_or_default();
+        match self {
+            Column::Network => network.label.clone(),
+            Column::Name => network.name.clone(),
+            Column::Block => data.block_number.to_string(),
+            Column::Tps => data.tps.to_string(),
+            Column::Gps => data.gps.to_string(),
+            Column::Dps => data.dps.to_string(),
+            Column::GasPerTx => data.gas_per_tx().to_string(),
+            Column::Stack => network.stack.clone(),
+            Column::Da => network.da.clone(),
+            Column::Settlement => network.parent_chain.clone(),
+            Column::Pending => data
+                .pending_tx_count
+                .map(|p| p.to_string())
+                .unwrap_or_default(),
+            Column::GasCost => network
+                .gas_cost_usd()
+                .map(|cost| cost.to_string())
+                .unwrap_or_default(),
+            Column::Age => Self::age(network)
+                .map(|age| format::decimal(age as f64, 1))
+                .unwrap_or_default(),
+        }
+    }
+}
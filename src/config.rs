@@ -1,16 +1,837 @@
-use clap::Parser;
+use crate::{
+    clipboard::CopyField,
+    format::Units,
+    history,
+    logging::{LogFormat, LogLevel},
+    output::OutputFormat,
+    parse_endpoint, parse_header, BorderStyle, ChainRegistry, Client, Column, ColumnPreset,
+    Endpoint, Header, HeaderStyle, Health, Layer, Network, Profiles, Secret, Section, Sections,
+    SnapshotWriter, SortingStrategy, UiOutput,
+};
+use clap::{parser::ValueSource, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
 use serde::Serialize;
+use std::{collections::HashMap, error::Error, ffi::OsString, path::PathBuf, time::Duration};
 
 #[derive(Parser, Debug, Serialize)]
-#[clap(version = crate::VERSION, author = "wavefnx @wavefnx")]
+#[clap(
+    version = crate::VERSION,
+    author = "wavefnx @wavefnx",
+    after_help = "Options listing an [env: ...] variable can also be set through the environment. \
+                  Command-line flags take precedence over the environment, which takes precedence over the defaults."
+)]
 /// Terminal interface tracking gas, transactions and data processed by Decentralized Networks.
 pub struct Config {
-    /// Interval in ms to wait between events.
-    /// Increase for lower resource consumption, decrease for more frequent updates.
-    #[clap(long, short = 'i', default_value = "100")]
-    pub interval_ms: u64,
+    /// The subcommand to run instead of the interface, if any.
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+
+    /// Deprecated, use `--input-poll-ms` and `--max-fps` instead.
+    /// Maps onto both: keys are checked every INTERVAL_MS, and the screen redrawn at most as often.
+    #[clap(long, short = 'i', conflicts_with_all = ["input_poll_ms", "max_fps"])]
+    pub interval_ms: Option<u64>,
+
+    /// Interval in ms between checks for key presses, lower values make the keys feel more responsive.
+    /// Events are processed as they arrive, independently of this interval.
+    #[clap(long, default_value = "50", value_parser = clap::value_parser!(u64).range(10..=1000))]
+    pub input_poll_ms: u64,
+
+    /// Maximum number of times per second the screen is redrawn.
+    /// Lower it to reduce the CPU usage, without affecting how responsive the keys feel.
+    #[clap(long, default_value = "30", value_parser = clap::value_parser!(u32).range(1..=120))]
+    pub max_fps: u32,
 
     /// Change the default API Endpoint by specifying a different URL.
-    #[clap(long, default_value = crate::DEFAULT_API_ENDPOINT)]
-    pub api_endpoint: String,
+    /// Repeat it to add fallbacks, tried in order until one serves the metadata, which is then
+    /// also used for the event stream.
+    #[clap(
+        long = "api-endpoint",
+        value_name = "API_ENDPOINT",
+        env = "ROLLER_API_ENDPOINT",
+        default_value = crate::DEFAULT_API_ENDPOINT
+    )]
+    pub api_endpoints: Vec<String>,
+
+    /// Token sent as a bearer `Authorization` header to the API, for endpoints requiring authentication.
+    /// Prefer `--auth-token-file` or the environment variable, which keep the token out of the shell
+    /// history and process list. The flag takes precedence over the file, which takes precedence over
+    /// the environment variable. The deprecated `ROLLER_AUTH_TOKEN` variable is still read, below all of them.
+    #[clap(long, env = "ROLLER_API_TOKEN", hide_env_values = true)]
+    #[serde(skip)]
+    pub auth_token: Option<Secret>,
+
+    /// File containing the token of `--auth-token`, e.g. a systemd credential.
+    /// Surrounding whitespace, such as a trailing newline, is ignored.
+    #[clap(long, value_name = "PATH")]
+    #[serde(skip)]
+    pub auth_token_file: Option<PathBuf>,
+
+    /// Custom HTTP header sent with every request to the API, as `Name: value`.
+    /// Repeat it to send several headers; the environment variable takes one header per line.
+    /// The values are redacted from the logs.
+    #[clap(
+        long = "header",
+        value_name = "HEADER",
+        value_parser = parse_header,
+        env = "ROLLER_HEADERS",
+        value_delimiter = '\n',
+        hide_env_values = true
+    )]
+    pub headers: Vec<Header>,
+
+    /// Columns to display, in order, as a comma-separated list.
+    #[clap(long, value_enum, value_delimiter = ',', default_values_t = Column::DEFAULT)]
+    pub columns: Vec<Column>,
+
+    /// A named layout of columns, in place of --columns: minimal for the network, TPS and block,
+    /// economic for the network, gas per second and gas per transaction, or full for every column.
+    #[clap(long, value_enum, conflicts_with = "columns")]
+    pub preset: Option<ColumnPreset>,
+
+    /// Metric to sort the networks by, in descending order.
+    #[clap(long, value_enum, default_value_t)]
+    pub sort: SortingStrategy,
+
+    /// Only display the networks with the given names, as a comma-separated list.
+    #[clap(long, value_delimiter = ',')]
+    pub include: Vec<String>,
+
+    /// Names of the networks to watch, a shorthand for `--include`.
+    /// A single network opens its detail pane on startup.
+    #[clap(value_name = "NETWORK", conflicts_with = "include")]
+    pub networks: Vec<String>,
+
+    /// Hide the networks with the given names, as a comma-separated list.
+    #[clap(long, value_delimiter = ',')]
+    pub exclude: Vec<String>,
+
+    /// Only display the N busiest networks by the `--sort` metric, 0 displays every network.
+    /// Also limits the rows printed by `--once` and `--follow`.
+    #[clap(long, default_value = "0")]
+    pub top: usize,
+
+    /// Only display the networks of the given layer, L1s have no parent chain to settle to.
+    #[clap(long, value_enum, default_value_t)]
+    pub layer: Layer,
+
+    /// Print a plain-text snapshot of the table once every network has been updated, then exit.
+    /// Does not enter the interactive interface. Implied when stdout is not a terminal.
+    #[clap(long)]
+    pub once: bool,
+
+    /// Maximum time in ms to wait for network updates in `--once` mode.
+    #[clap(long, default_value = "10000")]
+    pub once_timeout_ms: u64,
+
+    /// Output format of the `--once` and `--follow` modes.
+    #[clap(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+
+    /// Also write the snapshot of `--once` to this file, as a self-contained HTML table
+    /// styled like the interface. Implies `--once`.
+    #[clap(long, value_name = "PATH", conflicts_with = "follow")]
+    pub export_html: Option<PathBuf>,
+
+    /// Print a snapshot to stdout after every network update, indefinitely.
+    /// Requires a machine-readable `--format`. The interface keeps running on stderr when stdout
+    /// is redirected while stderr is a terminal, see `--ui-output`, redirect both to run without it.
+    #[clap(long, conflicts_with = "once")]
+    pub follow: bool,
+
+    /// Add the "Pending" column with the mempool depth of each network to the table.
+    /// Disabled by default due to width constraints.
+    #[clap(long)]
+    pub pending_txs: bool,
+
+    /// Add the "Age" column to the table, drawing the time since the last update of each network
+    /// as a bar turning from green to red over 30 seconds.
+    #[clap(long)]
+    pub age_bar: bool,
+
+    /// Pending transaction count above which the "Pending" column is highlighted.
+    #[clap(long, default_value = "100000")]
+    pub pending_warn: u64,
+
+    /// Number of consecutive updates with an unchanged block number after which the block is flagged.
+    /// Highlighted as a warning after three times as many.
+    #[clap(long, default_value = "10")]
+    pub stale_blocks: u32,
+
+    /// Time without updates after which a network is considered stale, dimming its row and leaving
+    /// it out of the active networks, e.g. "90s". At least 5 seconds, "off" or "0" disables it.
+    #[clap(long, default_value = "60s", value_parser = parse_stale_threshold)]
+    pub stale_threshold: Duration,
+
+    /// Flag the networks whose transactions, gas or data per second are this many standard
+    /// deviations away from the mean of their last 30 updates with "⚡", e.g. "2.5". 0 disables it.
+    #[clap(long, default_value = "3", value_parser = parse_anomaly_z)]
+    pub anomaly_z: f32,
+
+    /// Wrap the network labels too long for their column onto more lines, rather than
+    /// truncating them with "…".
+    #[clap(long)]
+    pub wrap_labels: bool,
+
+    /// Disable colors, using only bold, dim and reversed text.
+    /// Also enabled by a non-empty `NO_COLOR` environment variable or a terminal without RGB color support.
+    #[clap(long)]
+    pub no_color: bool,
+
+    /// Don't draw a QR code of the explorer URL in the detail pane,
+    /// for terminals that render block characters poorly.
+    #[clap(long)]
+    pub no_qr: bool,
+
+    /// Don't prefix the settlement chains with their emoji, e.g. "⟠ ethereum",
+    /// for terminals that don't render emoji.
+    #[clap(long)]
+    pub no_emoji: bool,
+
+    /// Don't color the "Stack" column by stack, e.g. red for the OP Stack and blue for Arbitrum Nitro.
+    #[clap(long)]
+    pub no_stack_colors: bool,
+
+    /// Number of decimal places of the metrics in the table, the detail pane and the totals.
+    /// The machine-readable formats always use full precision.
+    #[clap(long, default_value = "2", value_parser = clap::value_parser!(u8).range(0..=6))]
+    pub precision: u8,
+
+    /// How the metrics and totals are written for humans: in full, abbreviated with short-scale
+    /// suffixes (1.23M, 4.56B) or with SI prefixes (1.23M, 4.56G). Toggled at runtime with `u`.
+    /// The JSON and CSV formats always write them in full.
+    #[clap(long, value_enum, default_value_t)]
+    pub units: Units,
+
+    /// Capture the mouse, scrolling the table with the wheel and describing the key hints of the
+    /// info bar on hover. Disables the native text selection of the terminal while roller runs.
+    #[clap(long)]
+    pub mouse: bool,
+
+    /// Draw the interface in this many lines below the cursor rather than the whole screen, keeping
+    /// the scrollback of the shell visible, e.g. 12 for a small ticker. At least 8 lines.
+    #[clap(long, value_name = "HEIGHT", value_parser = clap::value_parser!(u16).range(8..))]
+    pub inline: Option<u16>,
+
+    /// Stream the interface is drawn to. Defaults to stdout, or with `--follow` to stderr when
+    /// stdout is redirected while stderr is a terminal, e.g. `roller --format csv --follow > data.csv`.
+    #[clap(long, value_enum, value_name = "STREAM")]
+    pub ui_output: Option<UiOutput>,
+
+    /// Number of blank characters between the columns of the table.
+    /// 0 fits more of each cell on narrow terminals, 2 to 4 separate the values better on wide ones.
+    #[clap(long, default_value = "1", value_parser = clap::value_parser!(u16).range(0..=4))]
+    pub column_spacing: u16,
+
+    /// Border drawn around the table and the info bar.
+    /// `quad` uses half-block characters, gaining a column of space for the table.
+    #[clap(long, value_enum, default_value_t)]
+    pub border_style: BorderStyle,
+
+    /// Text style of the header row of the table.
+    #[clap(long, value_enum, default_value_t)]
+    pub header_style: HeaderStyle,
+
+    /// Write a JSON snapshot of the networks to this directory every `--snapshot-interval-secs`.
+    #[clap(long)]
+    pub snapshot_dir: Option<PathBuf>,
+
+    /// Interval in seconds between the snapshots written to `--snapshot-dir`.
+    #[clap(long, default_value = "60", value_parser = clap::value_parser!(u64).range(1..))]
+    pub snapshot_interval_secs: u64,
+
+    /// Maximum number of snapshots kept in `--snapshot-dir`, the oldest are removed first.
+    #[clap(long, default_value = "1000", value_parser = clap::value_parser!(u64).range(1..))]
+    pub snapshot_max_files: u64,
+
+    /// Save the recent history of each network on exit and restore it on the next launch, so the
+    /// anomaly detection doesn't start over. Kept in `history.json` next to the default
+    /// configuration file, a history older than an hour is discarded.
+    #[clap(long)]
+    pub persist_history: bool,
+
+    /// Verify the configuration, the metadata endpoint and the SSE stream, report the results and exit.
+    /// Exits with 0 on success, 3 if the metadata can't be retrieved and 4 if the stream fails.
+    #[clap(long, conflicts_with_all = ["once", "follow"])]
+    pub check: bool,
+
+    /// Write diagnostic logs to the given file. Logging is disabled without it.
+    #[clap(long)]
+    pub log_file: Option<PathBuf>,
+
+    /// Minimum level of the logs written to `--log-file`.
+    #[clap(long, value_enum, default_value_t)]
+    pub log_level: LogLevel,
+
+    /// Format of the logs written to `--log-file`.
+    #[clap(long, value_enum, default_value_t)]
+    pub log_format: LogFormat,
+
+    /// Don't warn when the major version of the API differs from the expected one.
+    #[clap(long)]
+    pub skip_version_check: bool,
+
+    /// Maximum time to establish a connection to the API, e.g. "10s" or "500ms".
+    #[clap(long, default_value = "10s", value_parser = parse_timeout)]
+    pub connect_timeout: Duration,
+
+    /// Maximum time for a request to the API to complete, e.g. "30s".
+    /// Doesn't apply to the SSE stream, which stays open indefinitely.
+    #[clap(long, default_value = "30s", value_parser = parse_timeout)]
+    pub request_timeout: Duration,
+
+    /// Number of times a request is retried after a connection or server error, 0 disables retries.
+    #[clap(long, default_value = "3")]
+    pub retries: u32,
+
+    /// Time to wait before retrying a failed request or reconnecting to the SSE stream, e.g. "1s".
+    /// Grows linearly with each retry of a request.
+    #[clap(long, default_value = "1s", value_parser = parse_duration)]
+    pub retry_delay: Duration,
+
+    /// Record the network metadata and the SSE events of the session to a newline-delimited JSON file.
+    #[clap(long, conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Replay a session recorded with `--record` instead of connecting to the API.
+    #[clap(long)]
+    pub replay: Option<PathBuf>,
+
+    /// Playback speed of `--replay`, e.g. 5 replays the session five times as fast.
+    #[clap(long, default_value = "1", requires = "replay", value_parser = parse_speed)]
+    pub replay_speed: f64,
+
+    /// Network metadata file used by `--replay` when the recording doesn't embed it.
+    /// Expects the format of the `/networkMetadata` endpoint.
+    #[clap(long, requires = "replay")]
+    pub metadata: Option<PathBuf>,
+
+    /// File of `id = "name"` lines mapping chain IDs to the names of the "Settlement" column,
+    /// overriding and extending the built-in chains.
+    #[clap(long, env = "ROLLER_CHAINS")]
+    pub chains: Option<PathBuf>,
+
+    /// Configuration file whose `[keys]` section of `action = "key"` lines rebinds the keys of the
+    /// interface, e.g. `sort_tps = "ctrl+t"`. Actions missing from the section keep their default keys.
+    /// Its `[da]` section of `name = "display name"` lines renames data availability layers,
+    /// e.g. `blobs = "Ethereum (blob)"`. Its `[prices]` section of `network = [gas price in gwei,
+    /// token price in USD]` lines sets the static prices of the "USD/s" column, e.g. `base = [0.005, 3400]`.
+    /// Its `[thresholds]` section of `metric = { warn = number, high = number }` lines colors the
+    /// metrics from these values, e.g. `tps = { warn = 1.0, high = 20.0 }`, overridden for a network
+    /// by a `[thresholds.networks.NAME]` section. Its `[headers]` section of `column = "label"` lines
+    /// relabels the header row of the table, e.g. `network = "Red"`.
+    /// Lines before any section set options by the name of their flag, e.g. `top = 5`, below the
+    /// environment and the command line, and `[profile.NAME]` sections override them for `--profile`.
+    #[clap(long, env = "ROLLER_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Profile of the `--config` file to apply over its top-level options, e.g. "staging".
+    #[clap(long, env = "ROLLER_PROFILE", requires = "config")]
+    pub profile: Option<String>,
+
+    /// Field of the selected network copied to the clipboard by the `c` key.
+    /// Where the clipboard can't be accessed, it's written to `~/.roller_clipboard` instead.
+    #[clap(long, value_enum, default_value_t)]
+    pub copy_field: CopyField,
+
+    /// Serve the metrics of the networks and the event stream on `localhost:<PORT>/metrics`,
+    /// in the Prometheus exposition format, while the interface or `--follow` runs.
+    #[cfg(feature = "prometheus")]
+    #[clap(long, value_name = "PORT")]
+    pub prometheus_port: Option<u16>,
+
+    /// Interval to re-fetch the network metadata at, adding newly launched networks to the table,
+    /// e.g. "5m", counted down on the footer of the table. At least 30 seconds, "off" or "0"
+    /// disables it. Ignored by `--replay`.
+    #[clap(long, default_value = "off", value_parser = parse_refresh_interval)]
+    pub refresh_metadata: Duration,
+
+    /// Where the value of each option was taken from, by argument id, see `source`.
+    /// Only known when parsed through `load` or `try_load_from`.
+    #[clap(skip)]
+    #[serde(skip)]
+    pub sources: HashMap<String, ValueSource>,
+
+    /// The options set by the `--config` file, by argument id, with the profile setting them.
+    #[clap(skip)]
+    #[serde(skip)]
+    pub file_sources: HashMap<String, Option<String>>,
+}
+
+/// The subcommands of roller, which run instead of the interface and exit.
+#[derive(Subcommand, Debug, Serialize)]
+pub enum Command {
+    /// Print the available networks, sorted by name, and exit.
+    List(ListArgs),
+    /// Write a commented configuration file with the default of every option, and exit.
+    Init(InitArgs),
+}
+
+/// The arguments of `roller init`.
+#[derive(Args, Debug, Serialize)]
+pub struct InitArgs {
+    /// File to write, instead of `roller/config.toml` in `$XDG_CONFIG_HOME` or `~/.config`.
+    #[clap(long)]
+    pub path: Option<PathBuf>,
+
+    /// Overwrite the file if it already exists.
+    #[clap(long)]
+    pub force: bool,
+}
+
+/// The arguments of `roller list`.
+#[derive(Args, Debug, Serialize)]
+pub struct ListArgs {
+    /// Output format of the list.
+    #[clap(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+
+    /// Only list the networks built with the given stack, e.g. "op".
+    #[clap(long)]
+    pub stack: Option<String>,
+
+    /// Only list the networks using the given data availability layer, e.g. "ethereum".
+    #[clap(long)]
+    pub da: Option<String>,
+
+    /// Only list the networks settling to the given chain, e.g. "base".
+    #[clap(long)]
+    pub settlement: Option<String>,
+}
+
+impl ListArgs {
+    /// Checks whether a network passes the metadata filters, ignoring case.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to check.
+    ///
+    /// ### Returns
+    /// `true` if the network should be listed.
+    pub fn matches(&self, network: &Network) -> bool {
+        let matches = |filter: &Option<String>, value: &str| {
+            filter
+                .as_ref()
+                .is_none_or(|f| f.eq_ignore_ascii_case(value))
+        };
+        matches(&self.stack, &network.stack)
+            && matches(&self.da, &network.da)
+            && matches(&self.settlement, &network.parent_chain)
+    }
+}
+
+impl Config {
+    /// Parses the configuration from the command line and the environment, exiting on invalid
+    /// arguments like `parse`, while remembering where each value was taken from.
+    pub fn load() -> Self {
+        Self::try_load_from(std::env::args_os()).unwrap_or_else(|e| e.exit())
+    }
+
+    /// Parses the configuration from the given arguments, the environment and the options of the
+    /// `--config` file, remembering where each value was taken from, see `source`.
+    ///
+    /// The arguments are parsed a first time to find the file and the profile, then again with
+    /// the options of the file resolved by `Profiles::merge` placed before them.
+    ///
+    /// ### Arguments
+    /// * `args` - The arguments, starting with the name of the binary.
+    ///
+    /// ### Returns
+    /// The configuration, or the error of the first invalid argument or option of the file.
+    pub fn try_load_from<I, T>(args: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+        let config = Self::try_load_args(args.clone())?;
+        let Some(path) = &config.config else {
+            return Ok(config);
+        };
+
+        let invalid = |e: String| clap::Error::raw(clap::error::ErrorKind::InvalidValue, e + "\n");
+        let layered = Profiles::load(path)
+            .and_then(|profiles| profiles.merge(config.profile.as_deref(), |id| config.is_set(id)))
+            .map_err(invalid)?;
+        let file_args = layered
+            .iter()
+            .flat_map(|l| l.args.iter().map(OsString::from));
+        let mut merged: Vec<OsString> = args.iter().take(1).cloned().collect();
+        merged.extend(file_args);
+        merged.extend(args.into_iter().skip(1));
+
+        let mut config = Self::try_load_args(merged)?;
+        config.file_sources = layered.into_iter().map(|l| (l.id, l.profile)).collect();
+        Ok(config)
+    }
+
+    /// Parses the configuration from the given arguments and the environment, remembering
+    /// where each value was taken from.
+    fn try_load_args(args: Vec<OsString>) -> Result<Self, clap::Error> {
+        let matches = Self::command().try_get_matches_from(args)?;
+        let mut config = Self::from_arg_matches(&matches)?;
+        config.sources = matches
+            .ids()
+            .filter_map(|id| Some((id.to_string(), matches.value_source(id.as_str())?)))
+            .collect();
+        Ok(config)
+    }
+
+    /// Checks whether an option was given on the command line, through the environment or by the
+    /// `--config` file.
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the option, the name of its field.
+    pub fn is_set(&self, id: &str) -> bool {
+        matches!(
+            self.sources.get(id),
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+        )
+    }
+
+    /// Describes where the value of an option was taken from, for the messages of `validate`.
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the option, the name of its field.
+    ///
+    /// ### Returns
+    /// The flag of the option, e.g. "--top", its environment variable if taken from it,
+    /// "--top of [profile.staging]" if set by the config file, or "the default of --top" if left
+    /// to the default.
+    pub fn source(&self, id: &str) -> String {
+        let command = Self::command();
+        let arg = command.get_arguments().find(|arg| arg.get_id() == id);
+        let flag = match arg {
+            Some(arg) => match (arg.get_long(), arg.get_value_names()) {
+                (Some(long), _) => format!("--{long}"),
+                (None, Some([name, ..])) => name.to_string(),
+                (None, _) => id.to_string(),
+            },
+            None => id.to_string(),
+        };
+        if let Some(profile) = self.file_sources.get(id) {
+            return match profile {
+                Some(profile) => format!("{flag} of [profile.{profile}]"),
+                None => format!("{flag} of the config file"),
+            };
+        }
+        match self.sources.get(id) {
+            Some(ValueSource::EnvVariable) => arg
+                .and_then(|arg| arg.get_env())
+                .map_or(flag, |env| env.to_string_lossy().into_owned()),
+            Some(ValueSource::DefaultValue) => format!("the default of {flag}"),
+            _ => flag,
+        }
+    }
+
+    /// Checks whether a network passes the `--include`, `--exclude` and `--layer` filters,
+    /// with the positional networks acting as `--include`.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to check.
+    ///
+    /// ### Returns
+    /// `true` if the network should be displayed.
+    pub fn is_included(&self, network: &Network) -> bool {
+        let include = self.watchlist();
+        (include.is_empty() || include.contains(&network.name))
+            && !self.exclude.contains(&network.name)
+            && self.layer.matches(network)
+    }
+
+    /// Returns the columns to display, in order, those of the `--preset` if any or the `--columns`.
+    pub fn columns(&self) -> Vec<Column> {
+        match self.preset {
+            Some(preset) => preset.columns(),
+            None => self.columns.clone(),
+        }
+    }
+
+    /// Returns the names of the networks to display, given either positionally or through `--include`.
+    pub fn watchlist(&self) -> &[String] {
+        match self.networks.is_empty() {
+            true => &self.include,
+            false => &self.networks,
+        }
+    }
+
+    /// Looks for watched networks that aren't part of the metadata, likely misspelled.
+    ///
+    /// ### Arguments
+    /// * `networks` - The networks of the metadata, before filtering.
+    ///
+    /// ### Returns
+    /// A warning message listing the unknown names, or `None` if every name is known.
+    pub fn unknown_networks_check(&self, networks: &[Network]) -> Option<String> {
+        let unknown: Vec<&str> = self
+            .watchlist()
+            .iter()
+            .filter(|name| !networks.iter().any(|n| n.name == **name))
+            .map(String::as_str)
+            .collect();
+        if unknown.is_empty() {
+            return None;
+        }
+
+        Some(format!("Unknown networks: {}.", unknown.join(", ")))
+    }
+
+    /// Returns the stream the interface is drawn to, see `--ui-output`.
+    ///
+    /// ### Returns
+    /// The stream set by `--ui-output`, otherwise the one detected by `UiOutput::detect` along
+    /// `--follow`, which leaves stdout to its records, otherwise stdout.
+    pub fn ui_output(&self) -> UiOutput {
+        match (self.ui_output, self.follow) {
+            (Some(output), _) => output,
+            (None, true) => UiOutput::detect(),
+            (None, false) => UiOutput::Stdout,
+        }
+    }
+
+    /// Returns the file the history of the networks is persisted to with `--persist-history`.
+    ///
+    /// ### Returns
+    /// The file, or `None` if persisting is disabled or no configuration directory is known.
+    pub fn history_path(&self) -> Option<PathBuf> {
+        self.persist_history.then(history::default_path).flatten()
+    }
+
+    /// Creates the snapshot writer configured by `--snapshot-dir`, if set.
+    ///
+    /// ### Returns
+    /// The writer, `None` if snapshots are disabled, or an error if the directory can't be created.
+    pub fn snapshot_writer(&self) -> std::io::Result<Option<SnapshotWriter>> {
+        self.snapshot_dir
+            .clone()
+            .map(|dir| {
+                SnapshotWriter::new(
+                    dir,
+                    Duration::from_secs(self.snapshot_interval_secs),
+                    self.snapshot_max_files as usize,
+                )
+            })
+            .transpose()
+    }
+
+    /// Creates the chain registry, with the mappings of `--chains` if set.
+    ///
+    /// ### Returns
+    /// The registry, or an error naming the line and id of an invalid mapping.
+    pub fn chain_registry(&self) -> Result<ChainRegistry, String> {
+        match &self.chains {
+            Some(path) => ChainRegistry::load(path),
+            None => Ok(ChainRegistry::default()),
+        }
+    }
+
+    /// Resolves the token sent to the API, from `--auth-token`, `--auth-token-file`,
+    /// `ROLLER_API_TOKEN` or the deprecated `ROLLER_AUTH_TOKEN`, in order of precedence.
+    ///
+    /// ### Returns
+    /// The token, `None` if no source is set, or an error if the file can't be read or is empty.
+    pub fn auth_token(&self) -> Result<Option<String>, String> {
+        let from_env = self.sources.get("auth_token") == Some(&ValueSource::EnvVariable);
+        match (&self.auth_token, &self.auth_token_file) {
+            (Some(token), _) if !from_env => Ok(Some(token.expose().to_string())),
+            (_, Some(path)) => {
+                let token = std::fs::read_to_string(path).map_err(|e| {
+                    format!("failed to read --auth-token-file {}: {e}", path.display())
+                })?;
+                match token.trim() {
+                    "" => Err(format!("--auth-token-file {} is empty", path.display())),
+                    token => Ok(Some(token.to_string())),
+                }
+            }
+            (Some(token), None) => Ok(Some(token.expose().to_string())),
+            (None, None) => Ok(std::env::var(LEGACY_AUTH_TOKEN_ENV)
+                .ok()
+                .filter(|token| !token.is_empty())),
+        }
+    }
+
+    /// Returns a warning if the deprecated `ROLLER_AUTH_TOKEN` is set, naming the variable replacing it.
+    pub fn auth_token_warning(&self) -> Option<String> {
+        std::env::var_os(LEGACY_AUTH_TOKEN_ENV)
+            .map(|_| format!("{LEGACY_AUTH_TOKEN_ENV} is deprecated, use ROLLER_API_TOKEN instead"))
+    }
+
+    /// Parses the sections of `--config` if set, reading the file once, see `Sections`.
+    ///
+    /// ### Returns
+    /// The sections, their defaults without a configuration file, or an error naming the line of an invalid entry.
+    pub fn sections(&self) -> Result<Sections, String> {
+        match &self.config {
+            Some(path) => Sections::load(path),
+            None => Ok(Sections::default()),
+        }
+    }
+
+    /// Returns the interval of `--refresh-metadata`, or `None` if refreshing is disabled.
+    pub fn metadata_refresh_interval(&self) -> Option<Duration> {
+        (!self.refresh_metadata.is_zero() && self.replay.is_none()).then_some(self.refresh_metadata)
+    }
+
+    /// Returns the `--anomaly-z`, or `None` if the anomaly detection is disabled.
+    pub fn anomaly_z(&self) -> Option<f32> {
+        (self.anomaly_z > 0.0).then_some(self.anomaly_z)
+    }
+
+    /// Returns the `--stale-threshold`, or `None` if the staleness handling is disabled.
+    pub fn stale_threshold(&self) -> Option<Duration> {
+        (!self.stale_threshold.is_zero()).then_some(self.stale_threshold)
+    }
+
+    /// Returns the interval between checks for key presses, from `--input-poll-ms` or the
+    /// deprecated `--interval-ms`.
+    pub fn input_poll(&self) -> Duration {
+        let ms = match self.interval_ms {
+            Some(interval) => interval.clamp(10, 1000),
+            None => self.input_poll_ms,
+        };
+        Duration::from_millis(ms)
+    }
+
+    /// Returns the minimum time between two redraws, from `--max-fps` or the deprecated `--interval-ms`,
+    /// which is rounded up to a budget of at most 120 frames per second, as `--max-fps` accepts.
+    pub fn frame_budget(&self) -> Duration {
+        match self.interval_ms {
+            Some(interval) => Duration::from_millis(interval.clamp(1000_u64.div_ceil(120), 1000)),
+            None => Duration::from_secs(1) / self.max_fps,
+        }
+    }
+
+    /// Returns a warning if the deprecated `--interval-ms` is used, naming the options it maps onto.
+    pub fn deprecation_warning(&self) -> Option<String> {
+        self.interval_ms.is_some().then(|| {
+            format!(
+                "--interval-ms is deprecated, use --input-poll-ms {} --max-fps {} instead",
+                self.input_poll().as_millis(),
+                (Duration::from_secs(1).as_millis() / self.frame_budget().as_millis()).max(1)
+            )
+        })
+    }
+
+    /// Validates and normalizes every `--api-endpoint`, see `parse_endpoint`.
+    ///
+    /// ### Returns
+    /// The endpoints in order, or the error of the first invalid one.
+    pub fn endpoints(&self) -> Result<Vec<Endpoint>, String> {
+        self.api_endpoints
+            .iter()
+            .map(|endpoint| parse_endpoint(endpoint))
+            .collect()
+    }
+
+    /// Creates the API client with the configured endpoints, authentication, headers, timeouts
+    /// and retries.
+    ///
+    /// ### Returns
+    /// The `Client`, or an error if an endpoint is invalid or the underlying HTTP client
+    /// can't be initialized.
+    pub fn client(&self) -> Result<Client, Box<dyn Error>> {
+        let mut endpoints = self.endpoints()?.into_iter().map(|e| e.url);
+        let first = endpoints.next().ok_or("no API endpoint configured")?;
+        let mut builder = endpoints
+            .fold(Client::builder(first), |builder, url| builder.fallback(url))
+            .connect_timeout(self.connect_timeout)
+            .request_timeout(self.request_timeout)
+            .retries(self.retries)
+            .retry_delay(self.retry_delay);
+        if let Some(token) = self.auth_token()? {
+            builder = builder.auth_token(token);
+        }
+        for header in &self.headers {
+            builder = builder.header(header.clone());
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Compares the major version reported by the API with `EXPECTED_API_VERSION`.
+    ///
+    /// ### Arguments
+    /// * `health` - The health response of the API.
+    ///
+    /// ### Returns
+    /// A warning message if the major versions differ, or `None` if they match
+    /// or `--skip-version-check` is set.
+    pub fn api_version_check(&self, health: &Health) -> Option<String> {
+        let major = health.version.split('.').next().unwrap_or_default();
+        if self.skip_version_check || major == crate::EXPECTED_API_VERSION {
+            return None;
+        }
+
+        Some(format!(
+            "API version mismatch: expected {}.x, got {major}.x. Some features may not work.",
+            crate::EXPECTED_API_VERSION
+        ))
+    }
+}
+
+/// Parses a human-readable duration, such as "10s", "500ms" or "1m 30s".
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    humantime::parse_duration(value).map_err(|e| e.to_string())
+}
+
+/// The former environment variable of `--auth-token`, read below every other source.
+const LEGACY_AUTH_TOKEN_ENV: &str = "ROLLER_AUTH_TOKEN";
+
+/// The shortest accepted `--refresh-metadata` interval, to avoid hammering the API.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Parses the `--refresh-metadata` interval, where "off" and zero disable refreshing.
+fn parse_refresh_interval(value: &str) -> Result<Duration, String> {
+    if value == "off" {
+        return Ok(Duration::ZERO);
+    }
+
+    match parse_duration(value)? {
+        Duration::ZERO => Ok(Duration::ZERO),
+        interval if interval < MIN_REFRESH_INTERVAL => Err(format!(
+            "the interval must be at least {}, or \"off\"",
+            humantime::format_duration(MIN_REFRESH_INTERVAL)
+        )),
+        interval => Ok(interval),
+    }
+}
+
+/// The shortest accepted `--stale-threshold`, below which networks between two updates would
+/// flicker as stale.
+const MIN_STALE_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Parses the `--stale-threshold`, where "off" and zero disable the staleness handling.
+fn parse_stale_threshold(value: &str) -> Result<Duration, String> {
+    if value == "off" {
+        return Ok(Duration::ZERO);
+    }
+
+    match parse_duration(value)? {
+        Duration::ZERO => Ok(Duration::ZERO),
+        threshold if threshold < MIN_STALE_THRESHOLD => Err(format!(
+            "the threshold must be at least {}, or \"off\"",
+            humantime::format_duration(MIN_STALE_THRESHOLD)
+        )),
+        threshold => Ok(threshold),
+    }
+}
+
+/// Parses the `--anomaly-z`, a non-negative number where zero disables the anomaly detection.
+fn parse_anomaly_z(value: &str) -> Result<f32, String> {
+    match value.parse::<f32>().map_err(|e| e.to_string())? {
+        z if z >= 0.0 && z.is_finite() => Ok(z),
+        _ => Err(String::from("the z-score must be a positive number, or 0")),
+    }
+}
+
+/// Parses the playback speed of `--replay`, which must be a positive number.
+fn parse_speed(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>().map_err(|e| e.to_string())? {
+        speed if speed > 0.0 && speed.is_finite() => Ok(speed),
+        _ => Err(String::from("the speed must be a positive number")),
+    }
+}
+
+/// Parses a human-readable duration, rejecting zero since it would fail every request.
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    match parse_duration(value)? {
+        Duration::ZERO => Err(String::from("the timeout must be greater than zero")),
+        timeout => Ok(timeout),
+    }
 }
@@ -0,0 +1,194 @@
+use crate::{DaRegistry, Headers, KeyMap, PriceRegistry, Thresholds};
+use std::path::Path;
+
+/// The sections of the `--config` file, besides the `[profile.NAME]` ones.
+pub const SECTIONS: [&str; 5] = ["keys", "da", "prices", "thresholds", "headers"];
+
+/// The prefix of the sections holding the options of a profile, followed by its name.
+pub const PROFILE_PREFIX: &str = "profile.";
+
+/// The prefix of the sections overriding the thresholds of a network, followed by its name.
+pub const THRESHOLDS_PREFIX: &str = "thresholds.networks.";
+
+/// Returns the lines of a section of the configuration file.
+///
+/// The file is split into sections by `[name]` headers, like a TOML document. Lines before the
+/// first header belong to the top level, the empty section name. Blank lines and lines starting
+/// with `#` are skipped, the other sections are left to their own parsers.
+///
+/// ### Arguments
+/// * `contents` - The contents of the configuration file.
+/// * `section` - The name of the section, one of `SECTIONS`, a profile section or `""`.
+///
+/// ### Returns
+/// The trimmed lines of the section along with their line numbers, or an error prefixed by the
+/// line number of an unknown section.
+pub(crate) fn section_lines<'a>(
+    contents: &'a str,
+    section: &str,
+) -> Result<Vec<(usize, &'a str)>, String> {
+    let mut current = "";
+    let mut lines = Vec::new();
+    for (line_number, line) in lines_of(contents) {
+        match header(line_number, line)? {
+            Some(name) => current = name,
+            None if current == section => lines.push((line_number, line)),
+            None => {}
+        }
+    }
+    Ok(lines)
+}
+
+/// Returns the names of the profiles of the configuration file, in order of appearance.
+///
+/// ### Arguments
+/// * `contents` - The contents of the configuration file.
+///
+/// ### Returns
+/// The names, without the `profile.` prefix, or an error prefixed by the line number of an
+/// unknown section.
+pub(crate) fn profiles(contents: &str) -> Result<Vec<&str>, String> {
+    subsections(contents, PROFILE_PREFIX)
+}
+
+/// Returns the names of the sections starting with a prefix, in order of appearance.
+///
+/// ### Arguments
+/// * `contents` - The contents of the configuration file.
+/// * `prefix` - The prefix of the sections, `PROFILE_PREFIX` or `THRESHOLDS_PREFIX`.
+///
+/// ### Returns
+/// The names, without the prefix, or an error prefixed by the line number of an unknown section.
+pub(crate) fn subsections<'a>(contents: &'a str, prefix: &str) -> Result<Vec<&'a str>, String> {
+    let mut names = Vec::new();
+    for (line_number, line) in lines_of(contents) {
+        if let Some(name) = header(line_number, line)?.and_then(|h| h.strip_prefix(prefix)) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Returns the trimmed lines with their line numbers, without the blank lines and comments.
+fn lines_of(contents: &str) -> impl Iterator<Item = (usize, &str)> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Parses the name of a section header.
+///
+/// ### Returns
+/// The name of the section, `None` if the line isn't a header, or an error if it's unknown.
+fn header(line_number: usize, line: &str) -> Result<Option<&str>, String> {
+    let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) else {
+        return Ok(None);
+    };
+    let name = name.trim();
+    let subsection = [PROFILE_PREFIX, THRESHOLDS_PREFIX]
+        .iter()
+        .any(|prefix| name.strip_prefix(prefix).is_some_and(|n| !n.is_empty()));
+    match SECTIONS.contains(&name) || subsection {
+        true => Ok(Some(name)),
+        false => Err(format!("{line_number}: unknown section `{line}`")),
+    }
+}
+
+/// Strips the quotes around the value of an entry.
+///
+/// ### Returns
+/// The value between the quotes, or `None` if it isn't a non-empty quoted string.
+pub(crate) fn unquote(value: &str) -> Option<&str> {
+    value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .filter(|value| !value.is_empty())
+}
+
+/// Reads a configuration file.
+///
+/// ### Returns
+/// The contents of the file, or an error naming it if it can't be read.
+pub(crate) fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {e}", path.display()))
+}
+
+/// A part of a configuration file with its own parser, extending the built-in defaults, such as
+/// the `[keys]` section of `--config` or the whole `--chains` file.
+pub trait Section: Default {
+    /// Adds the entries of the section to the current ones.
+    ///
+    /// ### Arguments
+    /// * `contents` - The contents of the whole file, the other sections are ignored.
+    ///
+    /// ### Returns
+    /// `Ok` if every entry is valid, or an error prefixed by the line number of the first invalid one.
+    fn extend_from_str(&mut self, contents: &str) -> Result<(), String>;
+
+    /// Creates the defaults extended by the section of a file read already.
+    ///
+    /// ### Arguments
+    /// * `path` - The file the contents were read from, naming it in the errors.
+    /// * `contents` - The contents of the file, see `extend_from_str` for the format.
+    ///
+    /// ### Returns
+    /// The section, or an error naming the file and line of the first invalid entry.
+    fn from_contents(path: &Path, contents: &str) -> Result<Self, String> {
+        let mut section = Self::default();
+        section
+            .extend_from_str(contents)
+            .map_err(|e| format!("{}:{e}", path.display()))?;
+        Ok(section)
+    }
+
+    /// Reads a file and creates the defaults extended by its section, see `from_contents`.
+    ///
+    /// ### Arguments
+    /// * `path` - The file to read.
+    ///
+    /// ### Returns
+    /// The section, or an error naming the file if it can't be read, or the line of the first invalid entry.
+    fn load(path: &Path) -> Result<Self, String> {
+        Self::from_contents(path, &read(path)?)
+    }
+}
+
+/// The sections of the `--config` file, parsed from a single read of it.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Sections {
+    /// The key map, with the `[keys]` section.
+    pub keys: KeyMap,
+    /// The registry of data availability layers, with the `[da]` section.
+    pub da: DaRegistry,
+    /// The registry of gas and token prices, with the `[prices]` section.
+    pub prices: PriceRegistry,
+    /// The thresholds of the metrics, with the `[thresholds]` sections.
+    pub thresholds: Thresholds,
+    /// The labels of the header row, with the `[headers]` section.
+    pub headers: Headers,
+}
+
+impl Sections {
+    /// Reads a configuration file once and parses each of its sections.
+    ///
+    /// ### Arguments
+    /// * `path` - The configuration file.
+    ///
+    /// ### Returns
+    /// The sections, or an error naming the file if it can't be read, or the line of the first invalid entry.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = read(path)?;
+        Ok(Self {
+            keys: KeyMap::from_contents(path, &contents)?,
+            da: DaRegistry::from_contents(path, &contents)?,
+            prices: PriceRegistry::from_contents(path, &contents)?,
+            thresholds: Thresholds::from_contents(path, &contents)?,
+            headers: Headers::from_contents(path, &contents)?,
+        })
+    }
+}
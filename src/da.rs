@@ -0,0 +1,89 @@
+use crate::config_file::{self, Section};
+use std::collections::HashMap;
+
+/// The data availability layers known without a `[da]` section, as `(name, display name)` pairs.
+/// The names are matched against the API values ignoring case.
+pub(crate) const BUILTIN_DA_LAYERS: [(&str, &str); 11] = [
+    ("ethereum", "Ethereum"),
+    ("blobs", "Ethereum (blob)"),
+    ("ethereum-blobs", "Ethereum (blob)"),
+    ("calldata", "Ethereum (calldata)"),
+    ("ethereum-calldata", "Ethereum (calldata)"),
+    ("celestia", "Celestia"),
+    ("eigenda", "EigenDA"),
+    ("eigen-da", "EigenDA"),
+    ("avail", "Avail"),
+    ("anytrust", "AnyTrust"),
+    ("dac", "DAC"),
+];
+
+/// Maps the data availability layers reported by the API to the names displayed in the "DA" column,
+/// so networks using the same layer read the same whatever the spelling of their metadata.
+///
+/// The registry starts with the built-in layers, which can be overridden or extended by the
+/// `[da]` section of the `--config` file. Unknown layers are displayed as reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaRegistry {
+    names: HashMap<String, String>,
+}
+
+impl Default for DaRegistry {
+    fn default() -> Self {
+        Self {
+            names: BUILTIN_DA_LAYERS
+                .iter()
+                .map(|(name, display)| (name.to_string(), display.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Section for DaRegistry {
+    /// Adds the layers listed in the `[da]` section of a configuration file, overriding the
+    /// display names of the existing ones.
+    ///
+    /// The section holds one `name = "display name"` entry per line, e.g. `blobstream = "Celestia"`,
+    /// where the name may be quoted when it contains spaces. Layers displayed under a known name,
+    /// such as "Celestia", are colored like it.
+    ///
+    /// ### Arguments
+    /// * `contents` - The contents of the configuration file.
+    ///
+    /// ### Returns
+    /// `Ok` if every entry is valid, or an error prefixed by the line number of the first invalid one.
+    fn extend_from_str(&mut self, contents: &str) -> Result<(), String> {
+        for (line_number, line) in config_file::section_lines(contents, "da")? {
+            let Some((name, display)) = line.split_once('=') else {
+                return Err(format!(
+                    "{line_number}: expected `name = \"display name\"`, got `{line}`"
+                ));
+            };
+            let name = config_file::unquote(name).unwrap_or(name.trim());
+            let display = config_file::unquote(display).ok_or_else(|| {
+                format!(
+                    "{line_number}: the display name of `{name}` must be a non-empty quoted string"
+                )
+            })?;
+
+            self.names.insert(name.to_lowercase(), display.to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl DaRegistry {
+    /// Returns the display name of a data availability layer.
+    ///
+    /// ### Arguments
+    /// * `da` - The layer, as reported by the API.
+    ///
+    /// ### Returns
+    /// The display name of the layer, or the layer itself if it isn't listed.
+    pub fn name(&self, da: &str) -> String {
+        self.names
+            .get(&da.trim().to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| da.to_string())
+    }
+}
@@ -0,0 +1,28 @@
+use std::fmt;
+
+/// Errors raised by roller itself, as opposed to the ones propagated from its dependencies.
+///
+/// They're returned boxed like every other error, callers interested in a specific
+/// variant can `downcast_ref` them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RollerError {
+    /// The API kept responding with an unsuccessful status after every retry.
+    HttpError {
+        /// The URL of the failed request.
+        endpoint: String,
+        /// The HTTP status code of the final response.
+        status: u16,
+    },
+}
+
+impl fmt::Display for RollerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RollerError::HttpError { endpoint, status } => {
+                write!(f, "{endpoint} responded with HTTP status {status}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RollerError {}
@@ -0,0 +1,253 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// The decimal prefixes used when abbreviating large quantities.
+const SI_PREFIXES: [&str; 7] = ["", "K", "M", "G", "T", "P", "E"];
+
+/// The short-scale suffixes used when abbreviating large numbers for humans.
+const HUMAN_SUFFIXES: [&str; 5] = ["", "K", "M", "B", "T"];
+
+/// How the numbers displayed to humans are written, see `number`.
+/// The machine-readable formats always write them in full.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum Units {
+    /// In full, e.g. `1234567.00`.
+    Raw,
+    /// Abbreviated with short-scale suffixes, e.g. `1.23M` or `4.56B`.
+    #[default]
+    Human,
+    /// Abbreviated with decimal (SI) prefixes, e.g. `1.23M` or `4.56G`.
+    Si,
+}
+
+impl Units {
+    /// Returns the mode following this one, cycling through raw, human and SI.
+    pub fn next(self) -> Self {
+        match self {
+            Units::Raw => Units::Human,
+            Units::Human => Units::Si,
+            Units::Si => Units::Raw,
+        }
+    }
+}
+
+impl std::fmt::Display for Units {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.to_possible_value().expect("no variant is skipped");
+        f.write_str(name.get_name())
+    }
+}
+
+/// The time unit the rates of the table are displayed per, toggled with `m`.
+/// The rates are always received and kept per second, only their display changes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RateUnit {
+    /// Per second, as reported by the API.
+    #[default]
+    Second,
+    /// Per minute, reading better for quiet networks whose rates round to zero per second.
+    Minute,
+}
+
+impl RateUnit {
+    /// Returns the other unit.
+    pub fn toggled(self) -> Self {
+        match self {
+            RateUnit::Second => RateUnit::Minute,
+            RateUnit::Minute => RateUnit::Second,
+        }
+    }
+
+    /// Returns the factor converting a rate per second into this unit.
+    pub fn factor(self) -> f64 {
+        match self {
+            RateUnit::Second => 1.0,
+            RateUnit::Minute => 60.0,
+        }
+    }
+}
+
+impl std::fmt::Display for RateUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RateUnit::Second => f.write_str("per second"),
+            RateUnit::Minute => f.write_str("per minute"),
+        }
+    }
+}
+
+/// The number of decimal places of the metrics, unless set with `--precision`.
+pub const DEFAULT_PRECISION: usize = 2;
+
+/// The time since the last update at which the bar of `age_bar` is full.
+pub const AGE_BAR_SECS: f32 = 30.0;
+
+/// Formats a value with a fixed number of decimal places, e.g. `12.345` with precision 2 becomes `12.35`.
+///
+/// ### Arguments
+/// * `value` - The value to format.
+/// * `precision` - The number of decimal places.
+///
+/// ### Returns
+/// The formatted value.
+pub fn decimal(value: f64, precision: usize) -> String {
+    format!("{value:.precision$}")
+}
+
+/// Formats a number for display, abbreviated from a thousand unless `units` is raw.
+///
+/// ### Arguments
+/// * `value` - The number to format.
+/// * `precision` - The number of decimal places, of the abbreviated number if abbreviated.
+/// * `units` - How to write the number.
+///
+/// ### Returns
+/// The formatted number, e.g. `4560000000.00`, `4.56B` or `4.56G` for the three modes.
+pub fn number(value: f64, precision: usize, units: Units) -> String {
+    let suffixes: &[&str] = match units {
+        Units::Raw => return decimal(value, precision),
+        Units::Human => &HUMAN_SUFFIXES,
+        Units::Si => &SI_PREFIXES,
+    };
+    let (value, suffix) = abbreviate(value, suffixes);
+    format!("{}{suffix}", decimal(value, precision))
+}
+
+/// Formats a quantity of a unit for display, abbreviated with decimal (SI) prefixes unless
+/// `units` is raw. The short-scale suffixes aren't used with a unit, as "B" would read as bytes.
+///
+/// ### Arguments
+/// * `value` - The quantity in its base unit.
+/// * `unit` - The unit appended after the number.
+/// * `precision` - The number of decimal places.
+/// * `units` - How to write the quantity.
+///
+/// ### Returns
+/// The formatted quantity, e.g. `1234567.00 B` or `1.23 MB`.
+pub fn quantity(value: f64, unit: &str, precision: usize, units: Units) -> String {
+    match units {
+        Units::Raw => format!("{} {unit}", decimal(value, precision)),
+        Units::Human | Units::Si => si(value, unit, precision),
+    }
+}
+
+/// Divides a value by a thousand until it's below a thousand or the suffixes run out.
+///
+/// ### Returns
+/// The divided value, and the suffix of the number of divisions.
+fn abbreviate<'a>(mut value: f64, suffixes: &[&'a str]) -> (f64, &'a str) {
+    let mut index = 0;
+    while value.abs() >= 1000.0 && index < suffixes.len() - 1 {
+        value /= 1000.0;
+        index += 1;
+    }
+    (value, suffixes[index])
+}
+
+/// Truncates a string to fit a width, ending it with "…" when anything was cut off.
+/// The width is counted in characters, so multi-byte characters are never split.
+///
+/// ### Arguments
+/// * `s` - The string to truncate.
+/// * `width` - The maximum number of characters, including the ellipsis.
+///
+/// ### Returns
+/// The string itself if it fits, or its first `width - 1` characters followed by "…".
+pub fn truncate_to_width(s: &str, width: usize) -> String {
+    if s.chars().count() <= width {
+        return s.to_string();
+    }
+
+    match width {
+        0 => String::new(),
+        _ => s
+            .chars()
+            .take(width - 1)
+            .chain(std::iter::once('…'))
+            .collect(),
+    }
+}
+
+/// Wraps a string at word boundaries into lines fitting a width, splitting the words longer
+/// than the width. The width is counted in characters, like `truncate_to_width`.
+///
+/// ### Arguments
+/// * `s` - The string to wrap.
+/// * `width` - The maximum number of characters of a line.
+///
+/// ### Returns
+/// The lines, at least one, or the string itself in a single line for a zero width.
+pub fn wrap_words(s: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return vec![s.to_string()];
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in s.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        let line_len = line.chars().count();
+        if line_len > 0 && line_len + 1 + word.len() <= width {
+            line.push(' ');
+            line.extend(word);
+            continue;
+        }
+        if line_len > 0 {
+            lines.push(std::mem::take(&mut line));
+        }
+        while word.len() > width {
+            lines.push(word.drain(..width).collect());
+        }
+        line.extend(word);
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Draws the time since the last update of a network as a bar of `█` characters, growing
+/// linearly until it fills the width at `AGE_BAR_SECS`.
+///
+/// At least one character is drawn, so that a network that just updated still shows a bar.
+///
+/// ### Arguments
+/// * `elapsed_secs` - The time since the last update, in seconds.
+/// * `width` - The width of the bar when full, in characters.
+///
+/// ### Returns
+/// The bar, padded with spaces to `width` characters.
+pub fn age_bar(elapsed_secs: f32, width: u8) -> String {
+    let ratio = (elapsed_secs / AGE_BAR_SECS).clamp(0.0, 1.0);
+    let filled = ((ratio * width as f32).ceil() as usize).clamp(1, width as usize);
+    format!("{:<width$}", "█".repeat(filled), width = width as usize)
+}
+
+/// Pretty-prints a JSON document with 2-space indentation.
+///
+/// ### Arguments
+/// * `json` - The JSON document.
+///
+/// ### Returns
+/// The indented document, or the input as-is if it isn't valid JSON.
+pub fn pretty_json(json: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(json)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .unwrap_or_else(|_| json.to_string())
+}
+
+/// Abbreviates a quantity using decimal (SI) prefixes, e.g. `1_200_000.0` with unit `B`
+/// and precision 1 becomes `1.2 MB`.
+///
+/// ### Arguments
+/// * `value` - The quantity in its base unit.
+/// * `unit` - The unit appended after the prefix.
+/// * `precision` - The number of decimal places of the abbreviated quantity.
+///
+/// ### Returns
+/// The abbreviated quantity.
+pub fn si(value: f64, unit: &str, precision: usize) -> String {
+    let (value, prefix) = abbreviate(value, &SI_PREFIXES);
+    format!("{} {prefix}{unit}", decimal(value, precision))
+}
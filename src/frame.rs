@@ -0,0 +1,39 @@
+use std::time::{Duration, Instant};
+
+/// Rate limits the redraws of the interface to a frame budget.
+///
+/// Input is checked every `--input-poll-ms`, while the screen is redrawn at most once per
+/// frame budget, derived from `--max-fps`. The current time is passed in by the caller,
+/// which keeps the limiter independent of the clock.
+#[derive(Debug, Clone)]
+pub struct FrameLimiter {
+    budget: Duration,
+    last: Option<Instant>,
+}
+
+impl FrameLimiter {
+    /// Creates a new limiter, allowing the first frame right away.
+    ///
+    /// ### Arguments
+    /// * `budget` - The minimum time between two frames.
+    pub fn new(budget: Duration) -> Self {
+        Self { budget, last: None }
+    }
+
+    /// Checks whether a frame may be drawn, recording it as drawn if so.
+    ///
+    /// ### Arguments
+    /// * `now` - The current time.
+    ///
+    /// ### Returns
+    /// `true` if the budget has elapsed since the previous frame, or no frame was drawn yet.
+    pub fn ready(&mut self, now: Instant) -> bool {
+        let ready = self
+            .last
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.budget);
+        if ready {
+            self.last = Some(now);
+        }
+        ready
+    }
+}
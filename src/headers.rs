@@ -0,0 +1,78 @@
+use crate::{
+    config_file::{self, Section},
+    Column,
+};
+use clap::ValueEnum;
+use std::collections::HashMap;
+
+/// The labels of the header row of the table, relabeling or translating the columns through the
+/// `[headers]` section of the `--config` file. Columns without a label keep their built-in
+/// English header, see `Column::header`.
+///
+/// Only the labels of the interface change, the machine-readable outputs keep the built-in ones.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Headers {
+    labels: HashMap<Column, String>,
+}
+
+impl Section for Headers {
+    /// Adds the labels listed in the `[headers]` section of a configuration file.
+    ///
+    /// The section holds one `column = "label"` entry per line, e.g. `network = "Red"`, where the
+    /// column is named like in `--columns`.
+    ///
+    /// ### Arguments
+    /// * `contents` - The contents of the configuration file.
+    ///
+    /// ### Returns
+    /// `Ok` if every entry is valid, or an error prefixed by the line number of the first invalid one.
+    fn extend_from_str(&mut self, contents: &str) -> Result<(), String> {
+        for (line_number, line) in config_file::section_lines(contents, "headers")? {
+            let Some((name, label)) = line.split_once('=') else {
+                return Err(format!(
+                    "{line_number}: expected `column = \"label\"`, got `{line}`"
+                ));
+            };
+            let name = config_file::unquote(name).unwrap_or(name.trim());
+            let column = Column::from_str(name, true).map_err(|_| {
+                let names: Vec<String> = Column::value_variants()
+                    .iter()
+                    .filter_map(|c| c.to_possible_value())
+                    .map(|v| v.get_name().to_string())
+                    .collect();
+                format!(
+                    "{line_number}: unknown column `{name}`, expected one of {}",
+                    names.join(", ")
+                )
+            })?;
+            let label = config_file::unquote(label).ok_or_else(|| {
+                format!("{line_number}: the label of `{name}` must be a non-empty quoted string")
+            })?;
+
+            self.labels.insert(column, label.to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl Headers {
+    /// Returns the label of the header of a column, if the section sets one.
+    ///
+    /// ### Arguments
+    /// * `column` - The column.
+    pub fn get(&self, column: Column) -> Option<&str> {
+        self.labels.get(&column).map(String::as_str)
+    }
+
+    /// Returns the label of the header of a column.
+    ///
+    /// ### Arguments
+    /// * `column` - The column.
+    ///
+    /// ### Returns
+    /// The configured label, or the built-in header of the column.
+    pub fn label(&self, column: Column) -> &str {
+        self.get(column).unwrap_or(column.header())
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{anomaly::HISTORY_LEN, init, Data, Network};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The age past which a saved history is discarded on startup rather than restored, since the
+/// metrics it holds no longer relate to the current ones.
+pub const MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// The histories of the networks as written to the file, see `save`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedHistory {
+    /// The time the file was written, in seconds since the Unix epoch.
+    saved_at: u64,
+    /// The past updates of each network by name, oldest first.
+    networks: HashMap<String, Vec<Sample>>,
+}
+
+/// A past update of a network, kept apart from `Data`, which reads the metrics as the strings
+/// sent by the API.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Sample {
+    block_number: u64,
+    tps: f32,
+    gps: f32,
+    dps: f32,
+    #[serde(default)]
+    pending_tx_count: Option<u64>,
+}
+
+impl From<&Data> for Sample {
+    fn from(data: &Data) -> Self {
+        Self {
+            block_number: data.block_number,
+            tps: data.tps,
+            gps: data.gps,
+            dps: data.dps,
+            pending_tx_count: data.pending_tx_count,
+        }
+    }
+}
+
+impl From<Sample> for Data {
+    fn from(sample: Sample) -> Self {
+        Self {
+            block_number: sample.block_number,
+            tps: sample.tps,
+            gps: sample.gps,
+            dps: sample.dps,
+            pending_tx_count: sample.pending_tx_count,
+        }
+    }
+}
+
+/// Returns the standard location of the history file, `history.json` next to the configuration
+/// file of `init::default_path`.
+pub fn default_path() -> Option<PathBuf> {
+    Some(init::default_path()?.with_file_name("history.json"))
+}
+
+/// Writes the history of the networks to a file, replacing it, see `Network::history`.
+///
+/// ### Arguments
+/// * `path` - The file to write, its parent directories are created if missing.
+/// * `networks` - The networks whose history to save, networks without history are left out.
+///
+/// ### Returns
+/// `Ok` once written, or an error naming the file if it can't be written.
+pub fn save(path: &Path, networks: &[&Network]) -> Result<(), String> {
+    let saved_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let networks = networks
+        .iter()
+        .filter(|n| !n.history.is_empty())
+        .map(|n| (n.name.clone(), n.history.iter().map(Sample::from).collect()))
+        .collect();
+    let contents = serde_json::to_vec(&SavedHistory { saved_at, networks })
+        .map_err(|e| format!("failed to serialize the history: {e}"))?;
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+    }
+    std::fs::write(path, contents).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
+
+/// Reads the history of the networks saved to a file by `save`.
+///
+/// ### Arguments
+/// * `path` - The file to read.
+/// * `max_age` - The age past which the saved history is discarded, see `MAX_AGE`.
+///
+/// ### Returns
+/// The past updates of each network by name, oldest first and at most `HISTORY_LEN` of them,
+/// empty if the file doesn't exist or is older than `max_age`, or an error naming the file if it
+/// can't be read or parsed.
+pub fn load(path: &Path, max_age: Duration) -> Result<HashMap<String, VecDeque<Data>>, String> {
+    let contents = match std::fs::read(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(format!("failed to read {}: {e}", path.display())),
+    };
+    let saved: SavedHistory = serde_json::from_slice(&contents)
+        .map_err(|e| format!("failed to parse {}: {e}", path.display()))?;
+
+    let saved_at = UNIX_EPOCH + Duration::from_secs(saved.saved_at);
+    let age = SystemTime::now()
+        .duration_since(saved_at)
+        .unwrap_or_default();
+    if age > max_age {
+        return Ok(HashMap::new());
+    }
+
+    Ok(saved
+        .networks
+        .into_iter()
+        .map(|(name, samples)| {
+            let skipped = samples.len().saturating_sub(HISTORY_LEN);
+            let history = samples.into_iter().skip(skipped).map(Data::from).collect();
+            (name, history)
+        })
+        .collect())
+}
+
+/// Writes the history of a network as CSV records, one per past update, oldest first.
+///
+/// ### Arguments
+/// * `writer` - The destination of the records.
+/// * `history` - The past updates of the network, see `Network::history`.
+///
+/// ### Returns
+/// `Ok` once written and flushed, or an error if writing fails.
+pub fn write_csv<W: Write>(writer: W, history: &VecDeque<Data>) -> csv::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for data in history {
+        writer.serialize(data)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use crate::{da, format, Action, Column, Config};
+use clap::{ArgAction, CommandFactory, ValueEnum};
+use std::path::{Path, PathBuf};
+
+/// The options left out of the template: those naming the file itself, deprecated, or requiring
+/// another option, which would fail once uncommented on their own.
+const SKIPPED_OPTIONS: [&str; 6] = [
+    "config",
+    "profile",
+    "help",
+    "version",
+    "replay-speed",
+    "interval-ms",
+];
+
+/// The width the descriptions of the template are wrapped at.
+const TEMPLATE_WIDTH: usize = 96;
+
+/// Returns the standard location of the configuration file, `roller/config.toml` in
+/// `$XDG_CONFIG_HOME`, or in `~/.config` when it's unset.
+pub fn default_path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .or_else(|| std::env::var_os("USERPROFILE"))
+                .filter(|home| !home.is_empty())
+                .map(|home| PathBuf::from(home).join(".config"))
+        })?;
+    Some(config_home.join("roller").join("config.toml"))
+}
+
+/// Generates the default configuration file, listing every option and section of the `--config`
+/// file along with its compiled-in default.
+///
+/// The settings are commented out with `# `, so that uncommenting any of them keeps the default
+/// until edited, while the descriptions and the examples without a default start with `## `.
+/// The options are taken from the flags of `Config`, so the template can't miss any.
+///
+/// ### Returns
+/// The contents of the file.
+pub fn template() -> String {
+    let mut lines = vec![
+        String::from("## The configuration of roller, generated by `roller init`."),
+        String::from(
+            "## Uncomment a setting to change it, pass the file with --config or ROLLER_CONFIG.",
+        ),
+        String::from(
+            "## Options are named like their flags, below the environment and the command line.",
+        ),
+        String::new(),
+    ];
+
+    let command = Config::command();
+    for arg in command.get_arguments() {
+        let Some(name) = arg.get_long() else {
+            continue;
+        };
+        if arg.is_hide_set() || SKIPPED_OPTIONS.contains(&name) {
+            continue;
+        }
+
+        let help = arg
+            .get_long_help()
+            .or(arg.get_help())
+            .map(|h| h.to_string());
+        lines.extend(describe(help.as_deref().unwrap_or_default()));
+        let defaults: Vec<String> = arg
+            .get_default_values()
+            .iter()
+            .map(|value| value_of(&value.to_string_lossy()))
+            .collect();
+        let repeated = matches!(arg.get_action(), ArgAction::Append);
+        lines.push(match (arg.get_action().takes_values(), &defaults[..]) {
+            (false, _) => format!("# {name} = false"),
+            (true, [value]) if !repeated => format!("# {name} = {value}"),
+            (true, []) if !repeated => {
+                let value = arg
+                    .get_value_names()
+                    .and_then(|names| names.first())
+                    .map_or_else(|| name.to_uppercase(), |n| n.to_string());
+                format!("## {name} = \"{value}\", unset by default")
+            }
+            (true, values) => format!("# {name} = [{}]", values.join(", ")),
+        });
+        lines.push(String::new());
+    }
+
+    lines.extend([
+        String::from(
+            "## Profiles override the options above when selected with --profile NAME, e.g.",
+        ),
+        String::from("## [profile.staging]"),
+        String::from("## api-endpoint = \"https://staging.example.com\""),
+        String::new(),
+        String::from(
+            "## The keys of the interface, as `action = \"key\"`, e.g. `sort_tps = \"ctrl+t\"`.",
+        ),
+        String::from("[keys]"),
+    ]);
+    lines.extend(
+        Action::ALL
+            .iter()
+            .map(|action| format!("# {} = \"{}\"", action.name(), action.default_key())),
+    );
+
+    lines.extend([
+        String::new(),
+        String::from("## The names of the data availability layers in the DA column,"),
+        String::from("## as `name = \"display name\"`."),
+        String::from("[da]"),
+    ]);
+    lines.extend(
+        da::BUILTIN_DA_LAYERS
+            .iter()
+            .map(|(name, display)| format!("# {name} = \"{display}\"")),
+    );
+
+    lines.extend([
+        String::new(),
+        String::from("## The labels of the header row of the table, as `column = \"label\"`."),
+        String::from("[headers]"),
+    ]);
+    lines.extend(Column::value_variants().iter().filter_map(|column| {
+        let name = column.to_possible_value()?;
+        Some(format!("# {} = \"{}\"", name.get_name(), column.header()))
+    }));
+
+    lines.extend([
+        String::new(),
+        String::from("## The static prices of the USD/s column,"),
+        String::from("## as `network = [gas price in gwei, token price in USD]`."),
+        String::from("[prices]"),
+        String::from("## base = [0.005, 3400]"),
+        String::new(),
+        String::from("## The thresholds coloring the tps, gps, dps and gas-per-tx metrics,"),
+        String::from("## as `metric = { warn = number, high = number }`."),
+        String::from("[thresholds]"),
+        String::from("## tps = { warn = 1.0, high = 20.0 }"),
+        String::new(),
+        String::from("## A network can override them in its own section, e.g."),
+        String::from("## [thresholds.networks.base]"),
+        String::from("## tps = { warn = 10.0, high = 50.0 }"),
+    ]);
+
+    lines.join("\n") + "\n"
+}
+
+/// Writes the default configuration file, see `template`.
+///
+/// ### Arguments
+/// * `path` - The file to write, its parent directories are created if missing.
+/// * `force` - Whether to overwrite the file if it exists.
+///
+/// ### Returns
+/// `Ok` once written, or an error if the file exists without `force` or can't be written.
+pub fn write(path: &Path, force: bool) -> Result<(), String> {
+    if path.exists() && !force {
+        return Err(format!(
+            "{} already exists, pass --force to overwrite it",
+            path.display()
+        ));
+    }
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+    }
+    std::fs::write(path, template()).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
+
+/// Writes the description of an option as `## ` comments, wrapped to `TEMPLATE_WIDTH`.
+fn describe(help: &str) -> Vec<String> {
+    help.lines()
+        .flat_map(|line| format::wrap_words(line, TEMPLATE_WIDTH))
+        .map(|line| format!("## {line}").trim_end().to_string())
+        .collect()
+}
+
+/// Writes a default value as a bare number, or a quoted string otherwise.
+fn value_of(value: &str) -> String {
+    match value.parse::<f64>() {
+        Ok(_) => value.to_string(),
+        Err(_) => format!("\"{value}\""),
+    }
+}
@@ -0,0 +1,391 @@
+use crate::{
+    config_file::{self, Section},
+    SortingStrategy,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::{collections::HashMap, fmt};
+
+/// The actions of the interface that can be bound to a key through the `[keys]` section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Exit the application.
+    Quit,
+    /// Move the selection to the previous row, or scroll the raw data up.
+    Up,
+    /// Move the selection to the next row, or scroll the raw data down.
+    Down,
+    /// Toggle the detail pane of the selected network.
+    Details,
+    /// Close the detail pane, or clear a locked search.
+    Close,
+    /// Open the block explorer of the selected network.
+    Explorer,
+    /// Copy the `--copy-field` of the selected network to the clipboard.
+    Copy,
+    /// Export the table as CSV.
+    Export,
+    /// Start or resume the search filtering the table by name.
+    Filter,
+    /// Jump to the row of a rank.
+    Goto,
+    /// Toggle the "Raw Data" section of the detail pane.
+    RawData,
+    /// Toggle the "Metadata" section of the detail pane.
+    Metadata,
+    /// Show the previous, more recent, page of the history table of the detail pane.
+    HistoryPageUp,
+    /// Show the next, older, page of the history table of the detail pane.
+    HistoryPageDown,
+    /// Export the history of the network in the detail pane as CSV.
+    ExportHistory,
+    /// Toggle the event counts of the summary line.
+    EventCounts,
+    /// Cycle how the metrics are written, in full, abbreviated or with SI prefixes.
+    Units,
+    /// Toggle the rates between per second and per minute.
+    RateUnit,
+    /// Expand the info bar into a reference of the keys grouped by purpose, or collapse it.
+    KeyReference,
+    /// Tear down the SSE stream and establish a new one, keeping the data of the networks.
+    Reconnect,
+    /// Suspend roller to the shell, restoring the terminal until it's resumed with `fg`.
+    Suspend,
+    /// Sort by Gas Per Second (GPS).
+    SortGps,
+    /// Sort by Transactions Per Second (TPS).
+    SortTps,
+    /// Sort by Data Per Second (DPS).
+    SortDps,
+    /// Sort by the number of pending transactions.
+    SortPending,
+    /// Sort by the average gas used by a transaction.
+    SortGasPerTx,
+}
+
+impl Action {
+    /// Every action, in the order they're checked for conflicting keys.
+    pub const ALL: [Action; 26] = [
+        Action::Quit,
+        Action::Up,
+        Action::Down,
+        Action::Details,
+        Action::Close,
+        Action::Explorer,
+        Action::Copy,
+        Action::Export,
+        Action::Filter,
+        Action::Goto,
+        Action::RawData,
+        Action::Metadata,
+        Action::HistoryPageUp,
+        Action::HistoryPageDown,
+        Action::ExportHistory,
+        Action::EventCounts,
+        Action::Units,
+        Action::RateUnit,
+        Action::KeyReference,
+        Action::Reconnect,
+        Action::Suspend,
+        Action::SortGps,
+        Action::SortTps,
+        Action::SortDps,
+        Action::SortPending,
+        Action::SortGasPerTx,
+    ];
+
+    /// Returns the name of the action in the `[keys]` section.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Details => "details",
+            Action::Close => "close",
+            Action::Explorer => "explorer",
+            Action::Copy => "copy",
+            Action::Export => "export",
+            Action::Filter => "filter",
+            Action::Goto => "goto",
+            Action::RawData => "raw_data",
+            Action::Metadata => "metadata",
+            Action::HistoryPageUp => "history_page_up",
+            Action::HistoryPageDown => "history_page_down",
+            Action::ExportHistory => "export_history",
+            Action::EventCounts => "event_counts",
+            Action::Units => "units",
+            Action::RateUnit => "rate_unit",
+            Action::KeyReference => "key_reference",
+            Action::Reconnect => "reconnect",
+            Action::Suspend => "suspend",
+            Action::SortGps => "sort_gps",
+            Action::SortTps => "sort_tps",
+            Action::SortDps => "sort_dps",
+            Action::SortPending => "sort_pending",
+            Action::SortGasPerTx => "sort_gas_per_tx",
+        }
+    }
+
+    /// Returns the key the action is bound to unless overridden.
+    pub fn default_key(&self) -> KeyBinding {
+        let code = match self {
+            Action::Quit => KeyCode::Char('q'),
+            Action::Up => KeyCode::Up,
+            Action::Down => KeyCode::Down,
+            Action::Details => KeyCode::Enter,
+            Action::Close => KeyCode::Esc,
+            Action::Explorer => KeyCode::Char('e'),
+            Action::Copy => KeyCode::Char('c'),
+            Action::Export => KeyCode::Char('x'),
+            Action::Filter => KeyCode::Char('/'),
+            Action::Goto => KeyCode::Char(':'),
+            Action::RawData => KeyCode::Char('d'),
+            Action::Metadata => KeyCode::Char('M'),
+            Action::HistoryPageUp => KeyCode::PageUp,
+            Action::HistoryPageDown => KeyCode::PageDown,
+            Action::ExportHistory => KeyCode::Char('D'),
+            Action::EventCounts => KeyCode::Char('v'),
+            Action::Units => KeyCode::Char('u'),
+            Action::RateUnit => KeyCode::Char('m'),
+            Action::KeyReference => KeyCode::Char('h'),
+            Action::Reconnect => KeyCode::Char('R'),
+            Action::Suspend => KeyCode::Char('z'),
+            Action::SortGps => KeyCode::Char('g'),
+            Action::SortTps => KeyCode::Char('t'),
+            Action::SortDps => KeyCode::Char('k'),
+            Action::SortPending => KeyCode::Char('p'),
+            Action::SortGasPerTx => KeyCode::Char('a'),
+        };
+        // Suspending keeps the key of shells, only reachable in the raw mode with a modifier
+        let modifiers = match self {
+            Action::Suspend => KeyModifiers::CONTROL,
+            _ => KeyModifiers::NONE,
+        };
+        KeyBinding::new(code, modifiers)
+    }
+
+    /// Returns the sorting strategy selected by the action, if it's a sorting action.
+    pub fn sorting_strategy(&self) -> Option<SortingStrategy> {
+        // Resolved through the exhaustive `SortingStrategy::action`, so a new strategy can't
+        // be left without a key.
+        SortingStrategy::ALL
+            .into_iter()
+            .find(|strategy| strategy.action() == *self)
+    }
+}
+
+/// The names of the keys without a printable character, as written in the `[keys]` section.
+const NAMED_KEYS: [(&str, KeyCode); 14] = [
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("enter", KeyCode::Enter),
+    ("esc", KeyCode::Esc),
+    ("tab", KeyCode::Tab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("space", KeyCode::Char(' ')),
+];
+
+/// A key, with the modifiers held while pressing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    /// The key code.
+    pub code: KeyCode,
+    /// The modifiers, Shift is implied by the case of characters and ignored.
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Creates a new key binding.
+    ///
+    /// ### Arguments
+    /// * `code` - The key code.
+    /// * `modifiers` - The modifiers held while pressing the key.
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self {
+            code,
+            modifiers: modifiers - KeyModifiers::SHIFT,
+        }
+    }
+
+    /// Parses a key, like `q`, `pageup`, `f5` or `ctrl+d`.
+    ///
+    /// Any number of `ctrl+` and `alt+` prefixes may precede the key, which is either a single
+    /// character, matched case-sensitively, or the case-insensitive name of a key.
+    ///
+    /// ### Arguments
+    /// * `value` - The key, as written in the `[keys]` section.
+    ///
+    /// ### Returns
+    /// The key binding, or an error naming the unknown modifier or key.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = value;
+        while let Some((modifier, rest)) = key.split_once('+').filter(|(_, rest)| !rest.is_empty())
+        {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                _ => return Err(format!("unknown modifier `{modifier}` in `{value}`")),
+            };
+            key = rest;
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => {
+                let name = key.to_lowercase();
+                let function = name
+                    .strip_prefix('f')
+                    .and_then(|n| n.parse().ok())
+                    .filter(|n| (1..=12).contains(n));
+                match NAMED_KEYS.iter().find(|(n, _)| *n == name) {
+                    Some((_, code)) => *code,
+                    None => KeyCode::F(function.ok_or_else(|| format!("unknown key `{key}`"))?),
+                }
+            }
+        };
+        Ok(Self::new(code, modifiers))
+    }
+
+    /// Checks whether a key event is a press of this key.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        *self == Self::new(event.code, event.modifiers)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(c) if c != ' ' => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            code => match NAMED_KEYS.iter().find(|(_, c)| *c == code) {
+                Some((name, _)) => write!(f, "{name}"),
+                None => write!(f, "{code:?}"),
+            },
+        }
+    }
+}
+
+/// Maps the keys of the interface to their actions.
+///
+/// Every action starts bound to its default key, see `Action::default_key`, and can be
+/// rebound through the `[keys]` section of the `--config` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    bindings: HashMap<Action, KeyBinding>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .iter()
+                .map(|action| (*action, action.default_key()))
+                .collect(),
+        }
+    }
+}
+
+impl Section for KeyMap {
+    /// Rebinds the actions listed in the `[keys]` section of a configuration file.
+    ///
+    /// The section holds one `action = "key"` entry per line, like a flat TOML table, e.g.
+    /// `sort_tps = "ctrl+t"`. Blank lines and lines starting with `#` are ignored, and
+    /// actions missing from the section keep their current key.
+    ///
+    /// ### Arguments
+    /// * `contents` - The contents of the configuration file.
+    ///
+    /// ### Returns
+    /// `Ok` if every entry is valid and no key is bound to two actions, or an error prefixed by
+    /// the line number of the first invalid entry.
+    fn extend_from_str(&mut self, contents: &str) -> Result<(), String> {
+        let mut assigned = HashMap::new();
+        for (line_number, line) in config_file::section_lines(contents, "keys")? {
+            let Some((name, key)) = line.split_once('=') else {
+                return Err(format!(
+                    "{line_number}: expected `action = \"key\"`, got `{line}`"
+                ));
+            };
+            let name = name.trim();
+            let action = Action::ALL
+                .into_iter()
+                .find(|action| action.name() == name)
+                .ok_or_else(|| format!("{line_number}: unknown action `{name}`"))?;
+            if let Some(previous) = assigned.insert(action, line_number) {
+                return Err(format!(
+                    "{line_number}: `{name}` is already assigned on line {previous}"
+                ));
+            }
+            let key = config_file::unquote(key).ok_or_else(|| {
+                format!("{line_number}: the key of `{name}` must be a non-empty quoted string")
+            })?;
+            let binding = KeyBinding::parse(key).map_err(|e| format!("{line_number}: {e}"))?;
+
+            self.bindings.insert(action, binding);
+        }
+
+        self.conflicts_check(&assigned)
+    }
+}
+
+impl KeyMap {
+    /// Checks that no key is bound to more than one action.
+    ///
+    /// ### Arguments
+    /// * `assigned` - The line of each action assigned by the configuration file.
+    ///
+    /// ### Returns
+    /// `Ok` if the keys are unique, or an error prefixed by the line assigning a taken key.
+    fn conflicts_check(&self, assigned: &HashMap<Action, usize>) -> Result<(), String> {
+        for (i, a) in Action::ALL.iter().enumerate() {
+            let Some(b) = Action::ALL[i + 1..]
+                .iter()
+                .find(|b| self.key(**b) == self.key(*a))
+            else {
+                continue;
+            };
+            // The default keys are unique, so at least one of the actions has been assigned
+            let line_number = assigned
+                .get(a)
+                .max(assigned.get(b))
+                .copied()
+                .unwrap_or_default();
+            return Err(format!(
+                "{line_number}: `{}` is bound to both `{}` and `{}`",
+                self.key(*a),
+                a.name(),
+                b.name()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the key bound to an action.
+    pub fn key(&self, action: Action) -> KeyBinding {
+        self.bindings
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| action.default_key())
+    }
+
+    /// Returns the action bound to the key of an event, if any.
+    pub fn action(&self, event: &KeyEvent) -> Option<Action> {
+        Action::ALL
+            .into_iter()
+            .find(|action| self.key(*action).matches(event))
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::mpsc::{self, Receiver, Sender},
+    time::{Duration, Instant},
+};
+
+/// The time between two pings of the network displayed in the detail pane.
+pub const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The time after which a ping is reported as a timeout.
+pub const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The number of pings kept for each network, drawn by the sparkline.
+pub const PING_HISTORY: usize = 10;
+
+/// The bars of the sparkline, from the lowest latency to the highest.
+const SPARK_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The outcome of a ping of the website of a network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ping {
+    /// The round-trip time of the request.
+    Latency(Duration),
+    /// No response was received within the timeout.
+    Timeout,
+    /// The request failed before a response was received, e.g. the host is unreachable.
+    Failed,
+}
+
+impl std::fmt::Display for Ping {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Ping::Latency(latency) => write!(f, "{} ms", latency.as_millis()),
+            Ping::Timeout => write!(f, "timeout"),
+            Ping::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// Measures the round-trip time of a HEAD request to the URL.
+///
+/// Any response counts, whatever its status, since it still measures the round trip.
+///
+/// ### Arguments
+/// * `http` - The HTTP client sending the request.
+/// * `url` - The URL to ping.
+/// * `timeout` - The time after which the ping is abandoned.
+///
+/// ### Returns
+/// The latency of the response, or why none was received.
+pub async fn ping(http: &reqwest::Client, url: &str, timeout: Duration) -> Ping {
+    let start = Instant::now();
+    match tokio::time::timeout(timeout, http.head(url).send()).await {
+        Ok(Ok(_)) => Ping::Latency(start.elapsed()),
+        Ok(Err(e)) => {
+            tracing::debug!(url, error = %e, "failed to ping the network");
+            Ping::Failed
+        }
+        Err(_) => Ping::Timeout,
+    }
+}
+
+/// Draws the pings as a sparkline, scaled from the lowest latency to the highest.
+/// Pings without a response are drawn as `×`.
+///
+/// ### Arguments
+/// * `pings` - The pings, oldest first.
+///
+/// ### Returns
+/// One character per ping.
+pub fn sparkline<'a>(pings: impl IntoIterator<Item = &'a Ping>) -> String {
+    let pings: Vec<&Ping> = pings.into_iter().collect();
+    let latencies = pings.iter().filter_map(|p| match p {
+        Ping::Latency(latency) => Some(latency.as_secs_f64()),
+        _ => None,
+    });
+    let (min, max) = latencies.fold((f64::MAX, 0.0_f64), |(min, max), l| {
+        (min.min(l), max.max(l))
+    });
+
+    pings
+        .iter()
+        .map(|p| match p {
+            Ping::Latency(latency) if max > min => {
+                let ratio = (latency.as_secs_f64() - min) / (max - min);
+                SPARK_BARS[(ratio * (SPARK_BARS.len() - 1) as f64).round() as usize]
+            }
+            Ping::Latency(_) => SPARK_BARS[0],
+            _ => '×',
+        })
+        .collect()
+}
+
+/// Finds the lowest and highest latencies of the pings, the range the sparkline is scaled over.
+///
+/// ### Arguments
+/// * `pings` - The pings, in any order.
+///
+/// ### Returns
+/// The `(min, max)` latencies, or `None` if no ping received a response.
+pub fn range<'a>(pings: impl IntoIterator<Item = &'a Ping>) -> Option<(Duration, Duration)> {
+    pings
+        .into_iter()
+        .filter_map(|p| match p {
+            Ping::Latency(latency) => Some(*latency),
+            _ => None,
+        })
+        .fold(None, |range, latency| match range {
+            Some((min, max)) => Some((latency.min(min), latency.max(max))),
+            None => Some((latency, latency)),
+        })
+}
+
+/// Pings the website of the network displayed in the detail pane, keeping the latest pings
+/// of each network.
+///
+/// The pings run on their own tasks with a dedicated HTTP client, so a slow website never
+/// holds up the processing of events. Their results are collected by `poll`.
+#[derive(Debug)]
+pub struct LatencyMonitor {
+    http: reqwest::Client,
+    sender: Sender<(String, Ping)>,
+    receiver: Receiver<(String, Ping)>,
+    history: HashMap<String, VecDeque<Ping>>,
+    /// The network pinged last and when.
+    last_ping: Option<(String, Instant)>,
+}
+
+impl Default for LatencyMonitor {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            http: reqwest::Client::new(),
+            sender,
+            receiver,
+            history: HashMap::new(),
+            last_ping: None,
+        }
+    }
+}
+
+impl LatencyMonitor {
+    /// Collects the finished pings, then pings the target if it changed or wasn't pinged
+    /// for `PING_INTERVAL`. Must be called from within the runtime, which runs the pings.
+    ///
+    /// ### Arguments
+    /// * `target` - The name and website of the network to ping, `None` to stop pinging.
+    ///
+    /// ### Returns
+    /// `true` if a ping finished, meaning the detail pane should be redrawn.
+    pub fn poll(&mut self, target: Option<(&str, &str)>) -> bool {
+        let mut finished = false;
+        while let Ok((name, ping)) = self.receiver.try_recv() {
+            self.record(&name, ping);
+            finished = true;
+        }
+
+        let Some((name, url)) = target else {
+            self.last_ping = None;
+            return finished;
+        };
+        let due = self
+            .last_ping
+            .as_ref()
+            .is_none_or(|(last, at)| last != name || at.elapsed() >= PING_INTERVAL);
+        if due {
+            self.last_ping = Some((name.to_string(), Instant::now()));
+            let (http, sender) = (self.http.clone(), self.sender.clone());
+            let (name, url) = (name.to_string(), url.to_string());
+            tokio::spawn(async move {
+                let ping = ping(&http, &url, PING_TIMEOUT).await;
+                // The monitor is gone once roller exits, along with the need for the result
+                let _ = sender.send((name, ping));
+            });
+        }
+        finished
+    }
+
+    /// Records a ping of a network, dropping the oldest one past `PING_HISTORY`.
+    ///
+    /// ### Arguments
+    /// * `name` - The name of the network.
+    /// * `ping` - The outcome of the ping.
+    pub fn record(&mut self, name: &str, ping: Ping) {
+        let history = self.history.entry(name.to_string()).or_default();
+        if history.len() == PING_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(ping);
+    }
+
+    /// Returns the latest pings of a network, oldest first.
+    pub fn history(&self, name: &str) -> Option<&VecDeque<Ping>> {
+        self.history.get(name)
+    }
+}
@@ -1,17 +1,91 @@
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const DEFAULT_API_ENDPOINT: &str = "https://tracker-api-gdesfolyga-uw.a.run.app";
+/// The major version of the API this client was built against.
+pub const EXPECTED_API_VERSION: &str = "1";
+
+mod error;
+pub use error::RollerError;
 
 mod client;
-pub use client::Client;
+pub use client::{
+    networks_from_metadata, parse_endpoint, parse_header, parse_metadata, Client, ClientBuilder,
+    ClientSettings, Endpoint, Header, Health, Secret,
+};
 
 mod network;
-pub use network::Network;
+pub use network::{chain_emoji, Data, Layer, Network, CHAIN_EMOJI, NEW_BADGE, NEW_BADGE_DURATION};
 
 mod tui;
-pub use tui::Tui;
+pub use tui::{
+    connection_led, next_refresh_countdown, stack_color, ConnectionState, EventCounts,
+    InfoBarSegment, SortDirection, SortingStrategy, Tui, HISTORY_PAGE_SIZE, MIN_TERMINAL_SIZE,
+};
 
 mod config;
-pub use config::Config;
+pub use config::{Command, Config, InitArgs, ListArgs};
 
 mod terminal;
-pub use terminal::Terminal;
+pub use terminal::{EnterOptions, Terminal, UiOutput, UiWriter};
+
+mod column;
+pub use column::{Column, ColumnPreset, DisplayOptions};
+
+pub mod output;
+
+pub mod format;
+
+pub mod qr;
+
+mod theme;
+pub use theme::{BorderStyle, HeaderStyle, Theme};
+
+pub mod logging;
+
+mod snapshot;
+pub use snapshot::SnapshotWriter;
+
+mod recording;
+pub use recording::{RecordedEvent, Recorder, Recording};
+
+mod chains;
+pub use chains::ChainRegistry;
+
+mod validation;
+pub use validation::{Problem, Severity};
+
+mod config_file;
+pub use config_file::{Section, Sections};
+
+mod da;
+pub use da::DaRegistry;
+
+mod prices;
+pub use prices::{GasPrice, PriceRegistry};
+
+mod headers;
+pub use headers::Headers;
+
+mod thresholds;
+pub use thresholds::{Alert, Level, Threshold, Thresholds, THRESHOLD_METRICS};
+
+mod profile;
+pub use profile::{FileOption, Layered, Profiles};
+
+pub mod anomaly;
+
+mod frame;
+pub use frame::FrameLimiter;
+
+mod keys;
+pub use keys::{Action, KeyBinding, KeyMap};
+
+pub mod latency;
+
+pub mod clipboard;
+
+pub mod init;
+
+pub mod history;
+
+#[cfg(feature = "prometheus")]
+pub mod metrics;
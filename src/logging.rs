@@ -0,0 +1,80 @@
+use crate::Config;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{error::Error, fs::OpenOptions, sync::Mutex};
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// The minimum level of the events written to the log file.
+#[derive(Debug, Default, Clone, Copy, ValueEnum, Serialize)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// The format of the lines written to the log file.
+#[derive(Debug, Default, Clone, Copy, ValueEnum, Serialize)]
+pub enum LogFormat {
+    /// One human-readable line per event.
+    #[default]
+    Compact,
+    /// One JSON object per line.
+    Json,
+}
+
+/// Installs a `tracing` subscriber writing to `--log-file`, if set.
+///
+/// The alternate screen prevents printing diagnostics to the terminal, so they're written to a file instead.
+/// Without the flag no subscriber is installed and the `tracing` macros are near zero-cost no-ops.
+/// Panics are logged as well, before being passed on to the previous panic hook.
+///
+/// The requests of the client are traced at the debug level and the handling of every event at
+/// the trace level, each span logging the time spent in it as it closes.
+///
+/// ### Arguments
+/// * `config` - The parsed command-line configuration.
+///
+/// ### Returns
+/// `Ok` if logging is disabled or was set up, or an error if the file can't be opened.
+pub fn init(config: &Config) -> Result<(), Box<dyn Error>> {
+    let Some(path) = &config.log_file else {
+        return Ok(());
+    };
+
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let builder = tracing_subscriber::fmt()
+        .with_writer(Mutex::new(file))
+        .with_max_level(LevelFilter::from(config.log_level))
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false);
+
+    match config.log_format {
+        LogFormat::Compact => builder.compact().try_init(),
+        LogFormat::Json => builder.json().try_init(),
+    }
+    .map_err(|e| e.to_string())?;
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        tracing::error!(panic = %info, "panicked");
+        previous(info);
+    }));
+
+    Ok(())
+}
@@ -1,62 +1,695 @@
-use clap::Parser;
-use crossterm::event::{self, Event as CEvent, KeyCode};
+use crossterm::event::{self, Event as CEvent, KeyEventKind};
 use eventsource_client::SSE;
-use futures::TryStreamExt;
-use roller::{Client, Config, Tui};
-use std::{error::Error, time::Duration};
+use futures::{Stream, TryStreamExt};
+use roller::{
+    history, init,
+    latency::LatencyMonitor,
+    output::{self, FollowWriter},
+    Action, ChainRegistry, Client, Command, Config, EnterOptions, FrameLimiter, InitArgs, ListArgs,
+    PriceRegistry, Problem, Recorder, Recording, Sections, Tui, UiOutput,
+};
+use std::{
+    error::Error,
+    io::{self, Stdout},
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+/// The SSE stream consumed by the output modes, from the API or a recording.
+type EventStream =
+    Pin<Box<dyn Stream<Item = Result<SSE, eventsource_client::Error>> + Send + Sync>>;
+
+/// Exit code of `--check` when the network metadata can't be retrieved.
+const EXIT_METADATA_FAILED: i32 = 3;
+/// Exit code of `--check` when the SSE stream can't be established or delivers no events.
+const EXIT_STREAM_FAILED: i32 = 4;
+/// How long `--check` waits for the first message of the SSE stream.
+const CHECK_STREAM_TIMEOUT: Duration = Duration::from_secs(5);
 
 // Currently we keep it single-threaded, since there's not much we get from multi.
 // In the future, data processing could be offloaded to a separate thread.
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> Result<(), Box<dyn Error>> {
     // Parse the command-line configuration
-    let config = Config::parse();
-    // Create a new client instance with the specified API endpoint, or default
-    let client = Client::new(config.api_endpoint);
+    let config = Config::load();
+    // Install the log file subscriber, if enabled
+    roller::logging::init(&config)?;
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "starting roller");
+    // Write the configuration file before anything could fail on the current one
+    if let Some(Command::Init(args)) = &config.command {
+        return run_init(args);
+    }
+    // Load the chain names early, so an invalid `--chains` file fails before any request
+    let chains = config.chain_registry()?;
+    // Likewise for the sections of the `--config` file
+    let Sections {
+        keys,
+        da,
+        prices,
+        thresholds,
+        headers,
+    } = config.sections()?;
+    // Report every problem of the configuration at once, before any request
+    let problems = config.validate();
+    report(&problems);
+    let errors = problems.iter().filter(|p| p.is_error()).count();
+    if errors > 0 {
+        return Err(format!("invalid configuration, {errors} error(s) reported above").into());
+    }
+    // Warn about deprecated options, naming their replacements
+    for warning in [config.deprecation_warning(), config.auth_token_warning()]
+        .into_iter()
+        .flatten()
+    {
+        tracing::warn!("{warning}");
+        eprintln!("{warning}");
+    }
+    // Validate the endpoints before any request, noting the assumptions made to normalize them
+    for notice in config.endpoints()?.into_iter().filter_map(|e| e.notice) {
+        tracing::info!("{notice}");
+        eprintln!("{notice}");
+    }
+    // Create a new client instance with the specified API endpoint, timeouts and retries
+    let client = config.client()?;
+    // Bind the metrics port before entering the terminal mode, so a port in use is reported.
+    // The server stops once dropped, when roller exits.
+    #[cfg(feature = "prometheus")]
+    let metrics_server = match config.prometheus_port {
+        Some(port) => Some(roller::metrics::MetricsServer::start(port).await?),
+        None => None,
+    };
+
+    if let Some(Command::List(args)) = &config.command {
+        return run_list(args, &client, &chains).await;
+    }
 
-    // Retrieve the list of networks from the API, this will give us the metadata.
+    if config.check {
+        std::process::exit(run_check(&config, &client, &chains).await);
+    }
+
+    // Replays run entirely from the recording, without network access.
+    let recording = config.replay.as_deref().map(Recording::read).transpose()?;
+
+    // Retrieve the network metadata, from the recording when replaying or the API otherwise.
+    let metadata = match &recording {
+        Some(recording) => recording.metadata(config.metadata.as_deref())?,
+        None => {
+            let metadata = client.get_metadata().await?;
+            // Warn about a mismatching API version of the endpoint serving the metadata, the
+            // health endpoint is optional so failing to reach it doesn't prevent roller from starting.
+            if let Ok(health) = client.check_api_health().await {
+                if let Some(warning) = config.api_version_check(&health) {
+                    tracing::warn!(version = health.version, "{warning}");
+                    eprintln!("{warning}");
+                }
+            }
+            metadata
+        }
+    };
+    // Create the recording before connecting, embedding the metadata as its header.
+    let recorder = config
+        .record
+        .as_deref()
+        .map(|path| Recorder::create(path, &metadata))
+        .transpose()?;
+
+    // Build the list of networks from the metadata.
     // At this point, the Data field of the Network struct will be `None` and will be populated later on through SSE.
-    let networks = client.get_networks().await?;
+    let mut networks = roller::networks_from_metadata(metadata, &chains)?;
+    prices.apply(&mut networks);
+    // Warn about watched networks missing from the metadata, since they'd silently show nothing.
+    if let Some(warning) = config.unknown_networks_check(&networks) {
+        tracing::warn!("{warning}");
+        eprintln!("{warning}");
+    }
+    // Warn about filters excluding every network, which would leave the table empty.
+    report(config.filters_check(&networks).as_slice());
+    // Keep only the networks that pass the `--include`, `--exclude` and `--layer` filters.
+    let total = networks.len();
+    networks.retain(|n| config.is_included(n));
+    let hidden = total - networks.len();
     // Create a new Tui instance with the retrieved network metadata.
-    let mut tui = Tui::new(networks);
+    let mut tui = Tui::new(networks).with_config(&config);
+    tui.keys = keys;
+    tui.display.da = da;
+    tui.display.thresholds = thresholds;
+    tui.display.headers = headers;
+    #[cfg(feature = "prometheus")]
+    {
+        tui.metrics = metrics_server.as_ref().map(|server| server.metrics());
+    }
+    tui.hidden_networks = hidden;
+
+    // Get the SSE Event Stream from the recording or the API, recording it if enabled.
+    let source = match recording {
+        Some(recording) => {
+            EventSource::new(recording.into_stream(config.replay_speed), None, recorder)
+        }
+        None => EventSource::new(client.get_stream().await?, Some(&client), recorder),
+    };
 
-    // Get the SSE Event Stream from the API.
-    let mut stream = client.get_stream().await?;
+    // Keep the interface along `--follow` on a terminal stream of its own, stdout holding the records
+    let ui_output = config.ui_output();
+    let refresh = MetadataRefresh::new(&config, &client, &chains, &prices);
+    if config.follow {
+        if ui_output == UiOutput::Stdout || !ui_output.is_terminal() {
+            return run_follow(&config, source.stream, tui, refresh).await;
+        }
+        let follow = follow_writer(&config, &tui)?;
+        return run_interactive(&config, source, tui, refresh, Some(follow)).await;
+    }
 
-    // Enter the terminal mode
-    let mut terminal = roller::Terminal::enter()?;
+    // Fall back to a plain-text snapshot when the interface would be drawn to a file or pipe,
+    // instead of writing the control sequences of the terminal mode into it.
+    if config.once || config.export_html.is_some() || !ui_output.is_terminal() {
+        return run_once(&config, source.stream, tui).await;
+    }
 
-    // Start the main event loop
-    while let Ok(event) = stream.try_next().await {
-        // Check if an event is received
+    run_interactive(&config, source, tui, refresh, None).await
+}
 
-        if let Some(SSE::Event(event)) = event {
-            // Update the networks data in the Tui
-            tui.update_networks(event);
-            // Redraw the Tui on the terminal
-            terminal.draw(|f| tui.render(f))?;
+/// Owns the SSE stream of the session, so that the reconnect key can replace it.
+struct EventSource<'a> {
+    stream: EventStream,
+    /// The client establishing new streams, `None` when replaying a recording.
+    client: Option<&'a Client>,
+    /// The recorder tapping the stream, attached again to every new stream.
+    recorder: Option<Recorder>,
+}
+
+impl<'a> EventSource<'a> {
+    fn new(stream: EventStream, client: Option<&'a Client>, recorder: Option<Recorder>) -> Self {
+        let stream = match &recorder {
+            Some(recorder) => recorder.clone().tap(stream),
+            None => stream,
+        };
+
+        Self {
+            stream,
+            client,
+            recorder,
+        }
+    }
+
+    /// Tears down the current stream and establishes a new one through `Client::get_stream`,
+    /// resuming after the last event received. The current stream is kept if a new one can't be established.
+    async fn reconnect(&mut self, last_event_id: Option<String>) -> Result<(), String> {
+        let client = self
+            .client
+            .ok_or("a replay can't reconnect, it has no connection")?;
+        tracing::info!("reconnect requested");
+        client.set_last_event_id(last_event_id);
+        let stream = client.get_stream().await.map_err(|e| e.to_string())?;
+        self.stream = match &self.recorder {
+            Some(recorder) => recorder.clone().tap(stream),
+            None => stream,
+        };
+        Ok(())
+    }
+}
+
+/// Periodically re-fetches the network metadata, as configured by `--refresh-metadata`.
+struct MetadataRefresh<'a> {
+    config: &'a Config,
+    client: &'a Client,
+    chains: &'a ChainRegistry,
+    prices: &'a PriceRegistry,
+    timer: Option<tokio::time::Interval>,
+}
+
+impl<'a> MetadataRefresh<'a> {
+    fn new(
+        config: &'a Config,
+        client: &'a Client,
+        chains: &'a ChainRegistry,
+        prices: &'a PriceRegistry,
+    ) -> Self {
+        // The metadata has just been fetched, so the first refresh happens after a whole interval.
+        let timer = config.metadata_refresh_interval().map(|interval| {
+            tokio::time::interval_at(tokio::time::Instant::now() + interval, interval)
+        });
+
+        Self {
+            config,
+            client,
+            chains,
+            prices,
+            timer,
+        }
+    }
+
+    /// Waits until the next refresh is due, never completing if refreshing is disabled.
+    async fn tick(&mut self) {
+        match &mut self.timer {
+            Some(timer) => {
+                timer.tick().await;
+            }
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Re-fetches the network metadata and merges the networks passing the filters into the Tui.
+    /// A failed refresh is logged and skipped, the next one is attempted after the interval.
+    async fn refresh(&self, tui: &mut Tui) {
+        tui.last_refresh_attempt = Instant::now();
+        let result = self.client.get_networks(self.chains).await;
+        tui.refreshing = false;
+        match result {
+            Ok(mut networks) => {
+                networks.retain(|n| self.config.is_included(n));
+                self.prices.apply(&mut networks);
+                tui.merge_networks(networks);
+            }
+            Err(e) => tracing::warn!(error = %e, "failed to refresh the network metadata"),
+        }
+    }
+}
+
+/// The job control signals sent by the shell, see `JobControl`.
+enum JobSignal {
+    /// The process is asked to stop, with ctrl+z from outside the raw mode or `kill -TSTP`.
+    Stop,
+    /// The process continued after being stopped, such as with `fg`.
+    Continue,
+}
+
+/// Listens to the job control signals on Unix, so that the terminal is restored before the
+/// process stops and taken over again once it continues.
+struct JobControl {
+    #[cfg(unix)]
+    stop: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    resume: tokio::signal::unix::Signal,
+}
+
+impl JobControl {
+    /// Starts listening, replacing the default action of SIGTSTP, which stops the process
+    /// right away, with `Terminal::suspend_process`.
+    fn new() -> io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use signal_hook::consts::{SIGCONT, SIGTSTP};
+            use tokio::signal::unix::{signal, SignalKind};
+            Ok(Self {
+                stop: signal(SignalKind::from_raw(SIGTSTP))?,
+                resume: signal(SignalKind::from_raw(SIGCONT))?,
+            })
+        }
+        #[cfg(not(unix))]
+        Ok(Self {})
+    }
+
+    /// Waits for the next signal, never completing on other platforms.
+    async fn recv(&mut self) -> JobSignal {
+        #[cfg(unix)]
+        tokio::select! {
+            _ = self.stop.recv() => JobSignal::Stop,
+            _ = self.resume.recv() => JobSignal::Continue,
+        }
+        #[cfg(not(unix))]
+        std::future::pending().await
+    }
+}
+
+/// Prints the problems of the configuration to stderr and the logs.
+fn report(problems: &[Problem]) {
+    for problem in problems {
+        match problem.is_error() {
+            true => tracing::error!("{problem}"),
+            false => tracing::warn!("{problem}"),
         }
+        eprintln!("{problem}");
+    }
+}
+
+/// Runs the interactive terminal interface until the user quits, also writing the records of
+/// `--follow` to stdout if set, while the interface is drawn to another stream.
+async fn run_interactive(
+    config: &Config,
+    mut source: EventSource<'_>,
+    mut tui: Tui,
+    mut refresh: MetadataRefresh<'_>,
+    mut follow: Option<FollowWriter<Stdout>>,
+) -> Result<(), Box<dyn Error>> {
+    // Create the snapshot directory before entering the terminal mode, so errors are visible.
+    let mut snapshots = config.snapshot_writer()?;
 
-        // This serves two purposes:
-        // 1. Checking for user input
-        // 2. Blocking the thread, lowering the interval in which we process events.
-        //
-        // In a multi-thread scenario, we can collect and process multiple events while waiting
-        if crossterm::event::poll(Duration::from_millis(config.interval_ms))? {
-            // Read the user input event
-            if let CEvent::Key(key) = event::read()? {
-                match key.code {
-                    // Break the loop and exit if 'q' is received
-                    KeyCode::Char('q') => break,
+    // Restore the history of the previous session, starting over if it can't be read
+    let history_path = config.history_path();
+    if let Some(path) = &history_path {
+        match history::load(path, history::MAX_AGE) {
+            Ok(history) => {
+                let restored = tui.restore_history(history);
+                tracing::info!(path = %path.display(), networks = restored, "restored the history");
+            }
+            Err(e) => tracing::warn!(error = %e, "failed to restore the history"),
+        }
+    }
+
+    // Enter the terminal mode, restored by a panic before its message is printed
+    let options = EnterOptions {
+        mouse: config.mouse,
+        inline: config.inline,
+        output: config.ui_output(),
+        key_releases: EnterOptions::supports_key_releases(),
+    };
+    tui.key_releases = options.key_releases;
+    roller::Terminal::install_panic_hook(options);
+    let mut terminal = roller::Terminal::enter(options)?;
+    let mut jobs = JobControl::new()?;
+
+    // Measure the latency of the network in the detail pane
+    tui.latency = Some(LatencyMonitor::default());
+
+    let input_poll = config.input_poll();
+    let mut frames = FrameLimiter::new(config.frame_budget());
+    // Whether the state changed since the last frame
+    let mut dirty = true;
+
+    // Start the main event loop, until the stream ends or the user quits
+    'events: loop {
+        tokio::select! {
+            message = source.stream.try_next() => {
+                match message {
+                    Ok(Some(event)) => {
+                        tui.mark_connected();
+                        // Check if an event is received
+                        if let SSE::Event(event) = event {
+                            // Update the networks data in the Tui
+                            let name = event.event_type.clone();
+                            tui.update_networks(event);
+                            // Persist a snapshot, if enabled and due
+                            if let Some(snapshots) = &mut snapshots {
+                                snapshots.tick(&tui.networks());
+                            }
+                            // Write the record of `--follow`, if running along
+                            if let Some(follow) = &mut follow {
+                                follow.write(&tui.top_networks(), &name)?;
+                            }
+                        }
+                    }
+                    Ok(None) => break,
+                    // The stream reconnects on its own after `--retry-delay`, until then the
+                    // connection indicator reports the stream as disconnected.
+                    Err(e) => {
+                        tracing::error!(error = %e, "event stream failed");
+                        tui.connected = false;
+                        #[cfg(feature = "prometheus")]
+                        if let Some(metrics) = &tui.metrics {
+                            metrics.record_reconnect();
+                        }
+                    }
+                }
+                // Keep-alive messages refresh the connection indicator too
+                dirty = true;
+            }
+            _ = refresh.tick() => {
+                // Draw the "Refreshing metadata…" footer while waiting for the API
+                tui.refreshing = true;
+                terminal.draw(|f| tui.render(f))?;
+                refresh.refresh(&mut tui).await;
+                dirty = true;
+            }
+            signal = jobs.recv() => {
+                match signal {
+                    JobSignal::Stop => terminal.suspend_process()?,
+                    // Also stopped by SIGSTOP, which can't be handled, so take the terminal over
+                    JobSignal::Continue => terminal.resume()?,
+                }
+                dirty = true;
+            }
+            // Wake up at least every `--input-poll-ms`, to check for user input between events
+            _ = tokio::time::sleep(input_poll) => {}
+        }
+
+        // Handle every pending input event, without blocking the processing of events
+        let mut pressed = false;
+        while event::poll(Duration::ZERO)? {
+            match event::read()? {
+                // Only reported with the keyboard enhancement, and on Windows, ending sort previews
+                CEvent::Key(key) if key.kind == KeyEventKind::Release => {
+                    tui.handle_key(key);
+                    dirty = true;
+                }
+                CEvent::Key(key) => match tui.keys.action(&key) {
+                    // Break the loop and exit on the quit key, unless it's typed into the search
+                    Some(Action::Quit) if !tui.is_typing() => {
+                        tracing::info!("quit requested");
+                        break 'events;
+                    }
+                    // Stop like the shell would on ctrl+z, which the raw mode delivers as a key
+                    Some(Action::Suspend) if !tui.is_typing() => {
+                        tracing::info!("suspend requested");
+                        terminal.suspend_process()?;
+                        dirty = true;
+                    }
                     // For any other key, pass it to the Tui for handling
-                    _ => tui.handle_input(key.code),
+                    _ => {
+                        tui.handle_key(key);
+                        pressed = true;
+                        dirty = true;
+                    }
+                },
+                // Only captured with `--mouse`
+                CEvent::Mouse(mouse) if tui.handle_mouse(mouse) => dirty = true,
+                // Wipe the screen and redraw at the new size right away, rather than waiting for
+                // the next event, so the previous layout doesn't linger
+                CEvent::Resize(..) => {
+                    tui.resized();
+                    terminal.clear()?;
+                    terminal.draw(|f| tui.render(f))?;
                 }
+                _ => {}
+            }
+        }
+        // No key arrived, so a held sorting key may have been released
+        if !pressed && tui.release_keys() {
+            dirty = true;
+        }
+        // Replace the stream on a press of the reconnect key, keeping the data of the networks
+        if tui.take_reconnect() {
+            // Draw the "Reconnecting…" status before establishing the new stream
+            terminal.draw(|f| tui.render(f))?;
+            let result = source.reconnect(tui.last_event_id.clone()).await;
+            if let Err(e) = &result {
+                tracing::warn!(error = %e, "failed to reconnect");
             }
+            tui.reconnected(result);
+            dirty = true;
+        }
+        // Start the pings that are due and collect the finished ones, without waiting for them
+        if tui.poll_latency() {
+            dirty = true;
+        }
+
+        // Redraw at most once per frame budget, later wake-ups draw the changes skipped meanwhile
+        if dirty && frames.ready(Instant::now()) {
+            terminal.draw(|f| tui.render(f))?;
+            dirty = false;
         }
     }
 
     // Exit the terminal mode and restore the previous terminal state
     terminal.exit()?;
 
+    // Save the history for the next session
+    if let Some(path) = &history_path {
+        if let Err(e) = history::save(path, &tui.networks()) {
+            tracing::warn!(error = %e, "failed to save the history");
+        }
+    }
+
     Ok(())
 }
+
+/// Consumes the SSE stream until every network has received an update or the `--once-timeout-ms`
+/// elapses, then prints a plain-text snapshot of the table to stdout, and writes it to `--export-html` if set.
+///
+/// The terminal mode is never entered, so the output is safe to redirect or pipe.
+async fn run_once(
+    config: &Config,
+    mut stream: EventStream,
+    mut tui: Tui,
+) -> Result<(), Box<dyn Error>> {
+    let timeout = tokio::time::sleep(Duration::from_millis(config.once_timeout_ms));
+    tokio::pin!(timeout);
+
+    while !tui.all_updated() {
+        tokio::select! {
+            _ = &mut timeout => break,
+            event = stream.try_next() => match event {
+                Ok(Some(SSE::Event(event))) => tui.update_networks(event),
+                Ok(Some(_)) => {}
+                // The stream ended or failed, print whatever has been received so far.
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::error!(error = %e, "event stream failed");
+                    break;
+                }
+            },
+        }
+    }
+
+    if !tui.any_updated() {
+        tracing::error!("no network updates were received before the timeout");
+        return Err("no network updates were received before the timeout".into());
+    }
+
+    let networks = tui.top_networks();
+    print!(
+        "{}",
+        output::render(
+            config.format,
+            &networks,
+            &tui.columns,
+            tui.display.precision,
+            tui.display.units,
+        )?
+    );
+    if let Some(path) = &config.export_html {
+        let display = &tui.display;
+        let html = output::html_table(&networks, &tui.columns, display.precision, display.units);
+        std::fs::write(path, html)
+            .map_err(|e| format!("failed to write --export-html {}: {e}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Prints to stdout after every network update, until the stream ends, see `FollowWriter`.
+///
+/// Like `--once`, the terminal mode is never entered.
+async fn run_follow(
+    config: &Config,
+    mut stream: EventStream,
+    mut tui: Tui,
+    mut refresh: MetadataRefresh<'_>,
+) -> Result<(), Box<dyn Error>> {
+    let mut snapshots = config.snapshot_writer()?;
+    let mut follow = follow_writer(config, &tui)?;
+
+    loop {
+        let message = tokio::select! {
+            message = stream.try_next() => message,
+            _ = refresh.tick() => {
+                refresh.refresh(&mut tui).await;
+                continue;
+            }
+        };
+        let Ok(Some(event)) =
+            message.inspect_err(|e| tracing::error!(error = %e, "event stream failed"))
+        else {
+            break;
+        };
+        let SSE::Event(event) = event else { continue };
+        let name = event.event_type.clone();
+        tui.update_networks(event);
+        if let Some(snapshots) = &mut snapshots {
+            snapshots.tick(&tui.networks());
+        }
+        follow.write(&tui.top_networks(), &name)?;
+    }
+
+    Ok(())
+}
+
+/// Creates the writer of `--follow` to stdout, with the columns and formatting of the Tui.
+fn follow_writer(config: &Config, tui: &Tui) -> Result<FollowWriter<Stdout>, Box<dyn Error>> {
+    FollowWriter::new(
+        io::stdout(),
+        config.format,
+        &tui.columns,
+        tui.display.precision,
+        tui.display.units,
+    )
+}
+
+/// Prints the networks of the metadata that pass the filters of `roller list`, sorted by name,
+/// without entering the terminal mode.
+async fn run_list(
+    args: &ListArgs,
+    client: &Client,
+    chains: &ChainRegistry,
+) -> Result<(), Box<dyn Error>> {
+    let mut networks = client.get_networks(chains).await?;
+    networks.retain(|n| args.matches(n));
+    networks.sort_by(|a, b| a.name.cmp(&b.name));
+
+    print!("{}", output::render_list(args.format, &networks)?);
+    Ok(())
+}
+
+/// Writes the default configuration file of `roller init`, see `roller::init::template`.
+fn run_init(args: &InitArgs) -> Result<(), Box<dyn Error>> {
+    let path = match &args.path {
+        Some(path) => path.clone(),
+        None => init::default_path()
+            .ok_or("no home directory to write the configuration to, pass --path")?,
+    };
+    init::write(&path, args.force)?;
+    println!(
+        "Wrote the default configuration to {}, pass it with --config or ROLLER_CONFIG.",
+        path.display()
+    );
+    Ok(())
+}
+
+/// Verifies the configuration and connectivity to the API, printing a report to stdout.
+///
+/// Retrieves the network metadata and waits briefly for the first message of the SSE stream,
+/// without entering the terminal mode.
+///
+/// ### Returns
+/// The exit code of the process, `0` if every step succeeded.
+async fn run_check(config: &Config, client: &Client, chains: &ChainRegistry) -> i32 {
+    let started = Instant::now();
+    let networks = match client.get_networks(chains).await {
+        Ok(networks) => networks,
+        Err(e) => {
+            println!("endpoint: {}", config.api_endpoints.join(", "));
+            println!("metadata: failed, {e}");
+            return EXIT_METADATA_FAILED;
+        }
+    };
+    // The endpoint that served the metadata, after falling back past the failing ones
+    println!("endpoint: {}", client.active_endpoint());
+    let included = networks.iter().filter(|n| config.is_included(n)).count();
+    println!(
+        "metadata: ok, {} networks ({included} after filters) in {} ms",
+        networks.len(),
+        started.elapsed().as_millis()
+    );
+
+    let started = Instant::now();
+    let mut stream = match client.get_stream().await {
+        Ok(stream) => stream,
+        Err(e) => {
+            println!("stream: failed, {e}");
+            return EXIT_STREAM_FAILED;
+        }
+    };
+    match tokio::time::timeout(CHECK_STREAM_TIMEOUT, stream.try_next()).await {
+        Ok(Ok(Some(_))) => {
+            println!(
+                "stream: ok, first message in {} ms",
+                started.elapsed().as_millis()
+            );
+            0
+        }
+        Ok(Ok(None)) => {
+            println!("stream: failed, closed before any message was received");
+            EXIT_STREAM_FAILED
+        }
+        Ok(Err(e)) => {
+            println!("stream: failed, {e}");
+            EXIT_STREAM_FAILED
+        }
+        Err(_) => {
+            println!(
+                "stream: failed, no message within {} s",
+                CHECK_STREAM_TIMEOUT.as_secs()
+            );
+            EXIT_STREAM_FAILED
+        }
+    }
+}
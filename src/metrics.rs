@@ -0,0 +1,218 @@
+use crate::{network::Data, Network};
+use std::{
+    fmt::Write,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+/// The largest request head read by the server, only the request line is used anyway.
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+
+/// A gauge exposed for every network, as `(name, help, metric)`.
+type Gauge = (&'static str, &'static str, fn(&Data) -> f64);
+
+/// The gauges exposed for every network that received an update.
+const GAUGES: [Gauge; 4] = [
+    (
+        "roller_tps",
+        "Transactions per second of the network.",
+        |d| d.tps as f64,
+    ),
+    (
+        "roller_gps",
+        "Gas per second of the network, in MGas/s.",
+        |d| d.gps as f64,
+    ),
+    (
+        "roller_dps",
+        "Data per second of the network, in KB/s.",
+        |d| d.dps as f64,
+    ),
+    (
+        "roller_block_number",
+        "Latest block number of the network.",
+        |d| d.block_number as f64,
+    ),
+];
+
+/// The state exposed by the metrics endpoint.
+#[derive(Debug, Default)]
+struct State {
+    /// The latest data of each network that received an update, in table order.
+    networks: Vec<(String, Data)>,
+    /// The number of events received from the SSE stream.
+    events_received: u64,
+    /// The number of times the SSE stream failed and reconnected.
+    reconnects: u64,
+}
+
+/// The metrics of a session, shared between the interface updating them and the server exposing them.
+///
+/// Cloning it is cheap, every clone refers to the same metrics.
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    state: Arc<Mutex<State>>,
+}
+
+impl Metrics {
+    /// Records a received event, along with the data of the networks once it's been applied.
+    ///
+    /// ### Arguments
+    /// * `networks` - The networks of the interface.
+    pub fn record_event(&self, networks: &[&Network]) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.events_received += 1;
+        state.networks = networks
+            .iter()
+            .filter_map(|n| Some((n.name.clone(), n.data.clone()?)))
+            .collect();
+    }
+
+    /// Records a failure of the SSE stream, after which it reconnects.
+    pub fn record_reconnect(&self) {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .reconnects += 1;
+    }
+
+    /// Renders the metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+        for (name, help, metric) in GAUGES {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} gauge");
+            for (network, data) in &state.networks {
+                let _ = writeln!(
+                    out,
+                    "{name}{{network=\"{}\"}} {}",
+                    escape(network),
+                    metric(data)
+                );
+            }
+        }
+        let _ = writeln!(
+            out,
+            "# HELP roller_events_received_total Events received from the SSE stream.\n\
+             # TYPE roller_events_received_total counter\n\
+             roller_events_received_total {}",
+            state.events_received
+        );
+        let _ = writeln!(
+            out,
+            "# HELP roller_reconnects_total Failures of the SSE stream, each followed by a reconnection.\n\
+             # TYPE roller_reconnects_total counter\n\
+             roller_reconnects_total {}",
+            state.reconnects
+        );
+        out
+    }
+}
+
+/// Escapes a label value of the exposition format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// The metrics endpoint, serving `/metrics` on localhost until dropped.
+pub struct MetricsServer {
+    metrics: Metrics,
+    port: u16,
+    task: JoinHandle<()>,
+}
+
+impl MetricsServer {
+    /// Binds the port on localhost and starts serving the metrics on the current runtime.
+    ///
+    /// ### Arguments
+    /// * `port` - The port to listen on, `0` picks a free one.
+    ///
+    /// ### Returns
+    /// The running server, or an error if the port can't be bound, e.g. when it's already in use.
+    pub async fn start(port: u16) -> Result<Self, String> {
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .await
+            .map_err(|e| format!("failed to serve the metrics on port {port}: {e}"))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("failed to serve the metrics on port {port}: {e}"))?
+            .port();
+        tracing::info!(port, "serving the metrics");
+
+        let metrics = Metrics::default();
+        let task = tokio::spawn(serve(listener, metrics.clone()));
+        Ok(Self {
+            metrics,
+            port,
+            task,
+        })
+    }
+
+    /// Returns the metrics exposed by the server.
+    pub fn metrics(&self) -> Metrics {
+        self.metrics.clone()
+    }
+
+    /// Returns the port the server listens on.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+/// Stops accepting connections once the server is dropped, such as when roller exits.
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Accepts connections, answering each of them on its own task.
+async fn serve(listener: TcpListener, metrics: Metrics) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(respond(stream, metrics.clone()));
+            }
+            Err(e) => tracing::warn!(error = %e, "failed to accept a metrics connection"),
+        }
+    }
+}
+
+/// Reads the head of a request and answers it, closing the connection afterwards.
+async fn respond(mut stream: TcpStream, metrics: Metrics) {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_SIZE {
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => request.extend_from_slice(&buffer[..n]),
+        }
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let (status, body) = match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", metrics.render()),
+        (Some("GET"), Some(_)) => ("404 Not Found", String::new()),
+        _ => ("400 Bad Request", String::new()),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    if let Err(e) = stream.write_all(response.as_bytes()).await {
+        tracing::debug!(error = %e, "failed to answer a metrics request");
+    }
+    let _ = stream.shutdown().await;
+}
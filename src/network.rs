@@ -1,8 +1,52 @@
-use ratatui::{text::Text, widgets::Row};
-use serde::{Deserialize, Deserializer};
+use crate::{
+    anomaly::{self, ANOMALY_MARKER, HISTORY_LEN},
+    column::DisplayOptions,
+    format, ChainRegistry, Column, GasPrice, SortingStrategy,
+};
+use clap::ValueEnum;
+use ratatui::{
+    text::{Line, Span, Text},
+    widgets::{Cell, Row},
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// The frames of the spinner displayed in the metric cells of networks without data.
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// The badge appended to the label of a network discovered while running, see `Network::is_new`.
+pub const NEW_BADGE: &str = " [NEW]";
+
+/// How long the label of a discovered network carries the `NEW_BADGE`.
+pub const NEW_BADGE_DURATION: Duration = Duration::from_secs(60);
+
+/// The emoji of the known parent chains, as `(name, emoji)` pairs, see `chain_emoji`.
+pub const CHAIN_EMOJI: [(&str, &str); 4] = [
+    ("ethereum", "⟠"),
+    ("base", "🔵"),
+    ("arbitrum", "🔴"),
+    ("polygon", "🟣"),
+];
+
+/// Returns the emoji prepended to the name of a parent chain in the "Settlement" column.
+///
+/// ### Arguments
+/// * `chain` - The name of the chain, matched ignoring case.
+///
+/// ### Returns
+/// The emoji of the chain, or an empty string for unknown chains.
+pub fn chain_emoji(chain: &str) -> &'static str {
+    CHAIN_EMOJI
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(chain))
+        .map_or("", |(_, emoji)| emoji)
+}
 
 /// Represents the data associated with a network.
-#[derive(Debug, serde::Deserialize, Default, Clone)]
+#[derive(Debug, serde::Deserialize, serde::Serialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Data {
     // The current block number of the network.
@@ -10,12 +54,16 @@ pub struct Data {
     // The transactions per second of the network.
     #[serde(deserialize_with = "deserialize_string_to_f32")]
     pub tps: f32,
-    // The gas per second of the network.
+    // The gas per second of the network, in millions of gas (MGas/s) as reported by the API,
+    // so it's displayed as-is under the "MGas/s" header.
     #[serde(deserialize_with = "deserialize_string_to_f32")]
     pub gps: f32,
-    // The data per second of the network, is processing.
+    // The data per second of the network, is processing. In kilobytes (KB/s) as reported by the API.
     #[serde(deserialize_with = "deserialize_string_to_f32")]
     pub dps: f32,
+    // The number of transactions waiting in the mempool, when provided by the API.
+    #[serde(default, deserialize_with = "deserialize_optional_string_to_u64")]
+    pub pending_tx_count: Option<u64>,
     //
     // The fields below are disabled until required.
     //
@@ -25,16 +73,86 @@ pub struct Data {
     // pub tx_count: u64,
 }
 
-#[derive(Debug, serde::Deserialize, Default, Clone)]
+impl Data {
+    /// Replaces the metrics a buggy backend could report but no network can process, negative,
+    /// NaN or infinite values, with zero, so they can't corrupt the sorting or the totals.
+    ///
+    /// ### Returns
+    /// The names of the metrics replaced, empty if the data was valid.
+    pub fn sanitize(&mut self) -> Vec<&'static str> {
+        let mut invalid = Vec::new();
+        for (name, value) in [
+            ("tps", &mut self.tps),
+            ("gps", &mut self.gps),
+            ("dps", &mut self.dps),
+        ] {
+            if !value.is_finite() || *value < 0.0 {
+                invalid.push(name);
+                *value = 0.0;
+            }
+        }
+        invalid
+    }
+
+    /// Returns the average gas used by a transaction, in thousands of gas (KGas/tx).
+    ///
+    /// ### Returns
+    /// The ratio of the gas and transactions per second, or `0` when no transactions are processed.
+    pub fn gas_per_tx(&self) -> f32 {
+        match self.tps > 0.0 {
+            true => self.gps * 1_000.0 / self.tps,
+            false => 0.0,
+        }
+    }
+}
+
+/// The layers of networks displayed by `--layer`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum Layer {
+    /// Only the base layers, networks without a parent chain.
+    L1,
+    /// Only the networks settling to a parent chain.
+    L2,
+    /// Every network.
+    #[default]
+    All,
+}
+
+impl Layer {
+    /// Checks whether a network belongs to the layer.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to check.
+    ///
+    /// ### Returns
+    /// `true` if the network should be displayed.
+    pub fn matches(&self, network: &Network) -> bool {
+        match self {
+            Layer::L1 => network.is_l1(),
+            Layer::L2 => !network.is_l1(),
+            Layer::All => true,
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Network {
     // The name of the network. This is also the networks ID.
     pub name: String,
     // The label/human-readable name of the network.
     pub label: String,
-    #[serde(deserialize_with = "deserialize_chain_id_to_name")]
-    // The parent chain, which is also used as a Settlment Layer
-    // in the case of an Layer 2 network.
+    // The chain ID of the parent chain, as provided by the API.
+    // Empty or `0` if the network has none, which makes it an L1.
+    #[serde(
+        rename(deserialize = "parentChain"),
+        deserialize_with = "deserialize_chain_id",
+        skip_serializing
+    )]
+    pub parent_chain_id: u64,
+    // The name of the parent chain, which is also used as a Settlment Layer
+    // in the case of an Layer 2 network. Resolved from `parent_chain_id` through the `ChainRegistry`.
+    #[serde(skip_deserializing)]
     pub parent_chain: String,
     // The data availability layer of the network.
     pub da: String,
@@ -43,28 +161,53 @@ pub struct Network {
     // The data associated with the network.
     // Populated through SSE (server-side event) data after initialization.
     pub data: Option<Data>,
+    // The block explorer URL of the network, if provided by the API.
+    #[serde(default)]
+    pub explorer: Option<String>,
+    // The website URL of the network, if provided by the API, pinged by the detail pane.
+    #[serde(default)]
+    pub website: Option<String>,
+    // The static prices of the network, from the `[prices]` section of the configuration file.
+    #[serde(skip)]
+    pub gas_price: Option<GasPrice>,
+    // The time of the last data update, `None` until the first SSE event is received.
+    #[serde(skip)]
+    pub updated_at: Option<Instant>,
+    // The data before the last update, used to compute the change of the metrics.
+    #[serde(skip)]
+    pub previous_data: Option<Data>,
+    // The number of consecutive updates that reported the same block number.
+    #[serde(skip)]
+    pub block_unchanged_count: u32,
+    // The data of the past updates, oldest first, up to `HISTORY_LEN`, used to detect anomalies.
+    #[serde(skip)]
+    pub history: VecDeque<Data>,
+    // The payload of the last event received for the network, as sent by the API.
+    #[serde(skip)]
+    pub last_raw_json: Option<String>,
+    // The time the network was discovered by a metadata refresh, `None` for the initial ones.
+    #[serde(skip)]
+    pub added_at: Option<Instant>,
     //
     // The fields below are disabled until required.
     //
     // pub provider: String,
-    // pub website: String,
-    // pub explorer: String,
 }
 
-/// Deserializes a chain ID into a chain name.
+/// Deserializes a chain ID, received as a string, into a u64.
+/// An empty or unparsable chain ID is treated as `0`, the parent chain of L1 networks.
 ///
 /// ### Arguments
 /// * `deserializer` - The deserializer used to deserialize the chain ID,serde in this case.
 ///
 /// ### Returns
-/// The chain name corresponding to the deserialized chain ID.
-pub fn deserialize_chain_id_to_name<'de, D>(deserializer: D) -> Result<String, D::Error>
+/// The deserialized chain ID.
+pub fn deserialize_chain_id<'de, D>(deserializer: D) -> Result<u64, D::Error>
 where
     D: Deserializer<'de>,
 {
     let chain_id: String = Deserialize::deserialize(deserializer)?;
-    let chain_id: u64 = chain_id.parse().unwrap_or_default();
-    Ok(Network::name_from_chain_id(chain_id))
+    Ok(chain_id.parse().unwrap_or_default())
 }
 
 /// Deserializes a string into a f32. The fields utilizing this deserialization function
@@ -84,6 +227,22 @@ where
     Ok(value.parse().unwrap_or_default())
 }
 
+/// Deserializes an optional string into a u64. Like `deserialize_string_to_f32`, the values
+/// are received as Strings, an unparsable value is treated as missing.
+///
+/// ### Arguments
+/// * `deserializer` - The deserializer used to deserialize the value, serde in this case.
+///
+/// ### Returns
+/// The u64 value of the deserialized string, or `None` if it's missing or unparsable.
+pub fn deserialize_optional_string_to_u64<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<String> = Deserialize::deserialize(deserializer)?;
+    Ok(value.and_then(|v| v.parse().ok()))
+}
+
 impl Network {
     /// A new instance of `Network` with the provided parameters.
     pub fn new(
@@ -97,53 +256,251 @@ impl Network {
         Self {
             name,
             label,
+            parent_chain_id: 0,
             parent_chain,
             da,
             stack,
             data,
+            explorer: None,
+            website: None,
+            gas_price: None,
+            updated_at: None,
+            previous_data: None,
+            block_unchanged_count: 0,
+            history: VecDeque::new(),
+            last_raw_json: None,
+            added_at: None,
         }
     }
 
-    /// Updates the data associated with the network.
+    /// Updates the data associated with the network and records the time of the update.
+    /// The replaced data is kept to compute the change of the metrics, see `delta`, and updates
+    /// reporting the same block number as the previous one are counted in `block_unchanged_count`.
+    /// The replaced data is also appended to the `history`, dropping the oldest past `HISTORY_LEN`.
     ///
     /// ### Arguments
     /// * `data` - The new data of the network.
     pub fn update_data(&mut self, data: Option<Data>) {
-        self.data = data;
+        let unchanged = matches!(
+            (&self.data, &data),
+            (Some(old), Some(new)) if old.block_number == new.block_number
+        );
+        self.block_unchanged_count = match unchanged {
+            true => self.block_unchanged_count.saturating_add(1),
+            false => 0,
+        };
+        self.previous_data = std::mem::replace(&mut self.data, data);
+        if let Some(previous) = &self.previous_data {
+            if self.history.len() == HISTORY_LEN {
+                self.history.pop_front();
+            }
+            self.history.push_back(previous.clone());
+        }
+        self.updated_at = Some(Instant::now());
     }
 
-    /// {Unstable} Converts the network into a ratatui `Row` widget.
-    /// It will get ownership of the data field before returning.
+    /// Computes the z-score of the current value of a metric against its `history`.
+    ///
+    /// ### Arguments
+    /// * `strategy` - The strategy whose metric to compare.
     ///
     /// ### Returns
-    /// A `Row` widget containing the network's data in a formatted manner.
-    pub fn to_row(&self) -> Row {
-        let data = self.data.to_owned().unwrap_or_default();
-        Row::new(vec![
-            Text::raw(&self.label),
-            Text::raw(data.block_number.to_string()),
-            Text::raw(data.tps.to_string()),
-            Text::raw(data.gps.to_string()),
-            Text::raw(data.dps.to_string()),
-            Text::raw(&self.stack),
-            Text::raw(&self.da),
-            Text::raw(&self.parent_chain),
-        ])
-    }
-
-    /// Returns the chain name based on the chain ID.
+    /// The z-score, see `anomaly::z_score`, or `None` without enough history or data.
+    pub fn z_score(&self, strategy: SortingStrategy) -> Option<f32> {
+        let data = self.data.as_ref()?;
+        let history: Vec<f32> = self.history.iter().map(|d| strategy.metric(d)).collect();
+        anomaly::z_score(&history, strategy.metric(data))
+    }
+
+    /// Checks whether the transactions, gas or data per second of the network are an outlier
+    /// against its recent history, a spike or a drop.
+    ///
+    /// ### Arguments
+    /// * `threshold` - The absolute z-score from which a value is an outlier, `None` disabling the check.
+    pub fn is_anomalous(&self, threshold: Option<f32>) -> bool {
+        let Some(threshold) = threshold else {
+            return false;
+        };
+        [
+            SortingStrategy::Tps,
+            SortingStrategy::Gps,
+            SortingStrategy::Dps,
+        ]
+        .into_iter()
+        .filter_map(|strategy| self.z_score(strategy))
+        .any(|z| z.abs() >= threshold)
+    }
+
+    /// Returns the change of the metric of a sorting strategy over the last update.
     ///
     /// ### Arguments
-    /// * `id` - The chain ID.
+    /// * `strategy` - The strategy whose metric to compare.
     ///
     /// ### Returns
-    /// The chain name corresponding to the chain ID, or "unknown" if the ID is not recognized or listed.
-    fn name_from_chain_id(id: u64) -> String {
-        match id {
-            1 => String::from("ethereum"),
-            8453 => String::from("base"),
-            42161 => String::from("arbitrum"),
-            _ => String::from("unknown"),
+    /// The difference between the current and previous value, or `None` before the second update.
+    pub fn delta(&self, strategy: SortingStrategy) -> Option<f32> {
+        let (data, previous) = (self.data.as_ref()?, self.previous_data.as_ref()?);
+        Some(strategy.metric(data) - strategy.metric(previous))
+    }
+
+    /// Checks whether the network is active, meaning it received an update that isn't stale,
+    /// see `is_stale`, and reported any transactions, gas or data being processed.
+    ///
+    /// ### Arguments
+    /// * `stale_after` - The time after which the last update is considered stale, if enabled.
+    pub fn is_active(&self, stale_after: Option<Duration>) -> bool {
+        let recent = self.updated_at.is_some() && !self.is_stale(stale_after, Instant::now());
+        let busy = self
+            .data
+            .as_ref()
+            .is_some_and(|d| d.tps > 0.0 || d.gps > 0.0 || d.dps > 0.0);
+        recent && busy
+    }
+
+    /// Checks whether the last update of the network is older than `stale_after`.
+    /// Networks that never received an update are still loading, rather than stale.
+    ///
+    /// ### Arguments
+    /// * `stale_after` - The time after which the last update is considered stale, `None`
+    ///   disabling the check.
+    /// * `now` - The current time.
+    pub fn is_stale(&self, stale_after: Option<Duration>, now: Instant) -> bool {
+        match (stale_after, self.updated_at) {
+            (Some(stale_after), Some(updated_at)) => {
+                now.saturating_duration_since(updated_at) >= stale_after
+            }
+            _ => false,
         }
     }
+
+    /// Checks whether the network was discovered less than `NEW_BADGE_DURATION` ago.
+    ///
+    /// ### Arguments
+    /// * `now` - The current time.
+    pub fn is_new(&self, now: Instant) -> bool {
+        self.added_at
+            .is_some_and(|added_at| now.saturating_duration_since(added_at) < NEW_BADGE_DURATION)
+    }
+
+    /// {Unstable} Converts the network into a ratatui `Row` widget.
+    ///
+    /// Until the network receives its first update, the block cell reads "loading…" and the
+    /// other metric cells display a spinner, advancing with every rendered frame.
+    ///
+    /// ### Arguments
+    /// * `columns` - The columns to include in the row, in order.
+    /// * `options` - The display options used to format and style the cells.
+    /// * `frame` - The number of frames rendered so far, used to animate the spinner.
+    /// * `label_width` - The width of the `Network` column, longer labels are truncated with "…",
+    ///   or wrapped at word boundaries with `wrap_labels`, the row growing as tall as the label
+    ///   and the other cells being centered vertically.
+    ///   Labels of anomalous networks, see `is_anomalous`, are prefixed with "⚡" and styled as such,
+    ///   while those of new networks, see `is_new`, end with the "[NEW]" badge, both within the width.
+    ///
+    /// ### Returns
+    /// A `Row` widget containing the network's data in a formatted manner.
+    pub fn to_row(
+        &self,
+        columns: &[Column],
+        options: &DisplayOptions,
+        frame: u64,
+        label_width: Option<usize>,
+    ) -> Row<'_> {
+        let anomalous = self.is_anomalous(options.anomaly_z);
+        // The marker is drawn two cells wide, followed by a space
+        let (marker, marker_width) = match anomalous {
+            true => (format!("{ANOMALY_MARKER} "), 3),
+            false => (String::new(), 0),
+        };
+        let badge = match self.is_new(Instant::now()) {
+            true => NEW_BADGE,
+            false => "",
+        };
+        let reserved = marker_width + badge.chars().count();
+        let label_lines = match label_width {
+            Some(width) if options.wrap_labels => {
+                format::wrap_words(&self.label, width.saturating_sub(reserved))
+            }
+            Some(width) => vec![format::truncate_to_width(
+                &self.label,
+                width.saturating_sub(reserved),
+            )],
+            None => vec![self.label.clone()],
+        };
+        let height = label_lines.len();
+        let label_style = match anomalous {
+            true => options.theme.anomaly,
+            false => Default::default(),
+        };
+        let label = label_lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| match i {
+                0 => Line::from(vec![
+                    Span::raw(marker.clone() + &line),
+                    Span::styled(badge, options.theme.new_badge),
+                ]),
+                _ => Line::from(" ".repeat(marker_width) + &line),
+            })
+            .collect::<Vec<_>>();
+
+        // Centers the single-line cells vertically within a row taller than one line
+        let centered = move |content: String| {
+            let mut lines = vec![Line::default(); (height - 1) / 2];
+            lines.push(Line::from(content));
+            Text::from(lines)
+        };
+        let cells = columns
+            .iter()
+            .map(move |column| match (&self.data, column) {
+                (_, Column::Network) => Cell::from(Text::from(label.clone())).style(label_style),
+                (None, Column::Block) => Cell::from(centered(String::from("loading…"))),
+                (None, column) if column.is_metric() => Cell::from(centered(
+                    SPINNER[(frame % SPINNER.len() as u64) as usize].to_string(),
+                )),
+                _ => {
+                    let (content, style) = column.styled_value(self, options);
+                    Cell::from(centered(content)).style(style)
+                }
+            });
+        Row::new(cells).height(height as u16)
+    }
+
+    /// Formats the metadata of the network as pretty JSON: every serialized field but the `data`
+    /// of the last update, as listed by `roller list --format json`.
+    ///
+    /// ### Returns
+    /// The JSON document, or an error if serialization fails.
+    pub fn metadata_json(&self) -> serde_json::Result<String> {
+        let mut metadata = serde_json::to_value(self)?;
+        if let Some(fields) = metadata.as_object_mut() {
+            fields.remove("data");
+        }
+        serde_json::to_string_pretty(&metadata)
+    }
+
+    /// Checks whether the network is an L1, one without a parent chain to settle to.
+    ///
+    /// ### Returns
+    /// `true` if the API reports no parent chain for the network.
+    pub fn is_l1(&self) -> bool {
+        self.parent_chain_id == 0
+    }
+
+    /// Estimates the cost of the gas used by the network per second, see `GasPrice::usd_per_second`.
+    ///
+    /// ### Returns
+    /// The cost in USD per second, or `None` without prices or data.
+    pub fn gas_cost_usd(&self) -> Option<f64> {
+        let data = self.data.as_ref()?;
+        Some(self.gas_price?.usd_per_second(data.gps))
+    }
+
+    /// Resolves the name of the parent chain from its chain ID.
+    ///
+    /// ### Arguments
+    /// * `chains` - The registry of chain names.
+    pub fn resolve_parent_chain(&mut self, chains: &ChainRegistry) {
+        self.parent_chain = chains.name(self.parent_chain_id);
+    }
 }
@@ -0,0 +1,409 @@
+use crate::{format::Units, Column, Network};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{
+    error::Error,
+    io::{self, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The separator placed between the columns of the plain-text table.
+const COLUMN_SEPARATOR: &str = "  ";
+
+/// The inline styles of the HTML table, after the slate palette of the interface.
+const HTML_TABLE_STYLE: &str = "border-collapse: collapse; background: #0f172a; color: #94a3b8; font-family: ui-monospace, monospace; font-size: 14px";
+/// The inline styles of the header cells of the HTML table.
+const HTML_HEADER_STYLE: &str =
+    "padding: 4px 12px; color: #e2e8f0; border-bottom: 1px solid #475569; text-align: left";
+/// The inline styles of the body cells of the HTML table.
+const HTML_CELL_STYLE: &str = "padding: 4px 12px";
+
+/// The formats available to the non-interactive output modes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum OutputFormat {
+    /// A plain-text, aligned table respecting `--columns`.
+    #[default]
+    Table,
+    /// A JSON document with every metric and metadata field of the networks.
+    Json,
+    /// Comma-separated values respecting `--columns`, with metrics in full precision.
+    Csv,
+}
+
+/// Returns the current time in milliseconds since the UNIX epoch.
+pub fn timestamp_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// The sum of the metrics over all networks of a snapshot.
+#[derive(Debug, Default, Serialize)]
+pub struct Totals {
+    /// The number of networks in the snapshot.
+    pub networks: usize,
+    /// The combined transactions per second.
+    pub tps: f32,
+    /// The combined gas per second.
+    pub gps: f32,
+    /// The combined data per second.
+    pub dps: f32,
+}
+
+/// The envelope of the JSON output, see the "JSON Output" section of the README for the schema.
+#[derive(Debug, Serialize)]
+pub struct Snapshot<'a> {
+    /// The time the snapshot was taken, in milliseconds since the UNIX epoch.
+    pub timestamp: u64,
+    /// The sum of the metrics over all networks.
+    pub totals: Totals,
+    /// The networks, in the current sorting order.
+    pub networks: &'a [&'a Network],
+}
+
+impl<'a> Snapshot<'a> {
+    /// Takes a snapshot of the networks at the current time.
+    ///
+    /// ### Arguments
+    /// * `networks` - The networks to include, in the order they should be serialized.
+    pub fn new(networks: &'a [&'a Network]) -> Self {
+        let timestamp = timestamp_ms();
+
+        let totals = networks.iter().filter_map(|n| n.data.as_ref()).fold(
+            Totals::default(),
+            |mut totals, data| {
+                totals.tps += data.tps;
+                totals.gps += data.gps;
+                totals.dps += data.dps;
+                totals
+            },
+        );
+
+        Self {
+            timestamp,
+            totals: Totals {
+                networks: networks.len(),
+                ..totals
+            },
+            networks,
+        }
+    }
+}
+
+/// Formats the networks in the requested output format.
+///
+/// ### Arguments
+/// * `format` - The output format.
+/// * `networks` - The networks to include, in the order they should be printed.
+/// * `columns` - The columns to include, used by the table and CSV formats.
+/// * `precision` - The number of decimal places of the metrics, used by the table format.
+///   The machine-readable formats always use full precision.
+/// * `units` - How the metrics are written, used by the table format.
+///   The machine-readable formats always write them in full.
+///
+/// ### Returns
+/// The formatted output, terminated by a newline, or an error if serialization fails.
+pub fn render(
+    format: OutputFormat,
+    networks: &[&Network],
+    columns: &[Column],
+    precision: usize,
+    units: Units,
+) -> Result<String, Box<dyn Error>> {
+    match format {
+        OutputFormat::Table => Ok(plain_table(networks, columns, precision, units)),
+        OutputFormat::Json => Ok(serde_json::to_string(&Snapshot::new(networks))? + "\n"),
+        OutputFormat::Csv => {
+            let mut writer = CsvWriter::new(Vec::new(), columns, false);
+            writer.write_header()?;
+            for network in networks {
+                writer.write_network(network)?;
+            }
+            Ok(String::from_utf8(writer.into_inner()?)?)
+        }
+    }
+}
+
+/// The columns of `roller list`, describing the networks by their metadata alone.
+pub const LIST_COLUMNS: [Column; 5] = [
+    Column::Name,
+    Column::Network,
+    Column::Stack,
+    Column::Da,
+    Column::Settlement,
+];
+
+/// Formats the metadata of the networks for `roller list`.
+///
+/// Unlike `render`, the JSON format is a plain array of the networks, without totals,
+/// since the networks have no data yet.
+///
+/// ### Arguments
+/// * `format` - The output format.
+/// * `networks` - The networks to include, in the order they should be printed.
+///
+/// ### Returns
+/// The formatted list, terminated by a newline, or an error if serialization fails.
+pub fn render_list(format: OutputFormat, networks: &[Network]) -> Result<String, Box<dyn Error>> {
+    match format {
+        OutputFormat::Json => Ok(serde_json::to_string(networks)? + "\n"),
+        _ => {
+            let networks: Vec<&Network> = networks.iter().collect();
+            render(format, &networks, &LIST_COLUMNS, 0, Units::Raw)
+        }
+    }
+}
+
+/// Writes networks as CSV records, with headers matching the columns and raw, full-precision values.
+///
+/// Used by `--format csv` and the export of the interactive interface.
+pub struct CsvWriter<W: Write> {
+    writer: csv::Writer<W>,
+    columns: Vec<Column>,
+    timestamp: bool,
+}
+
+impl<W: Write> CsvWriter<W> {
+    /// Creates a new CSV writer.
+    ///
+    /// ### Arguments
+    /// * `writer` - The destination of the records.
+    /// * `columns` - The columns of every record, in order.
+    /// * `timestamp` - Whether to prepend a `timestamp` column with the time each record was written.
+    pub fn new(writer: W, columns: &[Column], timestamp: bool) -> Self {
+        Self {
+            writer: csv::Writer::from_writer(writer),
+            columns: columns.to_vec(),
+            timestamp,
+        }
+    }
+
+    /// Writes the header record.
+    pub fn write_header(&mut self) -> csv::Result<()> {
+        let timestamp = self.timestamp.then_some("timestamp");
+        let headers = self.columns.iter().map(Column::header);
+        self.writer
+            .write_record(timestamp.into_iter().chain(headers))
+    }
+
+    /// Writes the record of a network.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to write.
+    pub fn write_network(&mut self, network: &Network) -> csv::Result<()> {
+        let timestamp = self.timestamp.then(|| timestamp_ms().to_string());
+        let values = self.columns.iter().map(|c| c.raw(network));
+        self.writer
+            .write_record(timestamp.into_iter().chain(values))
+    }
+
+    /// Flushes the buffered records to the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Flushes the buffered records and returns the underlying writer.
+    pub fn into_inner(self) -> Result<W, Box<dyn Error>> {
+        self.writer.into_inner().map_err(|e| e.into_error().into())
+    }
+}
+
+/// Writes the output of `--follow` after every network update.
+///
+/// With `--format json` every update writes the whole snapshot as a single line (NDJSON),
+/// while `--format csv` appends the record of the updated network, prefixed by a timestamp.
+/// Every update is flushed, so consumers of a pipe receive them as they happen.
+pub struct FollowWriter<W: Write> {
+    writer: W,
+    format: OutputFormat,
+    columns: Vec<Column>,
+    precision: usize,
+    units: Units,
+}
+
+impl<W: Write> FollowWriter<W> {
+    /// Creates a new writer, writing the header of the CSV format right away.
+    ///
+    /// ### Arguments
+    /// * `writer` - The destination of the updates, such as stdout.
+    /// * `format` - The output format.
+    /// * `columns` - The columns to include, in order.
+    /// * `precision` - The number of decimal places of the metrics of the plain-text table.
+    /// * `units` - How the metrics of the plain-text table are written.
+    ///
+    /// ### Returns
+    /// The writer, or an error if the header can't be written.
+    pub fn new(
+        mut writer: W,
+        format: OutputFormat,
+        columns: &[Column],
+        precision: usize,
+        units: Units,
+    ) -> Result<Self, Box<dyn Error>> {
+        if format == OutputFormat::Csv {
+            let mut csv = CsvWriter::new(&mut writer, columns, true);
+            csv.write_header()?;
+            csv.flush()?;
+        }
+
+        Ok(Self {
+            writer,
+            format,
+            columns: columns.to_vec(),
+            precision,
+            units,
+        })
+    }
+
+    /// Writes the update of a network.
+    ///
+    /// ### Arguments
+    /// * `networks` - The networks displayed, in order.
+    /// * `updated` - The name of the updated network, appended alone by the CSV format.
+    ///
+    /// ### Returns
+    /// `Ok` once written and flushed, or an error if writing fails.
+    pub fn write(&mut self, networks: &[&Network], updated: &str) -> Result<(), Box<dyn Error>> {
+        match self.format {
+            OutputFormat::Csv => {
+                // Networks excluded by the filters aren't tracked and those past `--top` aren't
+                // displayed, so there's nothing to append.
+                let mut csv = CsvWriter::new(&mut self.writer, &self.columns, true);
+                if let Some(network) = networks.iter().find(|n| n.name == updated) {
+                    csv.write_network(network)?;
+                }
+                csv.flush()?;
+            }
+            _ => {
+                let rendered = render(
+                    self.format,
+                    networks,
+                    &self.columns,
+                    self.precision,
+                    self.units,
+                )?;
+                self.writer.write_all(rendered.as_bytes())?;
+            }
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Formats the networks into a self-contained HTML document holding a single table,
+/// styled like the interface through inline styles, for embedding into web pages.
+///
+/// ### Arguments
+/// * `networks` - The networks to include, in the order they should be rendered.
+/// * `columns` - The columns to include, in the order they should be rendered.
+/// * `precision` - The number of decimal places of the metrics.
+/// * `units` - How the metrics are written, in full or abbreviated.
+///
+/// ### Returns
+/// The HTML document, with a header row followed by one row per network.
+pub fn html_table(
+    networks: &[&Network],
+    columns: &[Column],
+    precision: usize,
+    units: Units,
+) -> String {
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(
+        "<title>roller</title>\n</head>\n<body style=\"margin: 0; background: #0f172a\">\n",
+    );
+    html.push_str(&format!(
+        "<table style=\"{HTML_TABLE_STYLE}\">\n<thead>\n<tr>"
+    ));
+    for column in columns {
+        html.push_str(&format!(
+            "<th style=\"{HTML_HEADER_STYLE}\">{}</th>",
+            escape_html(column.header())
+        ));
+    }
+    html.push_str("</tr>\n</thead>\n<tbody>\n");
+
+    for network in networks {
+        html.push_str("<tr>");
+        for column in columns {
+            // Metrics are right-aligned, so that their decimal points line up.
+            let align = match column.is_metric() {
+                true => "; text-align: right",
+                false => "",
+            };
+            html.push_str(&format!(
+                "<td style=\"{HTML_CELL_STYLE}{align}\">{}</td>",
+                escape_html(&column.value(network, precision, units))
+            ));
+        }
+        html.push_str("</tr>\n");
+    }
+
+    html.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    html
+}
+
+/// Escapes the characters of text with a special meaning in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Formats the networks into a plain-text, aligned table.
+///
+/// The table contains no styling or terminal control sequences, which makes it
+/// suitable for scrollback, pipes, files or cron emails.
+///
+/// ### Arguments
+/// * `networks` - The networks to include, in the order they should be printed.
+/// * `columns` - The columns to include, in the order they should be printed.
+/// * `precision` - The number of decimal places of the metrics.
+/// * `units` - How the metrics are written, in full or abbreviated.
+///
+/// ### Returns
+/// The formatted table, with a header line followed by one line per network.
+pub fn plain_table(
+    networks: &[&Network],
+    columns: &[Column],
+    precision: usize,
+    units: Units,
+) -> String {
+    let rows: Vec<Vec<String>> = networks
+        .iter()
+        .map(|network| {
+            columns
+                .iter()
+                .map(|c| c.value(network, precision, units))
+                .collect()
+        })
+        .collect();
+
+    // The width of each column is the widest of its header and values.
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .fold(column.header().chars().count(), usize::max)
+        })
+        .collect();
+
+    let header = columns.iter().map(|c| c.header().to_string()).collect();
+
+    let mut table = String::new();
+    for row in std::iter::once(header).chain(rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(COLUMN_SEPARATOR);
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+
+    table
+}
@@ -0,0 +1,105 @@
+use crate::{
+    config_file::{self, Section},
+    Network,
+};
+use std::collections::HashMap;
+
+/// The static prices of a network, used to estimate the cost of its gas in USD.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GasPrice {
+    /// The price of a unit of gas, in gwei of the native token.
+    pub gas_price_gwei: f64,
+    /// The price of the native token, in USD.
+    pub token_usd: f64,
+}
+
+impl GasPrice {
+    /// Estimates the cost of the gas used per second.
+    ///
+    /// ### Arguments
+    /// * `gps` - The gas per second, in millions of gas (MGas/s) as reported by the API.
+    ///
+    /// ### Returns
+    /// The cost in USD per second, `gps × gas price × token price`.
+    pub fn usd_per_second(&self, gps: f32) -> f64 {
+        // MGas/s × gwei/gas = 10^6 × 10^-9 native tokens per second
+        gps as f64 * self.gas_price_gwei * self.token_usd / 1_000.0
+    }
+}
+
+/// Maps the networks to the static prices configured in the `[prices]` section of the
+/// `--config` file, since the API doesn't report any.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PriceRegistry {
+    prices: HashMap<String, GasPrice>,
+}
+
+impl Section for PriceRegistry {
+    /// Adds the prices listed in the `[prices]` section of a configuration file.
+    ///
+    /// The section holds one `network = [gas price in gwei, token price in USD]` entry per line,
+    /// e.g. `base = [0.005, 3400]`, where the network is its name as used by the filters.
+    ///
+    /// ### Arguments
+    /// * `contents` - The contents of the configuration file.
+    ///
+    /// ### Returns
+    /// `Ok` if every entry is valid, or an error prefixed by the line number of the first invalid one.
+    fn extend_from_str(&mut self, contents: &str) -> Result<(), String> {
+        for (line_number, line) in config_file::section_lines(contents, "prices")? {
+            let invalid = || {
+                format!("{line_number}: expected `network = [gas price in gwei, token price in USD]`, got `{line}`")
+            };
+            let (name, prices) = line.split_once('=').ok_or_else(invalid)?;
+            let name = config_file::unquote(name).unwrap_or(name.trim());
+            let prices: Vec<f64> = prices
+                .trim()
+                .strip_prefix('[')
+                .and_then(|prices| prices.strip_suffix(']'))
+                .ok_or_else(invalid)?
+                .split(',')
+                .map(|price| price.trim().parse::<f64>())
+                .collect::<Result<_, _>>()
+                .map_err(|_| invalid())?;
+            let [gas_price_gwei, token_usd] = prices[..] else {
+                return Err(invalid());
+            };
+            if !(gas_price_gwei.is_finite() && token_usd.is_finite())
+                || gas_price_gwei < 0.0
+                || token_usd < 0.0
+            {
+                return Err(format!(
+                    "{line_number}: the prices of `{name}` must be positive numbers"
+                ));
+            }
+
+            let price = GasPrice {
+                gas_price_gwei,
+                token_usd,
+            };
+            self.prices.insert(name.to_string(), price);
+        }
+
+        Ok(())
+    }
+}
+
+impl PriceRegistry {
+    /// Returns the prices of a network, if configured.
+    ///
+    /// ### Arguments
+    /// * `name` - The name of the network.
+    pub fn get(&self, name: &str) -> Option<GasPrice> {
+        self.prices.get(name).copied()
+    }
+
+    /// Sets the prices of the networks, see `Network::gas_price`.
+    ///
+    /// ### Arguments
+    /// * `networks` - The networks of the metadata.
+    pub fn apply(&self, networks: &mut [Network]) {
+        for network in networks {
+            network.gas_price = self.get(&network.name);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{config_file, Config};
+use clap::CommandFactory;
+use std::path::Path;
+
+/// An option set by the configuration file, as a `name = value` line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileOption {
+    /// The long name of the option, e.g. `api-endpoint` for `--api-endpoint`.
+    pub name: String,
+    /// The values of the option, several for an array like `["base", "zora"]`.
+    pub values: Vec<String>,
+}
+
+/// An option of the configuration file to apply, resolved by `Profiles::merge`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layered {
+    /// The id of the option, the name of its field in `Config`.
+    pub id: String,
+    /// The arguments setting the option, e.g. `["--top", "5"]`.
+    pub args: Vec<String>,
+    /// The profile setting the option, `None` for the top level of the file.
+    pub profile: Option<String>,
+}
+
+/// The options of the `--config` file: those at its top level, before any section, and those
+/// of its `[profile.NAME]` sections, each overriding the top level when selected by `--profile`.
+///
+/// The options are named like their flags without the leading dashes, e.g. `top = 5`,
+/// `border-style = "double"`, `no-color = true` or `include = ["base", "zora"]`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Profiles {
+    base: Vec<FileOption>,
+    profiles: Vec<(String, Vec<FileOption>)>,
+}
+
+impl Profiles {
+    /// Reads the options of a configuration file.
+    ///
+    /// ### Arguments
+    /// * `path` - The configuration file, see `parse` for the format.
+    ///
+    /// ### Returns
+    /// The options, or an error naming the file and line of the first invalid one.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = config_file::read(path)?;
+        Self::parse(&contents).map_err(|e| format!("{}:{e}", path.display()))
+    }
+
+    /// Parses the options of a configuration file.
+    ///
+    /// ### Arguments
+    /// * `contents` - The contents of the configuration file.
+    ///
+    /// ### Returns
+    /// The options, or an error prefixed by the line number of the first unknown option or
+    /// invalid value.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let base = options(config_file::section_lines(contents, "")?)?;
+        let profiles = config_file::profiles(contents)?
+            .into_iter()
+            .map(|name| {
+                let section = format!("{}{name}", config_file::PROFILE_PREFIX);
+                let options = options(config_file::section_lines(contents, &section)?)?;
+                Ok((name.to_string(), options))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { base, profiles })
+    }
+
+    /// Returns the names of the profiles, in the order of the file.
+    pub fn names(&self) -> Vec<&str> {
+        self.profiles
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Resolves the options of the file to apply, below the command line and the environment.
+    ///
+    /// This is where the layers are merged, from lowest to highest precedence: the defaults,
+    /// the top level of the file, the selected profile, the environment and the command line.
+    /// The last two are applied by clap, so the options they set are left out here.
+    ///
+    /// ### Arguments
+    /// * `profile` - The profile selected by `--profile`, if any.
+    /// * `is_set` - Checks whether an option, by id, is set on the command line or the environment.
+    ///
+    /// ### Returns
+    /// The options to apply, or an error listing the available profiles if `profile` is unknown.
+    pub fn merge(
+        &self,
+        profile: Option<&str>,
+        is_set: impl Fn(&str) -> bool,
+    ) -> Result<Vec<Layered>, String> {
+        let selected = match profile {
+            Some(name) => {
+                let options = self
+                    .profiles
+                    .iter()
+                    .find(|(profile, _)| profile == name)
+                    .map(|(_, options)| options)
+                    .ok_or_else(|| match self.names().as_slice() {
+                        [] => format!("unknown profile `{name}`, the config file has none"),
+                        names => format!(
+                            "unknown profile `{name}`, available profiles: {}",
+                            names.join(", ")
+                        ),
+                    })?;
+                options.iter().map(|o| (o, Some(name))).collect()
+            }
+            None => Vec::new(),
+        };
+
+        let command = Config::command();
+        let mut layered: Vec<Layered> = Vec::new();
+        for (option, profile) in self.base.iter().map(|o| (o, None)).chain(selected) {
+            // Every option has been checked against the flags while parsing
+            let Some(arg) = command
+                .get_arguments()
+                .find(|arg| arg.get_long() == Some(option.name.as_str()))
+            else {
+                continue;
+            };
+            let id = arg.get_id().to_string();
+            if is_set(&id) {
+                continue;
+            }
+
+            let flag = format!("--{}", option.name);
+            let args = match arg.get_action().takes_values() {
+                true => option
+                    .values
+                    .iter()
+                    .flat_map(|value| [flag.clone(), value.clone()])
+                    .collect(),
+                false if option.values == ["true"] => vec![flag],
+                false => Vec::new(),
+            };
+            // The profile replaces the value of the top level, rather than adding to it
+            layered.retain(|l| l.id != id);
+            layered.push(Layered {
+                id,
+                args,
+                profile: profile.map(String::from),
+            });
+        }
+        Ok(layered)
+    }
+}
+
+/// Parses the `name = value` lines of the top level or of a profile.
+fn options(lines: Vec<(usize, &str)>) -> Result<Vec<FileOption>, String> {
+    let command = Config::command();
+    lines
+        .into_iter()
+        .map(|(line_number, line)| {
+            let Some((name, value)) = line.split_once('=') else {
+                return Err(format!(
+                    "{line_number}: expected `option = value`, got `{line}`"
+                ));
+            };
+            let name = name.trim();
+            let arg = command
+                .get_arguments()
+                .find(|arg| arg.get_long() == Some(name))
+                .filter(|_| !matches!(name, "config" | "profile" | "help" | "version"))
+                .ok_or_else(|| format!("{line_number}: unknown option `{name}`"))?;
+            let values = values(value.trim())
+                .ok_or_else(|| format!("{line_number}: invalid value of `{name}`, expected a quoted string, a number, a boolean or an array of them"))?;
+            if !arg.get_action().takes_values() && !matches!(values[..], [ref v] if v == "true" || v == "false") {
+                return Err(format!(
+                    "{line_number}: `{name}` is a switch, expected true or false"
+                ));
+            }
+            Ok(FileOption {
+                name: name.to_string(),
+                values,
+            })
+        })
+        .collect()
+}
+
+/// Parses the value of an option: a quoted string, a bare number or boolean, or an array of them.
+///
+/// ### Returns
+/// The values, or `None` if the value is malformed.
+fn values(value: &str) -> Option<Vec<String>> {
+    match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        Some(items) => {
+            let mut values = Vec::new();
+            let mut rest = items.trim();
+            while !rest.is_empty() {
+                let (item, tail) = match rest.strip_prefix('"') {
+                    Some(quoted) => {
+                        let end = quoted.find('"')?;
+                        (&rest[..end + 2], quoted[end + 1..].trim_start())
+                    }
+                    None => rest.split_at(rest.find(',').unwrap_or(rest.len())),
+                };
+                values.push(scalar(item.trim())?);
+                rest = match tail.trim_start().strip_prefix(',') {
+                    Some(tail) => tail.trim_start(),
+                    None if tail.trim().is_empty() => "",
+                    None => return None,
+                };
+            }
+            Some(values)
+        }
+        None => Some(vec![scalar(value)?]),
+    }
+}
+
+/// Parses a single value, a quoted string or a bare number or boolean.
+fn scalar(value: &str) -> Option<String> {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(string) if !string.contains('"') => Some(string.to_string()),
+        Some(_) => None,
+        None => {
+            let bare = value == "true" || value == "false" || value.parse::<f64>().is_ok();
+            bare.then(|| value.to_string())
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use qrcode::{render::unicode::Dense1x2, EcLevel, QrCode, Version};
+
+/// The QR code version of the detail pane, 25×25 modules, fitting URLs of up to 32 bytes.
+const VERSION: Version = Version::Normal(2);
+
+/// Renders a URL as a QR code of Unicode half-block characters, two modules per character.
+///
+/// URLs too long for a version 2 code are truncated to their bare domain.
+/// The modules are inverted, drawing the light ones, so the code scans on dark terminal backgrounds.
+///
+/// ### Arguments
+/// * `url` - The URL to encode.
+///
+/// ### Returns
+/// The lines of the QR code, or `None` if even the domain of the URL doesn't fit.
+pub fn render(url: &str) -> Option<Vec<String>> {
+    let code = QrCode::with_version(url, VERSION, EcLevel::L)
+        .or_else(|_| QrCode::with_version(domain(url), VERSION, EcLevel::L))
+        .ok()?;
+    let image = code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .build();
+    Some(image.lines().map(String::from).collect())
+}
+
+/// Returns the bare domain of a URL, without its scheme, path or query.
+///
+/// ### Arguments
+/// * `url` - The URL to truncate.
+pub fn domain(url: &str) -> &str {
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    url.split(['/', '?', '#']).next().unwrap_or(url)
+}
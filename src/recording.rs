@@ -0,0 +1,188 @@
+use crate::client::{parse_metadata, validate_metadata, StreamResponse};
+use eventsource_client::{Event, SSE};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The first line of a recording, embedding the network metadata of the session.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Header {
+    /// The `/networkMetadata` document the session was started with.
+    pub metadata: Value,
+}
+
+/// A single SSE event of a recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedEvent {
+    /// The time the event was received, in milliseconds since the start of the recording.
+    pub offset_ms: u64,
+    /// The type of the event, the name of the network it belongs to.
+    pub event: String,
+    /// The raw data of the event.
+    pub data: String,
+}
+
+/// Records the SSE events of a session to a newline-delimited JSON file.
+///
+/// The file starts with a `Header` line, followed by one `RecordedEvent` line per event.
+/// Clones write to the same file, so a reconnected stream keeps appending to the recording.
+#[derive(Clone)]
+pub struct Recorder {
+    writer: Arc<Mutex<BufWriter<File>>>,
+    started: Instant,
+}
+
+impl Recorder {
+    /// Creates the recording file and writes its header.
+    ///
+    /// ### Arguments
+    /// * `path` - The file to record to, truncated if it exists.
+    /// * `metadata` - The network metadata of the session, embedded in the header.
+    pub fn create(path: &Path, metadata: &Value) -> io::Result<Self> {
+        let mut recorder = Self {
+            writer: Arc::new(Mutex::new(BufWriter::new(File::create(path)?))),
+            started: Instant::now(),
+        };
+        let header = Header {
+            metadata: metadata.clone(),
+        };
+        recorder.write_line(&header)?;
+        Ok(recorder)
+    }
+
+    /// Appends an event to the recording.
+    ///
+    /// ### Arguments
+    /// * `event` - The received SSE event.
+    pub fn record(&mut self, event: &Event) -> io::Result<()> {
+        let event = RecordedEvent {
+            offset_ms: self.started.elapsed().as_millis() as u64,
+            event: event.event_type.clone(),
+            data: event.data.clone(),
+        };
+        self.write_line(&event)
+    }
+
+    /// Records every event of the stream as it passes through.
+    ///
+    /// ### Arguments
+    /// * `stream` - The SSE stream to record.
+    ///
+    /// ### Returns
+    /// The same stream, recording its events as they're consumed.
+    pub fn tap(mut self, stream: StreamResponse) -> StreamResponse {
+        Box::pin(stream.inspect(move |message| {
+            if let Ok(SSE::Event(event)) = message {
+                if let Err(e) = self.record(event) {
+                    tracing::warn!(error = %e, "failed to record event");
+                }
+            }
+        }))
+    }
+
+    /// Writes a value as a single line and flushes it, so the recording survives a crash.
+    fn write_line<T: Serialize>(&mut self, value: &T) -> io::Result<()> {
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        serde_json::to_writer(&mut *writer, value)?;
+        writer.write_all(b"\n")?;
+        writer.flush()
+    }
+}
+
+/// A session recorded by the `Recorder`, to be replayed without network access.
+#[derive(Debug)]
+pub struct Recording {
+    /// The network metadata of the header, `None` if the recording has no header.
+    pub metadata: Option<Value>,
+    /// The events of the recording, in order.
+    pub events: Vec<RecordedEvent>,
+}
+
+impl Recording {
+    /// Reads a recording from a file.
+    ///
+    /// ### Arguments
+    /// * `path` - The file to read.
+    ///
+    /// ### Returns
+    /// The recording, or an error pointing to the offending line if the file is malformed.
+    pub fn read(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut recording = Recording {
+            metadata: None,
+            events: Vec::new(),
+        };
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            // Only the first line may be the header
+            if i == 0 {
+                if let Ok(header) = serde_json::from_str::<Header>(&line) {
+                    validate_metadata(&header.metadata)
+                        .map_err(|e| format!("unexpected metadata in {}: {e}", path.display()))?;
+                    recording.metadata = Some(header.metadata);
+                    continue;
+                }
+            }
+            let event = serde_json::from_str(&line)
+                .map_err(|e| format!("{}:{}: invalid event: {e}", path.display(), i + 1))?;
+            recording.events.push(event);
+        }
+
+        Ok(recording)
+    }
+
+    /// Returns the network metadata of the recording, or reads it from `fallback` when
+    /// the recording has no header.
+    ///
+    /// ### Arguments
+    /// * `fallback` - The metadata file passed through `--metadata`, if any.
+    ///
+    /// ### Returns
+    /// The metadata document, or an error if neither source provides it.
+    pub fn metadata(&self, fallback: Option<&Path>) -> Result<Value, Box<dyn Error>> {
+        if let Some(metadata) = &self.metadata {
+            return Ok(metadata.clone());
+        }
+
+        let path =
+            fallback.ok_or("the recording has no metadata header, pass it with --metadata")?;
+        let body = std::fs::read(path)?;
+        Ok(parse_metadata(&body, &path.display().to_string())?)
+    }
+
+    /// Replays the events of the recording as an SSE stream, keeping their original timing.
+    ///
+    /// ### Arguments
+    /// * `speed` - The playback speed, `2.0` replays the events twice as fast.
+    ///
+    /// ### Returns
+    /// A stream of the recorded events, which ends after the last one.
+    pub fn into_stream(self, speed: f64) -> StreamResponse {
+        let started = tokio::time::Instant::now();
+        Box::pin(
+            futures::stream::iter(self.events).then(move |event| async move {
+                let offset = Duration::from_secs_f64(event.offset_ms as f64 / 1000.0 / speed);
+                tokio::time::sleep_until(started + offset).await;
+                Ok(SSE::Event(Event {
+                    event_type: event.event,
+                    data: event.data,
+                    id: None,
+                    retry: None,
+                }))
+            }),
+        )
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{output, Network};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// The prefix of the snapshot file names, used to recognize them when rotating.
+const FILE_PREFIX: &str = "roller-";
+
+/// Periodically persists the networks as JSON files in a directory, building a time-series
+/// dataset from a live session.
+///
+/// Each snapshot uses the schema of `--format json` and is named after its timestamp.
+/// Only the most recent `max_files` snapshots are kept.
+pub struct SnapshotWriter {
+    dir: PathBuf,
+    interval: Duration,
+    max_files: usize,
+    last: Instant,
+}
+
+impl SnapshotWriter {
+    /// Creates a new writer, creating the directory if it doesn't exist.
+    ///
+    /// ### Arguments
+    /// * `dir` - The directory the snapshots are written to.
+    /// * `interval` - The time between snapshots.
+    /// * `max_files` - The maximum number of snapshots kept in the directory.
+    ///
+    /// ### Returns
+    /// The writer, or an error if the directory can't be created.
+    pub fn new(dir: PathBuf, interval: Duration, max_files: usize) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            interval,
+            max_files,
+            last: Instant::now(),
+        })
+    }
+
+    /// Writes a snapshot of the networks if the interval has elapsed since the previous one.
+    ///
+    /// The networks are serialized right away, while writing the file and rotating
+    /// the directory happen on a blocking task, off the main loop.
+    ///
+    /// ### Arguments
+    /// * `networks` - The networks to persist, in their current order.
+    pub fn tick(&mut self, networks: &[&Network]) {
+        if self.last.elapsed() < self.interval {
+            return;
+        }
+        self.last = Instant::now();
+
+        let snapshot = match serde_json::to_vec_pretty(&output::Snapshot::new(networks)) {
+            Ok(snapshot) => snapshot,
+            Err(e) => return tracing::error!(error = %e, "failed to serialize snapshot"),
+        };
+        let path = self
+            .dir
+            .join(format!("{FILE_PREFIX}{}.json", output::timestamp_ms()));
+        let (dir, max_files) = (self.dir.clone(), self.max_files);
+
+        tokio::task::spawn_blocking(move || {
+            match fs::write(&path, snapshot).and_then(|_| rotate(&dir, max_files)) {
+                Ok(_) => tracing::debug!(path = %path.display(), "wrote snapshot"),
+                Err(e) => {
+                    tracing::error!(path = %path.display(), error = %e, "failed to write snapshot")
+                }
+            }
+        });
+    }
+}
+
+/// Removes the oldest snapshots of the directory, keeping at most `max_files`.
+///
+/// The file names embed a millisecond timestamp of fixed length, so sorting them
+/// by name sorts them chronologically.
+fn rotate(dir: &Path, max_files: usize) -> io::Result<()> {
+    let mut snapshots: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(FILE_PREFIX) && name.ends_with(".json"))
+        })
+        .collect();
+    snapshots.sort();
+
+    let excess = snapshots.len().saturating_sub(max_files);
+    for path in &snapshots[..excess] {
+        fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
@@ -1,74 +1,430 @@
+use clap::ValueEnum;
 use crossterm::{
-    event::DisableMouseCapture,
-    execute,
+    cursor::Show,
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    execute, queue,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::backend::CrosstermBackend;
+use ratatui::{backend::CrosstermBackend, layout::Rect, TerminalOptions, Viewport};
+use serde::Serialize;
 use std::{
     error::Error,
-    io::Stdout,
+    fs::{File, OpenOptions},
+    io::{self, IsTerminal, Stderr, Stdout, Write},
     ops::{Deref, DerefMut},
 };
 
+/// The controlling terminal of the process, written to by `UiOutput::Tty`.
+#[cfg(not(windows))]
+const TTY_PATH: &str = "/dev/tty";
+#[cfg(windows)]
+const TTY_PATH: &str = "CONOUT$";
+
+/// The stream the interface is drawn to, see `EnterOptions::output`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum UiOutput {
+    /// The standard output.
+    #[default]
+    Stdout,
+    /// The standard error, leaving stdout to the records of `--follow`.
+    Stderr,
+    /// The controlling terminal, whatever the standard streams are redirected to.
+    Tty,
+}
+
+impl UiOutput {
+    /// Selects stdout if it's a terminal, otherwise stderr if it is, so the interface stays
+    /// visible while stdout is redirected, and stdout when neither is.
+    pub fn detect() -> Self {
+        match (io::stdout().is_terminal(), io::stderr().is_terminal()) {
+            (false, true) => UiOutput::Stderr,
+            _ => UiOutput::Stdout,
+        }
+    }
+
+    /// Checks whether the stream is a terminal the interface can be drawn to.
+    pub fn is_terminal(self) -> bool {
+        match self {
+            UiOutput::Stdout => io::stdout().is_terminal(),
+            UiOutput::Stderr => io::stderr().is_terminal(),
+            UiOutput::Tty => self.open().is_ok(),
+        }
+    }
+
+    /// Opens the stream.
+    ///
+    /// ### Returns
+    /// The writer of the stream, or an error if the controlling terminal can't be opened.
+    pub fn open(self) -> io::Result<UiWriter> {
+        Ok(match self {
+            UiOutput::Stdout => UiWriter::Stdout(io::stdout()),
+            UiOutput::Stderr => UiWriter::Stderr(io::stderr()),
+            UiOutput::Tty => UiWriter::Tty(OpenOptions::new().write(true).open(TTY_PATH)?),
+        })
+    }
+}
+
+/// The writer of the stream selected by a `UiOutput`.
+#[derive(Debug)]
+pub enum UiWriter {
+    /// The standard output.
+    Stdout(Stdout),
+    /// The standard error.
+    Stderr(Stderr),
+    /// The controlling terminal, opened for writing.
+    Tty(File),
+}
+
+impl Write for UiWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            UiWriter::Stdout(stdout) => stdout.write(buf),
+            UiWriter::Stderr(stderr) => stderr.write(buf),
+            UiWriter::Tty(tty) => tty.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            UiWriter::Stdout(stdout) => stdout.flush(),
+            UiWriter::Stderr(stderr) => stderr.flush(),
+            UiWriter::Tty(tty) => tty.flush(),
+        }
+    }
+}
+
 /// The `Terminal` struct represents a terminal instance.
 ///
 /// It wraps the `ratatui::Terminal` with a `CrosstermBackend` and provides
 /// methods for entering and exiting the terminal mode.
-pub struct Terminal {
+///
+/// The backend writes to the stream of `EnterOptions::output`, stdout by default, any other writer can be used through `enter_with`,
+/// or `with_writer` to render to a file, a pipe or a test buffer without the terminal mode.
+/// The interface takes over the alternate screen, or a few lines below the cursor with
+/// `enter_inline`, keeping the scrollback of the shell visible.
+///
+/// The terminal is restored when dropped if `exit` wasn't called, so that returning early with an
+/// error doesn't leave the shell in raw mode on the alternate screen.
+///
+/// While running, the terminal can be handed back to the shell with `suspend` and taken over
+/// again with `resume`, see `suspend_process` for the job control of ctrl+z.
+pub struct Terminal<W: Write = UiWriter> {
     /// The inner `ratatui::Terminal` instance with a `CrosstermBackend`.
     /// It implements the `Deref` and `DerefMut` which allow direct access to it.
-    pub inner: ratatui::Terminal<CrosstermBackend<Stdout>>,
+    pub inner: ratatui::Terminal<CrosstermBackend<W>>,
+    /// The options the terminal mode was entered with, `None` if it wasn't and `exit` has
+    /// nothing to revert.
+    entered: Option<EnterOptions>,
+    /// Whether `exit` was called, so dropping the terminal doesn't restore it again.
+    exited: bool,
+    /// Whether the terminal is restored by `suspend` until `resume` is called.
+    suspended: bool,
+}
+
+/// The options of the terminal mode entered by `Terminal::enter`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EnterOptions {
+    /// Whether to capture the mouse, which disables the native text selection of the terminal.
+    pub mouse: bool,
+    /// The height of the inline viewport drawn below the cursor, in place of the alternate
+    /// screen, `None` to take over the whole screen.
+    pub inline: Option<u16>,
+    /// The stream the interface is drawn to by `Terminal::enter`.
+    pub output: UiOutput,
+    /// Whether the terminal reports the repeat and release of keys, which requires the keyboard
+    /// enhancement of the kitty protocol, see `supports_key_releases`.
+    pub key_releases: bool,
+}
+
+impl EnterOptions {
+    /// Writes the commands entering the alternate screen, unless inline, setting the mouse capture
+    /// and asking for the release of keys if enabled.
+    ///
+    /// ### Arguments
+    /// * `writer` - The writer of the terminal.
+    pub fn write_enter<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        if self.inline.is_none() {
+            queue!(writer, EnterAlternateScreen)?;
+        }
+        match self.mouse {
+            true => queue!(writer, EnableMouseCapture)?,
+            false => queue!(writer, DisableMouseCapture)?,
+        }
+        if self.key_releases {
+            let flags = KeyboardEnhancementFlags::REPORT_EVENT_TYPES;
+            queue!(writer, PushKeyboardEnhancementFlags(flags))?;
+        }
+        writer.flush()
+    }
+
+    /// Writes the commands reverting `write_enter`, releasing the mouse if it was captured.
+    ///
+    /// ### Arguments
+    /// * `writer` - The writer of the terminal.
+    pub fn write_exit<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        if self.key_releases {
+            queue!(writer, PopKeyboardEnhancementFlags)?;
+        }
+        if self.mouse {
+            queue!(writer, DisableMouseCapture)?;
+        }
+        if self.inline.is_none() {
+            queue!(writer, LeaveAlternateScreen)?;
+        }
+        writer.flush()
+    }
+
+    /// Queries whether the controlling terminal can report the release of keys, see `key_releases`.
+    /// Always `false` on Windows, whose console reports them regardless.
+    ///
+    /// ### Returns
+    /// `true` if the terminal supports the keyboard enhancement, `false` if not or if it can't tell.
+    pub fn supports_key_releases() -> bool {
+        crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false)
+    }
+
+    /// Returns the viewport of the terminal mode, the whole screen or the inline lines.
+    pub fn viewport(&self) -> Viewport {
+        match self.inline {
+            Some(height) => Viewport::Inline(height),
+            None => Viewport::Fullscreen,
+        }
+    }
+
+    /// Restores the terminal from the terminal mode without a `Terminal` instance, such as from
+    /// a panic hook: disables the raw mode, reverts `write_enter` and shows the cursor.
+    /// Safe to call several times, or when the terminal mode wasn't entered.
+    ///
+    /// ### Arguments
+    /// * `writer` - The writer of the terminal.
+    pub fn restore<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        disable_raw_mode()?;
+        self.write_exit(writer)?;
+        execute!(writer, Show)
+    }
+}
+
+impl Terminal<UiWriter> {
+    /// Enters the terminal mode on the stream of `EnterOptions::output`, see `enter_with`.
+    ///
+    /// ### Arguments
+    /// * `options` - The options of the terminal mode, such as the mouse capture.
+    ///
+    /// ### Returns
+    /// A new instance of the `Terminal` struct, or an error if any part of the process fails.
+    pub fn enter(options: EnterOptions) -> Result<Self, Box<dyn Error>> {
+        Self::enter_with(options.output.open()?, options)
+    }
+
+    /// Enters the terminal mode on stdout within an inline viewport of a few lines below the
+    /// cursor, rather than the alternate screen, see `EnterOptions::inline`.
+    ///
+    /// ### Arguments
+    /// * `height` - The height of the viewport, in rows.
+    ///
+    /// ### Returns
+    /// A new instance of the `Terminal` struct, or an error if any part of the process fails.
+    pub fn enter_inline(height: u16) -> Result<Self, Box<dyn Error>> {
+        Self::enter(EnterOptions {
+            inline: Some(height),
+            ..Default::default()
+        })
+    }
+
+    /// Installs a panic hook restoring the terminal, see `EnterOptions::restore`, before the
+    /// previous hook prints the panic, so the message and backtrace are legible rather than
+    /// lost on the alternate screen and the shell isn't left in raw mode.
+    ///
+    /// ### Arguments
+    /// * `options` - The options the terminal mode is entered with.
+    pub fn install_panic_hook(options: EnterOptions) {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            // The panic is printed whether the terminal could be restored or not
+            if let Ok(mut writer) = options.output.open() {
+                let _ = options.restore(&mut writer);
+            }
+            hook(info);
+        }));
+    }
 }
 
-impl Terminal {
+impl<W: Write> Terminal<W> {
     /// Enters the terminal mode and returns a new `Terminal` instance.
     ///
     /// By enabling the terminals behavior changes,
     /// you can find out more by following this [link](https://docs.rs/crossterm/latest/crossterm/terminal/index.html#raw-mode)
     ///
-    /// We then instantiate a new `CrosstermBackend` with Stdout as a writer, `EnterAlternateScreen`, clean the screen and then return the Terminal instance.
+    /// We then instantiate a new `CrosstermBackend` with the writer, `EnterAlternateScreen`, set the mouse capture,
+    /// clean the screen and then return the Terminal instance.
+    ///
+    /// ### Arguments
+    /// * `writer` - The writer of the terminal, whose size is queried from the controlling terminal.
+    /// * `options` - The options of the terminal mode, see `EnterOptions::write_enter`.
     ///
     /// ### Returns
     /// A new instance of the `Terminal` struct, or an error if any part of the process fails.
-    pub fn enter() -> Result<Self, Box<dyn Error>> {
+    pub fn enter_with(writer: W, options: EnterOptions) -> Result<Self, Box<dyn Error>> {
         // [link](https://docs.rs/crossterm/latest/crossterm/terminal/index.html#raw-mode)
         enable_raw_mode()?;
-        let backend = CrosstermBackend::new(std::io::stdout());
-        let mut terminal = ratatui::Terminal::new(backend)?;
-        execute!(
-            terminal.backend_mut(),
-            EnterAlternateScreen,
-            DisableMouseCapture
-        )?;
+        let backend = CrosstermBackend::new(writer);
+        let viewport = options.viewport();
+        let mut terminal = ratatui::Terminal::with_options(backend, TerminalOptions { viewport })?;
+        options.write_enter(terminal.backend_mut())?;
 
         terminal.clear()?;
 
-        Ok(Self { inner: terminal })
+        Ok(Self {
+            inner: terminal,
+            entered: Some(options),
+            exited: false,
+            suspended: false,
+        })
+    }
+
+    /// Creates a terminal of a fixed size drawing to any writer, such as a file, a pipe or a test
+    /// buffer, without entering the terminal mode or querying the size of the controlling terminal.
+    ///
+    /// ### Arguments
+    /// * `writer` - The writer receiving the escape sequences of the frames.
+    /// * `width` - The width of the frames, in columns.
+    /// * `height` - The height of the frames, in rows.
+    ///
+    /// ### Returns
+    /// A new instance of the `Terminal` struct, or an error if the backend fails.
+    pub fn with_writer(writer: W, width: u16, height: u16) -> Result<Self, Box<dyn Error>> {
+        let options = TerminalOptions {
+            viewport: Viewport::Fixed(Rect::new(0, 0, width, height)),
+        };
+        let inner = ratatui::Terminal::with_options(CrosstermBackend::new(writer), options)?;
+
+        Ok(Self {
+            inner,
+            entered: None,
+            exited: false,
+            suspended: false,
+        })
     }
 
     /// Exits the terminal mode and restores the previous terminal state.
     ///
     /// Reverts the changes made by `Self::enter`, clears the terminal and shows/resets the cursor to the top left corner.
+    /// An inline viewport is cleared instead, leaving the cursor where it started, so the prompt
+    /// of the shell follows the scrollback.
+    /// Terminals created by `with_writer` are only cleared. Only the first call has an effect,
+    /// the terminal being marked as exited even if restoring it fails.
     ///
     /// ### Returns
     /// An `Ok` result if the terminal was successfully exited or an error if any part of the process fails.
     pub fn exit(&mut self) -> Result<(), Box<dyn Error>> {
-        disable_raw_mode()?;
-        execute!(self.backend_mut(), LeaveAlternateScreen,)?;
+        if std::mem::replace(&mut self.exited, true) {
+            return Ok(());
+        }
+
+        // A suspended terminal is restored already
+        let entered = self.entered.take();
+        if let Some(options) = entered.filter(|_| !self.suspended) {
+            disable_raw_mode()?;
+            options.write_exit(self.backend_mut())?;
+        }
         self.clear()?;
-        self.set_cursor(0, 0)?;
+        let cursor = match entered.and_then(|options| options.inline) {
+            Some(_) => self.get_frame().size(),
+            None => Rect::default(),
+        };
+        self.set_cursor(cursor.x, cursor.y)?;
         self.show_cursor()?;
 
         Ok(())
     }
+
+    /// Hands the terminal back to the shell while the process is stopped: disables the raw mode,
+    /// reverts `EnterOptions::write_enter` and shows the cursor, keeping the terminal to `resume`.
+    /// Does nothing if the terminal is suspended already or has exited.
+    ///
+    /// ### Returns
+    /// An `Ok` result if the terminal was restored or an error if any part of the process fails.
+    pub fn suspend(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.exited || self.suspended {
+            return Ok(());
+        }
+
+        if let Some(options) = self.entered {
+            disable_raw_mode()?;
+            options.write_exit(self.backend_mut())?;
+        }
+        self.show_cursor()?;
+        self.suspended = true;
+
+        Ok(())
+    }
+
+    /// Takes the terminal over again once the process continues, entering the terminal mode with
+    /// the options of `enter` and clearing it, so that the next draw repaints the whole frame.
+    ///
+    /// Since a process can be stopped without a chance to `suspend`, the terminal mode is entered
+    /// again even if it wasn't suspended, the shell possibly having reset it. Does nothing once
+    /// the terminal has exited.
+    ///
+    /// ### Returns
+    /// An `Ok` result if the terminal was taken over or an error if any part of the process fails.
+    pub fn resume(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.exited {
+            return Ok(());
+        }
+
+        if let Some(options) = self.entered {
+            enable_raw_mode()?;
+            options.write_enter(self.backend_mut())?;
+        }
+        self.clear()?;
+        self.suspended = false;
+
+        Ok(())
+    }
+
+    /// Checks whether the terminal is suspended, see `suspend`.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// Suspends the process like a shell does on ctrl+z, which the raw mode turns into a key:
+    /// `suspend`s the terminal, stops the process until it's continued, such as with `fg`,
+    /// then `resume`s the terminal. Only supported on Unix, a no-op elsewhere.
+    ///
+    /// ### Returns
+    /// An `Ok` result once resumed, or an error if any part of the process fails.
+    pub fn suspend_process(&mut self) -> Result<(), Box<dyn Error>> {
+        #[cfg(unix)]
+        {
+            self.suspend()?;
+            signal_hook::low_level::raise(signal_hook::consts::SIGSTOP)?;
+            self.resume()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Restores the terminal if it's dropped without calling `exit`, such as on an early return.
+/// Errors are logged rather than raised, since they can't be reported from there.
+impl<W: Write> Drop for Terminal<W> {
+    fn drop(&mut self) {
+        if let Err(e) = self.exit() {
+            tracing::warn!(error = %e, "failed to restore the terminal");
+        }
+    }
 }
 
 /// Implements the `Deref` trait for `Terminal`.
 ///
 /// This allows dereferencing a `Terminal` instance to access the underlying
-/// `ratatui::Terminal<CrosstermBackend<Stdout>>` methods and properties directly.
-impl Deref for Terminal {
-    type Target = ratatui::Terminal<CrosstermBackend<Stdout>>;
+/// `ratatui::Terminal<CrosstermBackend<W>>` methods and properties directly.
+impl<W: Write> Deref for Terminal<W> {
+    type Target = ratatui::Terminal<CrosstermBackend<W>>;
 
     fn deref(&self) -> &Self::Target {
         &self.inner
@@ -78,8 +434,8 @@ impl Deref for Terminal {
 /// Implements the `DerefMut` trait for `Terminal`.
 ///
 /// Mutably dereferencing a `Terminal` instance to access and modify the
-/// underlying `ratatui::Terminal<CrosstermBackend<Stdout>>` methods and properties directly.
-impl DerefMut for Terminal {
+/// underlying `ratatui::Terminal<CrosstermBackend<W>>` methods and properties directly.
+impl<W: Write> DerefMut for Terminal<W> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.inner
     }
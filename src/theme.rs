@@ -0,0 +1,236 @@
+use clap::ValueEnum;
+#[cfg(not(feature = "no-color"))]
+use ratatui::style::{palette::tailwind::SLATE, Color};
+use ratatui::{
+    style::{Modifier, Style},
+    widgets::BorderType,
+};
+use serde::Serialize;
+
+/// The border drawn around the blocks of the interface.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum BorderStyle {
+    /// Rounded corners.
+    #[default]
+    Rounded,
+    /// Straight lines.
+    Plain,
+    /// Double lines.
+    Double,
+    /// Thick lines.
+    Thick,
+    /// Half-block characters drawn inside the block, for a more compact look.
+    /// Requires ratatui 0.24 or newer.
+    Quad,
+}
+
+impl From<BorderStyle> for BorderType {
+    fn from(style: BorderStyle) -> Self {
+        match style {
+            BorderStyle::Rounded => BorderType::Rounded,
+            BorderStyle::Plain => BorderType::Plain,
+            BorderStyle::Double => BorderType::Double,
+            BorderStyle::Thick => BorderType::Thick,
+            BorderStyle::Quad => BorderType::QuadrantInside,
+        }
+    }
+}
+
+/// The text style of the header row of the network table.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub enum HeaderStyle {
+    /// Bold text.
+    Bold,
+    /// Underlined text, distinct in most color schemes.
+    #[default]
+    Underline,
+    /// Plain text, like the rows.
+    None,
+}
+
+impl From<HeaderStyle> for Modifier {
+    fn from(style: HeaderStyle) -> Self {
+        match style {
+            HeaderStyle::Bold => Modifier::BOLD,
+            HeaderStyle::Underline => Modifier::UNDERLINED,
+            HeaderStyle::None => Modifier::empty(),
+        }
+    }
+}
+
+/// The data availability layers colored in the "DA" column, by the start of their display name,
+/// in the order of the styles of `Theme::da_layers`.
+const DA_LAYERS: [&str; 4] = ["Ethereum", "Celestia", "EigenDA", "Avail"];
+
+/// The styles used by every widget of the interface.
+///
+/// The theme is chosen once at startup, widgets should never build their own colored styles
+/// so that the monochrome variant is respected everywhere.
+///
+/// Builds with the `no-color` feature compile out the colored theme, always using the monochrome one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Whether the theme uses colors, `false` for the monochrome variant.
+    pub color: bool,
+    /// The base style of the blocks and their text.
+    pub base: Style,
+    /// The style of the selected row of the table.
+    pub highlight: Style,
+    /// The style of values above their warning threshold.
+    pub warning: Style,
+    /// The style of values approaching their warning threshold.
+    pub caution: Style,
+    /// The style of the rows of networks without a recent update, see `--stale-threshold`.
+    pub stale: Style,
+    /// The style of the row of the network that just updated.
+    pub flash: Style,
+    /// The style the flash of an updated row fades to, before returning to `base`.
+    pub flash_fading: Style,
+    /// The style of the query of the `/` search while it's being typed.
+    pub filter: Style,
+    /// The style of the query of the `/` search once it's locked.
+    pub filter_locked: Style,
+    /// The styles of the known data availability layers, see `da`.
+    pub da_layers: [Style; DA_LAYERS.len()],
+    /// The style of the bar of the "Age" column right after an update, see `age`.
+    pub age_fresh: Style,
+    /// The style of the bar of the "Age" column once it's full.
+    pub age_stale: Style,
+    /// The style of the label of a network with an anomalous metric, see `--anomaly-z`.
+    pub anomaly: Style,
+    /// The style of the badge of a network discovered while running, see `Network::is_new`.
+    pub new_badge: Style,
+}
+
+#[cfg(not(feature = "no-color"))]
+impl Default for Theme {
+    fn default() -> Self {
+        Self::colored()
+    }
+}
+
+#[cfg(feature = "no-color")]
+impl Default for Theme {
+    fn default() -> Self {
+        Self::monochrome()
+    }
+}
+
+impl Theme {
+    /// The default, colored theme.
+    #[cfg(not(feature = "no-color"))]
+    pub const fn colored() -> Self {
+        Self {
+            color: true,
+            base: Style::new().fg(SLATE.c400),
+            highlight: Style::new().bg(SLATE.c500).fg(SLATE.c900),
+            warning: Style::new().fg(Color::Red),
+            caution: Style::new().fg(Color::Yellow),
+            stale: Style::new().fg(SLATE.c600).add_modifier(Modifier::DIM),
+            flash: Style::new().fg(SLATE.c50).add_modifier(Modifier::BOLD),
+            flash_fading: Style::new().fg(SLATE.c200),
+            filter: Style::new().fg(Color::Yellow),
+            filter_locked: Style::new().fg(Color::Green),
+            da_layers: [
+                Style::new().fg(Color::LightBlue),
+                Style::new().fg(Color::LightMagenta),
+                Style::new().fg(Color::LightCyan),
+                Style::new().fg(Color::LightGreen),
+            ],
+            age_fresh: Style::new().fg(Color::Rgb(0, 230, 64)),
+            age_stale: Style::new().fg(Color::Rgb(139, 0, 0)),
+            anomaly: Style::new()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD),
+            new_badge: Style::new().fg(Color::LightCyan),
+        }
+    }
+
+    /// The monochrome theme, using only the bold, dim and reversed modifiers.
+    pub const fn monochrome() -> Self {
+        Self {
+            color: false,
+            base: Style::new(),
+            highlight: Style::new().add_modifier(Modifier::REVERSED),
+            warning: Style::new().add_modifier(Modifier::BOLD),
+            caution: Style::new().add_modifier(Modifier::UNDERLINED),
+            stale: Style::new().add_modifier(Modifier::DIM),
+            flash: Style::new().add_modifier(Modifier::BOLD),
+            flash_fading: Style::new(),
+            filter: Style::new().add_modifier(Modifier::UNDERLINED),
+            filter_locked: Style::new().add_modifier(Modifier::BOLD),
+            da_layers: [Style::new(); DA_LAYERS.len()],
+            age_fresh: Style::new(),
+            age_stale: Style::new().add_modifier(Modifier::BOLD),
+            anomaly: Style::new().add_modifier(Modifier::BOLD),
+            new_badge: Style::new().add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// Returns the style of a data availability layer, the base style for unknown layers.
+    ///
+    /// ### Arguments
+    /// * `name` - The display name of the layer, e.g. "Ethereum (blob)".
+    pub fn da(&self, name: &str) -> Style {
+        DA_LAYERS
+            .iter()
+            .position(|layer| name.starts_with(layer))
+            .map_or(Style::new(), |i| self.da_layers[i])
+    }
+
+    /// Returns the style of the bar of the "Age" column, a linear gradient from `age_fresh`
+    /// to `age_stale` when both are RGB colors, otherwise `age_stale` once the bar is full.
+    ///
+    /// ### Arguments
+    /// * `ratio` - How full the bar is, from `0.0` to `1.0`.
+    pub fn age(&self, ratio: f32) -> Style {
+        let ratio = ratio.clamp(0.0, 1.0);
+        match (self.age_fresh.fg, self.age_stale.fg) {
+            #[cfg(not(feature = "no-color"))]
+            (Some(Color::Rgb(r1, g1, b1)), Some(Color::Rgb(r2, g2, b2))) => {
+                let mix =
+                    |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * ratio) as u8;
+                self.age_fresh
+                    .fg(Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2)))
+            }
+            _ if ratio >= 1.0 => self.age_stale,
+            _ => self.age_fresh,
+        }
+    }
+
+    /// Chooses the theme based on the `--no-color` flag and the environment.
+    ///
+    /// The monochrome theme is used when the flag is set, the `NO_COLOR` environment variable
+    /// is set to a non-empty value (see [no-color.org](https://no-color.org)),
+    /// or the terminal doesn't advertise support for RGB colors.
+    ///
+    /// ### Arguments
+    /// * `no_color` - Whether `--no-color` was passed.
+    #[cfg(not(feature = "no-color"))]
+    pub fn detect(no_color: bool) -> Self {
+        let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        match no_color || no_color_env || !supports_rgb() {
+            true => Self::monochrome(),
+            false => Self::colored(),
+        }
+    }
+
+    /// Chooses the monochrome theme, the only one of builds with the `no-color` feature.
+    #[cfg(feature = "no-color")]
+    pub fn detect(_no_color: bool) -> Self {
+        Self::monochrome()
+    }
+}
+
+/// Checks whether the terminal advertises support for RGB colors through `COLORTERM` or `TERM`.
+///
+/// Terminals with a 256-color palette are accepted as well, since they approximate RGB colors closely.
+#[cfg(not(feature = "no-color"))]
+fn supports_rgb() -> bool {
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    let term = std::env::var("TERM").unwrap_or_default();
+    matches!(colorterm.as_str(), "truecolor" | "24bit")
+        || ["256color", "truecolor", "direct"]
+            .iter()
+            .any(|capability| term.contains(capability))
+}
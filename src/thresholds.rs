@@ -0,0 +1,222 @@
+use crate::{
+    config_file::{self, Section},
+    Column, Network,
+};
+use std::{collections::HashMap, fmt};
+
+/// The metrics that can be given thresholds, along with their names in the `[thresholds]` section.
+pub const THRESHOLD_METRICS: [(&str, Column); 4] = [
+    ("tps", Column::Tps),
+    ("gps", Column::Gps),
+    ("dps", Column::Dps),
+    ("gas-per-tx", Column::GasPerTx),
+];
+
+/// How far a metric is past its thresholds, in increasing order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Below the warn threshold, or without thresholds.
+    Normal,
+    /// At or above the warn threshold, styled as a caution.
+    Warn,
+    /// At or above the high threshold, styled as a warning.
+    High,
+}
+
+/// The boundaries of the levels of a metric.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Threshold {
+    /// The value from which the metric is at the `Warn` level.
+    pub warn: f64,
+    /// The value from which the metric is at the `High` level, above `warn`.
+    pub high: f64,
+}
+
+impl Threshold {
+    /// Returns the level of a value, the boundaries belonging to the upper level.
+    ///
+    /// ### Arguments
+    /// * `value` - The value of the metric.
+    pub fn level(&self, value: f64) -> Level {
+        if value >= self.high {
+            Level::High
+        } else if value >= self.warn {
+            Level::Warn
+        } else {
+            Level::Normal
+        }
+    }
+}
+
+/// A metric of a network past its warn threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Alert {
+    /// The column of the metric.
+    pub column: Column,
+    /// The level reached by the metric, never `Normal`.
+    pub level: Level,
+    /// The value of the metric.
+    pub value: f64,
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.level {
+            Level::High => "high",
+            _ => "warn",
+        };
+        write!(f, "{} {} at {}", self.column.header(), level, self.value)
+    }
+}
+
+/// The thresholds color-coding the metrics of the table and raising alerts, configured by the
+/// `[thresholds]` section of the `--config` file with optional `[thresholds.networks.NAME]`
+/// overrides. Metrics without thresholds are never colored.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Thresholds {
+    metrics: HashMap<&'static str, Threshold>,
+    networks: HashMap<String, HashMap<&'static str, Threshold>>,
+}
+
+impl Section for Thresholds {
+    /// Adds the thresholds listed in the `[thresholds]` section of a configuration file, and the
+    /// overrides of the `[thresholds.networks.NAME]` sections.
+    ///
+    /// The sections hold one `metric = { warn = number, high = number }` entry per line,
+    /// e.g. `tps = { warn = 1.0, high = 20.0 }`, where the metric is one of `THRESHOLD_METRICS`.
+    /// The metrics missing from the section of a network keep the thresholds of `[thresholds]`.
+    ///
+    /// ### Arguments
+    /// * `contents` - The contents of the configuration file.
+    ///
+    /// ### Returns
+    /// `Ok` if every entry is valid, or an error prefixed by the line number of the first invalid one.
+    fn extend_from_str(&mut self, contents: &str) -> Result<(), String> {
+        let lines = config_file::section_lines(contents, "thresholds")?;
+        self.metrics.extend(parse_section(lines)?);
+        for network in config_file::subsections(contents, config_file::THRESHOLDS_PREFIX)? {
+            let section = format!("{}{network}", config_file::THRESHOLDS_PREFIX);
+            let lines = config_file::section_lines(contents, &section)?;
+            self.networks
+                .entry(network.to_string())
+                .or_default()
+                .extend(parse_section(lines)?);
+        }
+
+        Ok(())
+    }
+}
+
+impl Thresholds {
+    /// Returns the thresholds of a metric of a network, its override if any.
+    ///
+    /// ### Arguments
+    /// * `network` - The name of the network.
+    /// * `column` - The column of the metric.
+    pub fn get(&self, network: &str, column: Column) -> Option<Threshold> {
+        let (name, _) = THRESHOLD_METRICS.iter().find(|(_, c)| *c == column)?;
+        self.networks
+            .get(network)
+            .and_then(|overrides| overrides.get(name))
+            .or_else(|| self.metrics.get(name))
+            .copied()
+    }
+
+    /// Returns the level of a metric of a network, `Normal` before its first update.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to check.
+    /// * `column` - The column of the metric.
+    pub fn level(&self, network: &Network, column: Column) -> Level {
+        match (self.get(&network.name, column), metric(network, column)) {
+            (Some(threshold), Some(value)) => threshold.level(value),
+            _ => Level::Normal,
+        }
+    }
+
+    /// Returns the metrics of a network past their warn threshold.
+    ///
+    /// ### Arguments
+    /// * `network` - The network to check.
+    ///
+    /// ### Returns
+    /// The alerts, in the order of `THRESHOLD_METRICS`.
+    pub fn alerts(&self, network: &Network) -> Vec<Alert> {
+        THRESHOLD_METRICS
+            .iter()
+            .filter_map(|&(_, column)| {
+                let level = self.level(network, column);
+                let value = metric(network, column)?;
+                (level > Level::Normal).then_some(Alert {
+                    column,
+                    level,
+                    value,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the value of a metric of a network, if it was updated.
+fn metric(network: &Network, column: Column) -> Option<f64> {
+    let data = network.data.as_ref()?;
+    match column {
+        Column::Tps => Some(data.tps as f64),
+        Column::Gps => Some(data.gps as f64),
+        Column::Dps => Some(data.dps as f64),
+        Column::GasPerTx => Some(data.gas_per_tx() as f64),
+        _ => None,
+    }
+}
+
+/// Parses the `metric = { warn = number, high = number }` entries of a section.
+fn parse_section(lines: Vec<(usize, &str)>) -> Result<HashMap<&'static str, Threshold>, String> {
+    let mut thresholds = HashMap::new();
+    for (line_number, line) in lines {
+        let invalid = || {
+            format!("{line_number}: expected `metric = {{ warn = number, high = number }}`, got `{line}`")
+        };
+        let (name, bounds) = line.split_once('=').ok_or_else(invalid)?;
+        let name = config_file::unquote(name).unwrap_or(name.trim());
+        let Some(&(name, _)) = THRESHOLD_METRICS.iter().find(|(n, _)| *n == name) else {
+            let names: Vec<&str> = THRESHOLD_METRICS.iter().map(|(n, _)| *n).collect();
+            return Err(format!(
+                "{line_number}: unknown metric `{name}`, expected one of {}",
+                names.join(", ")
+            ));
+        };
+
+        let (mut warn, mut high) = (None, None);
+        let bounds = bounds
+            .trim()
+            .strip_prefix('{')
+            .and_then(|bounds| bounds.strip_suffix('}'))
+            .ok_or_else(invalid)?;
+        for bound in bounds.split(',').filter(|b| !b.trim().is_empty()) {
+            let (key, value) = bound.split_once('=').ok_or_else(invalid)?;
+            let value = value.trim().parse::<f64>().map_err(|_| invalid())?;
+            match key.trim() {
+                "warn" => warn = Some(value),
+                "high" => high = Some(value),
+                _ => return Err(invalid()),
+            }
+        }
+        let (Some(warn), Some(high)) = (warn, high) else {
+            return Err(invalid());
+        };
+        if !(warn.is_finite() && high.is_finite()) || warn < 0.0 || high < 0.0 {
+            return Err(format!(
+                "{line_number}: the thresholds of `{name}` must be non-negative numbers"
+            ));
+        }
+        if warn >= high {
+            return Err(format!(
+                "{line_number}: the warn threshold of `{name}` must be below its high threshold"
+            ));
+        }
+
+        thresholds.insert(name, Threshold { warn, high });
+    }
+
+    Ok(thresholds)
+}
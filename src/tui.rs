@@ -1,11 +1,78 @@
-use crate::{network::Data, Network};
-use crossterm::event::KeyCode;
+use crate::{
+    anomaly::HISTORY_LEN,
+    clipboard::{self, Copied, CopyField},
+    format::{self, Units},
+    latency::{self, LatencyMonitor, Ping},
+    network::Data,
+    output::CsvWriter,
+    Action, Column, Config, DisplayOptions, KeyMap, Network, Theme, Thresholds,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind};
 use eventsource_client::Event;
-use ratatui::style::palette::tailwind::SLATE;
 use ratatui::{prelude::*, widgets::*};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, VecDeque},
+    fmt,
+    ops::Range,
+    time::{Duration, Instant},
+};
 
-/// Represents the sorting strategies for the network table, currently only `DESC`.
-#[derive(Debug, Default)]
+/// The maximum time between two presses of the same key for the second one to be a key repeat,
+/// meaning the key is being held down.
+const KEY_REPEAT_WINDOW: Duration = Duration::from_millis(50);
+
+/// The longest time a held key usually waits before it starts repeating, 250 to 660 ms on most
+/// systems. A first press is only considered released once it has elapsed without a repeat.
+const KEY_REPEAT_DELAY: Duration = Duration::from_millis(700);
+
+/// How long the row of a network flashes after an update, then fades for as long again.
+const FLASH_DURATION: Duration = Duration::from_millis(250);
+
+/// The number of sorting keys, last among the key hints of the info bar.
+const SORT_HINTS: usize = 5;
+
+/// The symbol drawn before the selected row of the table.
+const HIGHLIGHT_SYMBOL: &str = ">> ";
+
+/// The frames of the heartbeat of the summary line, advanced by every received event.
+const HEARTBEAT: [char; 4] = ['·', '•', '●', '•'];
+
+/// The time without events after which a connection is considered stale, in milliseconds.
+const CONNECTION_STALE_AFTER_MS: u64 = 5_000;
+
+/// The status shown from a press of the reconnect key until the new stream delivers a message.
+const RECONNECTING_STATUS: &str = "Reconnecting…";
+
+/// The smallest terminal size the interface is laid out in, as `(width, height)`: the summary,
+/// the info bar and the table with its borders, header and a single row.
+/// Smaller terminals display a message asking to enlarge them instead.
+pub const MIN_TERMINAL_SIZE: (u16, u16) = (20, 8);
+
+/// The height the detail pane grows to on short terminals, up to the height of the table: its
+/// borders, the stats and the section lines, and the history table with a few updates.
+const DETAIL_PANE_MIN_HEIGHT: u16 = 21;
+
+/// The number of past updates listed per page by the history table of the detail pane, fewer
+/// if they don't fit in the pane.
+pub const HISTORY_PAGE_SIZE: usize = 20;
+
+/// Represents the sorting strategies for the network table.
+///
+/// The enum is deliberately exhaustive, every `match` on it lists each variant so that adding
+/// one fails to compile until its metric, column and key are defined.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    clap::ValueEnum,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 pub enum SortingStrategy {
     /// Sort by Gas Per Second (GPS).
     #[default]
@@ -14,6 +81,239 @@ pub enum SortingStrategy {
     Tps,
     /// Sort by Data Per Second (DPS).
     Dps,
+    /// Sort by the number of pending transactions in the mempool.
+    PendingTxCount,
+    /// Sort by the average gas used by a transaction, networks without transactions last.
+    GasPerTx,
+}
+
+impl SortingStrategy {
+    /// Every strategy, in the order of the key hints.
+    pub const ALL: [SortingStrategy; 5] = [
+        SortingStrategy::Gps,
+        SortingStrategy::Tps,
+        SortingStrategy::Dps,
+        SortingStrategy::PendingTxCount,
+        SortingStrategy::GasPerTx,
+    ];
+
+    /// Returns the value of the metric the strategy sorts by.
+    ///
+    /// ### Arguments
+    /// * `data` - The data of a network.
+    pub fn metric(&self, data: &Data) -> f32 {
+        match self {
+            SortingStrategy::Gps => data.gps,
+            SortingStrategy::Tps => data.tps,
+            SortingStrategy::Dps => data.dps,
+            SortingStrategy::PendingTxCount => data.pending_tx_count.unwrap_or_default() as f32,
+            SortingStrategy::GasPerTx => data.gas_per_tx(),
+        }
+    }
+
+    /// Returns the column displaying the metric the strategy sorts by.
+    pub fn column(&self) -> Column {
+        match self {
+            SortingStrategy::Gps => Column::Gps,
+            SortingStrategy::Tps => Column::Tps,
+            SortingStrategy::Dps => Column::Dps,
+            SortingStrategy::PendingTxCount => Column::Pending,
+            SortingStrategy::GasPerTx => Column::GasPerTx,
+        }
+    }
+
+    /// Returns the unit of the metric, matching the headers of the table.
+    pub fn unit(&self) -> &'static str {
+        match self {
+            SortingStrategy::Gps => "MGas/s",
+            SortingStrategy::Tps => "TPS",
+            SortingStrategy::Dps => "KB/s",
+            SortingStrategy::PendingTxCount => "pending",
+            SortingStrategy::GasPerTx => "KGas/tx",
+        }
+    }
+
+    /// Returns the action selecting the strategy, see `Action::sorting_strategy`.
+    pub fn action(&self) -> Action {
+        match self {
+            SortingStrategy::Gps => Action::SortGps,
+            SortingStrategy::Tps => Action::SortTps,
+            SortingStrategy::Dps => Action::SortDps,
+            SortingStrategy::PendingTxCount => Action::SortPending,
+            SortingStrategy::GasPerTx => Action::SortGasPerTx,
+        }
+    }
+}
+
+/// The direction the network table is sorted in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    /// Lowest values first.
+    Ascending,
+    /// Highest values first.
+    #[default]
+    Descending,
+}
+
+impl SortDirection {
+    /// Returns the opposite direction.
+    pub fn reversed(self) -> Self {
+        match self {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        }
+    }
+}
+
+/// Running totals of the gas and data processed by all networks since the application started.
+///
+/// These are accumulated by roller itself from the per-second rates of each update,
+/// and always start from zero on launch.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SessionTotals {
+    /// The total gas processed, in Gas.
+    pub gas: f64,
+    /// The total data processed, in bytes.
+    pub bytes: f64,
+}
+
+impl SessionTotals {
+    /// Adds the contribution of a network, holding the given rates for `secs` seconds.
+    ///
+    /// ### Arguments
+    /// * `data` - The rates of the network during the interval.
+    /// * `secs` - The length of the interval in seconds.
+    pub fn accumulate(&mut self, data: &Data, secs: f64) {
+        // `gps` is displayed as MGas/s and `dps` as KB/s.
+        self.gas += data.gps as f64 * 1_000_000.0 * secs;
+        self.bytes += data.dps as f64 * 1_000.0 * secs;
+    }
+}
+
+/// A key hint of the info bar, described by a tooltip while the mouse hovers over it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfoBarSegment {
+    /// The key bound to the action, e.g. "g".
+    pub key: String,
+    /// The short name of the action in the hints, e.g. "gas per second".
+    pub short: &'static str,
+    /// The description of the action shown in the tooltip.
+    pub tooltip: String,
+}
+
+impl InfoBarSegment {
+    /// Returns the hint as displayed in the info bar, e.g. "[g] gas per second".
+    pub fn label(&self) -> String {
+        format!("[{}] {}", self.key, self.short)
+    }
+}
+
+/// Counts of the events received since the application started, by what became of them.
+///
+/// Surfaced by the event counts of the summary line, so events lost along the way show up
+/// as a number rather than silently missing updates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EventCounts {
+    /// The events parsed and applied to their network.
+    pub parsed: u64,
+    /// The events whose data failed to parse, leaving their network without data.
+    pub failed: u64,
+    /// The events of networks that aren't tracked, hidden by the filters or missing from the metadata.
+    pub untracked: u64,
+}
+
+impl fmt::Display for EventCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "events: {} parsed, {} failed to parse, {} untracked",
+            self.parsed, self.failed, self.untracked
+        )
+    }
+}
+
+/// The state of the connection to the SSE stream, displayed by the connection indicator.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionState {
+    /// Whether the SSE stream is currently connected.
+    pub connected: bool,
+    /// The time elapsed since the last event was received, in milliseconds.
+    pub last_event_ms_ago: u64,
+}
+
+/// Computes the time left until the next metadata refresh, see `--refresh-metadata`.
+///
+/// ### Arguments
+/// * `last_refresh` - The time of the last attempt to refresh, or of the start.
+/// * `interval_secs` - The interval between refreshes, in seconds.
+///
+/// ### Returns
+/// The number of seconds left, `0` once the refresh is due.
+pub fn next_refresh_countdown(last_refresh: Instant, interval_secs: u64) -> u64 {
+    interval_secs.saturating_sub(last_refresh.elapsed().as_secs())
+}
+
+/// Creates the "LED" of the connection indicator.
+///
+/// The LED is green when connected and events are arriving, yellow when connected but no events
+/// have been received in the last 5 seconds and red when disconnected. Each state also uses a
+/// different symbol, so it can be told apart without colors.
+///
+/// ### Arguments
+/// * `state` - The state of the connection.
+///
+/// ### Returns
+/// The styled `Span` of the LED.
+pub fn connection_led(state: ConnectionState) -> Span<'static> {
+    match state {
+        ConnectionState {
+            connected: false, ..
+        } => led(" ○ ", Color::Red),
+        ConnectionState {
+            last_event_ms_ago, ..
+        } if last_event_ms_ago > CONNECTION_STALE_AFTER_MS => led(" ◐ ", Color::Yellow),
+        _ => led(" ● ", Color::Green),
+    }
+}
+
+/// Creates a colored LED symbol.
+#[cfg(not(feature = "no-color"))]
+fn led(symbol: &'static str, color: Color) -> Span<'static> {
+    Span::styled(symbol, Style::new().fg(color))
+}
+
+/// Creates an LED symbol, builds with the `no-color` feature rely on the symbol alone.
+#[cfg(feature = "no-color")]
+fn led(symbol: &'static str, _color: Color) -> Span<'static> {
+    Span::raw(symbol)
+}
+
+/// The colors of the known stacks in the "Stack" column, by their names as reported by the API
+/// or spelled out, see `stack_color`.
+const STACK_COLORS: [(&[&str], Color); 4] = [
+    (&["op stack", "op", "optimism"], Color::Red),
+    (
+        &["arbitrum nitro", "nitro", "arbitrum", "orbit"],
+        Color::Blue,
+    ),
+    (&["zkevm", "polygon zkevm"], Color::Magenta),
+    (&["polygon cdk", "cdk"], Color::Rgb(143, 0, 255)),
+];
+
+/// Returns the color of a stack in the "Stack" column, after the brand of the known stacks:
+/// red for the OP Stack, blue for Arbitrum Nitro, purple for zkEVM and violet for Polygon CDK.
+///
+/// ### Arguments
+/// * `stack` - The name of the stack, matched ignoring case.
+///
+/// ### Returns
+/// The color of the stack, gray for unknown stacks.
+pub fn stack_color(stack: &str) -> Color {
+    let stack = stack.trim().to_lowercase();
+    STACK_COLORS
+        .iter()
+        .find(|(names, _)| names.contains(&stack.as_str()))
+        .map_or(Color::Gray, |(_, color)| *color)
 }
 
 // Currently since we're working with one screen, we can keep things more compact.
@@ -21,37 +321,109 @@ pub enum SortingStrategy {
 //
 /// Represents the Text-based User Interface (TUI) for displaying network information.
 pub struct Tui {
-    /// The list of networks to display in the table.
-    pub networks: Vec<Network>,
+    /// The networks to display in the table by name, see `networks` for them in table order.
+    networks: HashMap<String, Network>,
+    /// The names of the networks in table order, the only part rearranged by sorting.
+    order: Vec<String>,
     /// {unstable} The current selection state of the table.
     pub selected: TableState,
     /// The current sorting strategy for the network table. GPS is default.
     pub sorting_strategy: SortingStrategy,
+    /// The last direction each sorting strategy was used with, descending unless reversed.
+    pub sort_directions: HashMap<SortingStrategy, SortDirection>,
     ///  Flag to check if the info bar has been rendered to avoid re-rendering.
     pub info_rendered: bool,
+    /// The columns of the network table, in display order.
+    pub columns: Vec<Column>,
+    /// A message displayed in the info bar in place of the key hints, cleared on the next key press.
+    pub status: Option<String>,
+    /// The gas and data processed by all networks since the application started.
+    pub session: SessionTotals,
+    /// The events received since the application started, by what became of them.
+    pub event_counts: EventCounts,
+    /// Whether the summary line shows the event counts, toggled with `v`.
+    pub event_counts_open: bool,
+    /// Whether the info bar is expanded into the key reference, toggled with `h`.
+    pub key_reference_open: bool,
+    /// The last position of the mouse, as `(column, row)`, while it hovers over the info bar.
+    /// Its key hint is described by a tooltip, see `render_tooltip`.
+    pub hovered: Option<(u16, u16)>,
+    /// The area of the info bar in the last rendered frame, to find the hovered key hint.
+    info_area: Rect,
+    /// The options used to format and style the cells of the table.
+    pub display: DisplayOptions,
+    /// Whether the detail pane of the selected network is open.
+    pub detail_open: bool,
+    /// Whether the "Raw Data" section of the detail pane is expanded, toggled with `d`.
+    pub raw_open: bool,
+    /// Whether the "Metadata" section of the detail pane is expanded in place of the "Raw Data"
+    /// one, toggled with `M`.
+    pub metadata_open: bool,
+    /// The number of lines the expanded "Raw Data" or "Metadata" section is scrolled by.
+    pub raw_scroll: u16,
+    /// The page of the history table of the detail pane, 0 for the most recent updates.
+    pub history_page: usize,
+    /// The number of updates per page of the history table in the last rendered frame, as many
+    /// as fit in the pane up to `HISTORY_PAGE_SIZE`.
+    history_rows: usize,
+    /// Whether the SSE stream is currently connected.
+    pub connected: bool,
+    /// Whether the reconnect key has been pressed, until the event loop takes the request.
+    pub reconnect_requested: bool,
+    /// The time the last event was received, if any.
+    pub last_event: Option<Instant>,
+    /// The ID of the last event received with one, to resume the stream from on reconnect.
+    pub last_event_id: Option<String>,
+    /// The number of frames rendered so far, animating the loading spinners.
+    pub render_frame_count: u64,
+    /// The number of events received so far, animating the heartbeat of the summary line.
+    pub heartbeat: u64,
+    /// The sorting strategy previewed while its key is held, committed once it's released.
+    pub sort_preview: Option<SortingStrategy>,
+    /// The last key pressed and when, used to detect key repeats.
+    pub last_key: Option<(KeyCode, Instant)>,
+    /// Whether the last key has repeated, so it's held past the initial delay of the key repeat.
+    pub key_repeating: bool,
+    /// Whether the terminal reports the release of keys, ending the sort previews as they're
+    /// released rather than once the key stops repeating.
+    pub key_releases: bool,
+    /// The name of the network updated last and when, used to flash its row.
+    pub last_updated: Option<(String, Instant)>,
+    /// The time the network metadata was last refreshed, `None` until the first refresh.
+    pub last_refresh: Option<Instant>,
+    /// The interval of `--refresh-metadata`, `None` if refreshing is disabled.
+    pub refresh_interval: Option<Duration>,
+    /// The time of the last attempt to refresh the network metadata, successful or not, or of
+    /// the start. The next refresh is due a `refresh_interval` later.
+    pub last_refresh_attempt: Instant,
+    /// Whether the network metadata is being refreshed.
+    pub refreshing: bool,
+    /// The query of the `/` search filtering the table by name, `None` when not searching.
+    pub filter_query: Option<String>,
+    /// Whether the query has been locked with Enter, returning the keys to their usual actions.
+    pub filter_locked: bool,
+    /// The digits of the rank typed after ':', `None` when not jumping to a row.
+    pub goto_input: Option<String>,
+    /// The number of rows displayed, the first ones in the current order, `None` to display every network.
+    pub top: Option<usize>,
+    /// The number of networks of the metadata hidden by the command-line filters.
+    pub hidden_networks: usize,
+    /// The keys bound to the actions of the interface.
+    pub keys: KeyMap,
+    /// The field of the selected network copied by the `copy` key.
+    pub copy_field: CopyField,
+    /// Pings the website of the network in the detail pane, `None` to skip the latency.
+    pub latency: Option<LatencyMonitor>,
+    /// The metrics served by `--prometheus-port`, updated with every event.
+    #[cfg(feature = "prometheus")]
+    pub metrics: Option<crate::metrics::Metrics>,
 }
 
 impl Tui {
-    // Offload constant values to the struct, so they aren't computed on runtime.
-    const TABLE_STYLE: Style = Style::new().fg(SLATE.c400);
-
-    const TABLE_HIGHLIGHT_STYLE: Style = Style::new().bg(SLATE.c500).fg(SLATE.c900);
-
-    const TABLE_WIDTHS: [Constraint; 8] = [
-        Constraint::Percentage(15),
-        Constraint::Percentage(10),
-        Constraint::Percentage(10),
-        Constraint::Percentage(10),
-        Constraint::Percentage(10),
-        Constraint::Percentage(15),
-        Constraint::Percentage(10),
-        Constraint::Percentage(10),
-    ];
-
     /// Creates a new instance of the Tui.
     ///
     /// The Tui is initialized with the provided list of networks, an empty table selection state set to the first row,
-    /// and the default sorting strategy (GPS).
+    /// the default sorting strategy (GPS) and the default columns.
     ///
     /// ### Arguments
     /// * `networks` - The list of networks to display in the table.
@@ -60,65 +432,542 @@ impl Tui {
     /// A new instance of the Tui.
     pub fn new(networks: Vec<Network>) -> Self {
         Self {
-            networks,
+            order: networks.iter().map(|n| n.name.clone()).collect(),
+            networks: networks.into_iter().map(|n| (n.name.clone(), n)).collect(),
             selected: TableState::new().with_selected(0),
             sorting_strategy: SortingStrategy::default(),
+            sort_directions: HashMap::new(),
             info_rendered: false,
+            columns: Column::DEFAULT.to_vec(),
+            status: None,
+            session: SessionTotals::default(),
+            event_counts: EventCounts::default(),
+            event_counts_open: false,
+            key_reference_open: false,
+            hovered: None,
+            info_area: Rect::default(),
+            display: DisplayOptions::default(),
+            detail_open: false,
+            raw_open: false,
+            metadata_open: false,
+            raw_scroll: 0,
+            history_page: 0,
+            history_rows: HISTORY_PAGE_SIZE,
+            connected: false,
+            reconnect_requested: false,
+            last_event_id: None,
+            last_event: None,
+            render_frame_count: 0,
+            heartbeat: 0,
+            sort_preview: None,
+            last_key: None,
+            key_repeating: false,
+            key_releases: false,
+            last_updated: None,
+            last_refresh: None,
+            refresh_interval: None,
+            last_refresh_attempt: Instant::now(),
+            refreshing: false,
+            filter_query: None,
+            filter_locked: false,
+            goto_input: None,
+            top: None,
+            hidden_networks: 0,
+            keys: KeyMap::default(),
+            copy_field: CopyField::default(),
+            latency: None,
+            #[cfg(feature = "prometheus")]
+            metrics: None,
         }
     }
 
+    /// Applies the user configuration to the Tui.
+    ///
+    /// ### Arguments
+    /// * `config` - The parsed command-line configuration.
+    ///
+    /// ### Returns
+    /// The Tui with the configured sorting strategy and columns.
+    pub fn with_config(mut self, config: &Config) -> Self {
+        self.sorting_strategy = config.sort;
+        self.columns = config.columns();
+        if config.pending_txs && !self.columns.contains(&Column::Pending) {
+            self.columns.push(Column::Pending);
+        }
+        if config.age_bar && !self.columns.contains(&Column::Age) {
+            self.columns.push(Column::Age);
+        }
+        self.display.pending_warn = config.pending_warn;
+        self.display.theme = Theme::detect(config.no_color);
+        self.display.border_type = config.border_style.into();
+        self.display.header_style = config.header_style;
+        self.display.precision = config.precision as usize;
+        self.display.units = config.units;
+        self.display.stale_blocks = config.stale_blocks;
+        self.display.stale_after = config.stale_threshold();
+        self.refresh_interval = config.metadata_refresh_interval();
+        self.display.column_spacing = config.column_spacing;
+        self.display.qr_code = !config.no_qr;
+        self.display.emoji = !config.no_emoji;
+        self.display.stack_colors = !config.no_stack_colors;
+        self.display.anomaly_z = config.anomaly_z();
+        self.display.wrap_labels = config.wrap_labels;
+        self.detail_open = config.networks.len() == 1;
+        self.top = (config.top > 0).then_some(config.top);
+        self.copy_field = config.copy_field;
+        self.sort_networks();
+        self
+    }
+
+    /// Checks whether every network has received at least one update.
+    pub fn all_updated(&self) -> bool {
+        self.networks.values().all(|n| n.data.is_some())
+    }
+
+    /// Checks whether any network has received at least one update.
+    pub fn any_updated(&self) -> bool {
+        self.networks.values().any(|n| n.data.is_some())
+    }
+
     /// Updates the networks data based on the incoming event.
     ///
-    /// This method searches for the network in the `networks` list that matches the `event_type`
-    /// of the incoming event.If a matching network is found, its data is updated with the data
-    /// from the event. After updating the network data, the `sort_networks` method is called to
-    /// re-sort the networks based on the current sorting strategy.
+    /// This method looks up the network matching the `event_type` of the incoming event by name.
+    /// If a matching network is found, its data is updated with the data from the event, then
+    /// the network alone is moved to its place in the current order, see `reposition`.
     ///
-    /// A`HashMap` could be used for faster lookups, although in this case we'd have to `collect()`
-    /// each time before rendering or sorting the networks.
+    /// The session totals are accumulated with the previous rates of the network, held for the
+    /// time elapsed since its previous update. The ID of the event, if any, is kept to resume
+    /// the stream from, see `Client::set_last_event_id`.
     ///
     /// ### Arguments
     /// * `event` - The incoming SSE event containing the network data to update.
+    #[tracing::instrument(
+        level = "trace",
+        name = "event",
+        skip_all,
+        fields(network = event.event_type, id = event.id)
+    )]
     pub fn update_networks(&mut self, event: Event) {
-        if let Some(network) = self
-            .networks
-            .iter_mut()
-            .find(|n| n.name == event.event_type)
-        {
+        tracing::trace!("received event");
+        self.last_event = Some(Instant::now());
+        if event.id.is_some() {
+            self.last_event_id = event.id;
+        }
+        self.heartbeat = self.heartbeat.wrapping_add(1);
+        if let Some(network) = self.networks.get_mut(&event.event_type) {
+            // accumulate the contribution of the network since its previous update
+            if let (Some(previous), Some(updated_at)) = (&network.data, network.updated_at) {
+                self.session
+                    .accumulate(previous, updated_at.elapsed().as_secs_f64());
+            }
+
             // parse the string data into the Data struct
-            let data = serde_json::from_str(&event.data).unwrap_or_default();
-            // and update the network
+            let mut data: Option<Data> = serde_json::from_str(&event.data)
+                .inspect(|_| self.event_counts.parsed += 1)
+                .inspect_err(|e| {
+                    self.event_counts.failed += 1;
+                    tracing::warn!(network = event.event_type, error = %e, "failed to parse event data")
+                })
+                .unwrap_or_default();
+            // and zero the metrics that parsed but can't be valid
+            if let Some(data) = &mut data {
+                let invalid = data.sanitize();
+                if !invalid.is_empty() {
+                    tracing::warn!(network = event.event_type, metrics = ?invalid, "replaced invalid metrics with zero");
+                }
+            }
+            // and update the network, keeping the payload for the "Raw Data" section
+            let alerts = self.display.thresholds.alerts(network);
             network.update_data(data);
+            for alert in self.display.thresholds.alerts(network) {
+                // Only report the metrics crossing a threshold, rather than every update past one
+                if !alerts
+                    .iter()
+                    .any(|a| a.column == alert.column && a.level >= alert.level)
+                {
+                    tracing::warn!(network = network.name, %alert, "threshold crossed");
+                }
+            }
+            network.last_raw_json = Some(event.data);
+            self.last_updated = Some((network.name.clone(), Instant::now()));
+            // Only the metric of this network changed, so the others are still in order
+            if let Some(index) = self.position(&event.event_type) {
+                self.reposition(index);
+            }
+        } else {
+            self.event_counts.untracked += 1;
+            tracing::debug!(
+                network = event.event_type,
+                "dropped event of an untracked network"
+            );
+        }
+
+        #[cfg(feature = "prometheus")]
+        if let Some(metrics) = &self.metrics {
+            metrics.record_event(&self.networks());
         }
+    }
+
+    /// Returns the networks in table order.
+    pub fn networks(&self) -> Vec<&Network> {
+        self.order
+            .iter()
+            .filter_map(|name| self.networks.get(name))
+            .collect()
+    }
+
+    /// Returns the network with the given name, if it's tracked.
+    pub fn network(&self, name: &str) -> Option<&Network> {
+        self.networks.get(name)
+    }
+
+    /// Returns the network with the given name for editing, if it's tracked.
+    ///
+    /// Changing its data doesn't move it in the table, see `reposition` and `sort_networks`.
+    pub fn network_mut(&mut self, name: &str) -> Option<&mut Network> {
+        self.networks.get_mut(name)
+    }
+
+    /// Returns the position of a network in the table.
+    ///
+    /// ### Arguments
+    /// * `name` - The name of the network.
+    ///
+    /// ### Returns
+    /// The position of the network, or `None` if it isn't tracked.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.order.iter().position(|n| n == name)
+    }
 
+    /// Moves a single network to its place in the current order, assuming every other network
+    /// is in order already, such as after an update of its data.
+    ///
+    /// Among networks with an equal metric, the network stays as close to its previous position
+    /// as possible, matching the stable full sort of `sort_networks`.
+    ///
+    /// ### Arguments
+    /// * `index` - The current position of the network.
+    ///
+    /// ### Returns
+    /// The new position of the network.
+    pub fn reposition(&mut self, index: usize) -> usize {
+        let strategy = self.sorting_strategy;
+        let direction = self.sort_direction(strategy);
+        let name = self.order.remove(index);
+        let Some(network) = self.networks.get(&name) else {
+            self.order.insert(index, name);
+            return index;
+        };
+
+        // The range of positions the network can take among the networks with an equal metric
+        let networks = &self.networks;
+        let ordering = |other: &String| compare(&networks[other], network, strategy, direction);
+        let first = self
+            .order
+            .partition_point(|n| ordering(n) == Ordering::Less);
+        let last = self
+            .order
+            .partition_point(|n| ordering(n) != Ordering::Greater);
+        let position = index.clamp(first, last);
+        self.order.insert(position, name);
+        position
+    }
+
+    /// Merges refreshed network metadata into the table.
+    ///
+    /// Networks that weren't tracked before are added and badged as new, see `Network::is_new`,
+    /// while the metadata of the tracked ones is updated in place, keeping their data.
+    /// Networks missing from the refresh are kept, since they may still receive events.
+    ///
+    /// ### Arguments
+    /// * `networks` - The refreshed networks, already filtered by `--include` and `--exclude`.
+    pub fn merge_networks(&mut self, networks: Vec<Network>) {
+        for mut refreshed in networks {
+            match self.networks.get_mut(&refreshed.name) {
+                Some(network) => {
+                    network.label = refreshed.label;
+                    network.parent_chain_id = refreshed.parent_chain_id;
+                    network.parent_chain = refreshed.parent_chain;
+                    network.da = refreshed.da;
+                    network.stack = refreshed.stack;
+                    network.explorer = refreshed.explorer;
+                    network.website = refreshed.website;
+                }
+                None => {
+                    tracing::info!(network = refreshed.name, "discovered a new network");
+                    self.order.push(refreshed.name.clone());
+                    refreshed.added_at = Some(Instant::now());
+                    self.networks.insert(refreshed.name.clone(), refreshed);
+                }
+            }
+        }
+
+        self.last_refresh = Some(Instant::now());
         self.sort_networks();
     }
 
-    /// Sorts the networks based on the current sorting strategy.
+    /// Restores the history of the networks saved by a previous session, see `history::load`.
+    ///
+    /// ### Arguments
+    /// * `history` - The past updates of each network by name, oldest first. The histories of
+    ///   networks that aren't tracked are ignored, the others keep at most `HISTORY_LEN` updates.
     ///
-    /// This method uses the `sort_by` function to sort the `networks` list based on the selected
-    /// sorting strategy. The sorting is performed in descending order.
-    fn sort_networks(&mut self) {
-        self.networks.sort_by(|a, b| {
-            let default = Data::default();
-            let a_data = a.data.as_ref().unwrap_or(&default);
-            let b_data = b.data.as_ref().unwrap_or(&default);
+    /// ### Returns
+    /// The number of networks whose history was restored.
+    pub fn restore_history(&mut self, history: HashMap<String, VecDeque<Data>>) -> usize {
+        let mut restored = 0;
+        for (name, mut past) in history {
+            if let Some(network) = self.networks.get_mut(&name) {
+                past.drain(..past.len().saturating_sub(HISTORY_LEN));
+                network.history = past;
+                restored += 1;
+            }
+        }
+        restored
+    }
 
-            let (a, b) = match self.sorting_strategy {
-                SortingStrategy::Gps => (a_data.gps, b_data.gps),
-                SortingStrategy::Tps => (a_data.tps, b_data.tps),
-                SortingStrategy::Dps => (a_data.dps, b_data.dps),
-            };
+    /// Pings the website of the network in the detail pane while it's open, see `LatencyMonitor::poll`.
+    ///
+    /// ### Returns
+    /// `true` if a ping finished, meaning the detail pane should be redrawn.
+    pub fn poll_latency(&mut self) -> bool {
+        let networks = self.visible_networks();
+        let target = self
+            .selected
+            .selected()
+            .and_then(|i| networks.get(i))
+            .filter(|_| self.detail_open)
+            .and_then(|n| Some((n.name.clone(), n.website.clone()?)));
+        match &mut self.latency {
+            Some(latency) => latency.poll(target.as_ref().map(|(n, w)| (n.as_str(), w.as_str()))),
+            None => false,
+        }
+    }
 
-            b.partial_cmp(&a).unwrap_or(std::cmp::Ordering::Equal)
-        });
+    /// Checks whether the `/` search query or a ':' rank is being typed, in which case every key
+    /// edits it and the main loop shouldn't quit on 'q'.
+    pub fn is_typing(&self) -> bool {
+        (self.filter_query.is_some() && !self.filter_locked) || self.goto_input.is_some()
+    }
+
+    /// Returns the networks displayed in the table, the `--top` ones matching the `/` search query if any.
+    ///
+    /// A network matches when its name or label contains the query, ignoring case.
+    pub fn visible_networks(&self) -> Vec<&Network> {
+        filter(self.networks(), self.top, self.filter_query.as_deref())
+    }
+
+    /// Returns the first `--top` networks in the current order, used by the plain-text output modes.
+    pub fn top_networks(&self) -> Vec<&Network> {
+        let mut networks = self.networks();
+        networks.truncate(self.top.unwrap_or(usize::MAX));
+        networks
+    }
+
+    /// Finds the "top mover", the network with the largest increase of the metric of the
+    /// current sorting strategy over its last update.
+    ///
+    /// ### Returns
+    /// The network and its increase, or `None` if no network increased.
+    pub fn top_mover(&self) -> Option<(&Network, f32)> {
+        self.networks()
+            .into_iter()
+            .filter_map(|n| Some((n, n.delta(self.sorting_strategy)?)))
+            .filter(|(_, delta)| *delta > 0.0)
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+    }
+
+    /// Takes the pending request of the reconnect key, if any.
+    ///
+    /// ### Returns
+    /// `true` once per press, the stream should then be replaced and `reconnected` called.
+    pub fn take_reconnect(&mut self) -> bool {
+        std::mem::take(&mut self.reconnect_requested)
+    }
+
+    /// Reports the outcome of replacing the stream, the data of the networks is kept either way.
+    ///
+    /// On success the connection is reported as down, until the new stream delivers a message
+    /// and `mark_connected` clears the "Reconnecting…" status.
+    ///
+    /// ### Arguments
+    /// * `result` - The result of establishing the new stream.
+    pub fn reconnected(&mut self, result: Result<(), String>) {
+        match result {
+            Ok(()) => self.connected = false,
+            Err(e) => self.status = Some(format!("Reconnect failed: {e}")),
+        }
+    }
+
+    /// Marks the SSE stream as connected after receiving a message, ending a reconnect.
+    pub fn mark_connected(&mut self) {
+        self.connected = true;
+        if self.status.as_deref() == Some(RECONNECTING_STATUS) {
+            self.status = None;
+        }
+    }
+
+    /// Returns the current state of the connection, for the connection indicator.
+    ///
+    /// Before the first event is received, the connection is reported as stale.
+    pub fn connection_state(&self) -> ConnectionState {
+        ConnectionState {
+            connected: self.connected,
+            last_event_ms_ago: self
+                .last_event
+                .map_or(u64::MAX, |t| t.elapsed().as_millis() as u64),
+        }
+    }
+
+    /// Returns the direction of the given sorting strategy.
+    pub fn sort_direction(&self, strategy: SortingStrategy) -> SortDirection {
+        self.sort_directions
+            .get(&strategy)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Switches the table to the given sorting strategy, restoring the direction it was last
+    /// used with. Selecting the current strategy again reverses its direction instead.
+    ///
+    /// ### Arguments
+    /// * `strategy` - The sorting strategy to switch to.
+    pub fn select_sort(&mut self, strategy: SortingStrategy) {
+        if self.sorting_strategy == strategy {
+            let direction = self.sort_direction(strategy).reversed();
+            self.sort_directions.insert(strategy, direction);
+        }
+        self.sorting_strategy = strategy;
+        self.sort_networks();
+    }
+
+    /// Commits the previewed sorting strategy, if any, as if its key was released.
+    pub fn commit_sort_preview(&mut self) {
+        if let Some(strategy) = self.sort_preview.take() {
+            self.select_sort(strategy);
+        }
+    }
+
+    /// Commits the sort preview once its key hasn't repeated for longer than a key repeat,
+    /// meaning it has been released. Called by the main loop while no key is pressed.
+    ///
+    /// ### Returns
+    /// `true` if a preview was committed and the table should be redrawn.
+    pub fn release_keys(&mut self) -> bool {
+        self.release_keys_at(Instant::now())
+    }
+
+    /// Commits the sort preview as of the given time, see `release_keys`.
+    ///
+    /// A key pressed once might still be waiting for the key repeat to start, so it's released
+    /// after `KEY_REPEAT_DELAY`, and a repeating one after `KEY_REPEAT_WINDOW`. Terminals reporting
+    /// the release of keys end the preview on the release instead, see `handle_key`.
+    ///
+    /// ### Arguments
+    /// * `now` - The current time.
+    ///
+    /// ### Returns
+    /// `true` if a preview was committed and the table should be redrawn.
+    pub fn release_keys_at(&mut self, now: Instant) -> bool {
+        let window = match self.key_repeating {
+            true => KEY_REPEAT_WINDOW,
+            false => KEY_REPEAT_DELAY,
+        };
+        let released = self
+            .last_key
+            .is_none_or(|(_, at)| now.saturating_duration_since(at) > window);
+        if self.key_releases || !released || self.sort_preview.is_none() {
+            return false;
+        }
+
+        self.commit_sort_preview();
+        true
+    }
+
+    /// Handles a sorting key, previewing its strategy until the key is released.
+    ///
+    /// A repeat of the held key keeps the preview, or the committed strategy if the hold outlasted
+    /// it, while any other press commits the pending preview first, so sequential presses of the
+    /// same key still reverse the direction once the first one is released.
+    fn press_sort_key(&mut self, strategy: SortingStrategy, repeat: bool) {
+        if repeat && self.sort_preview.unwrap_or(self.sorting_strategy) == strategy {
+            return;
+        }
+        self.commit_sort_preview();
+        self.sort_preview = Some(strategy);
+    }
+
+    /// Returns the visible networks in the order the given strategy would sort them once selected.
+    fn previewed_networks(&self, strategy: SortingStrategy) -> Vec<Network> {
+        let direction = match self.sorting_strategy == strategy {
+            true => self.sort_direction(strategy).reversed(),
+            false => self.sort_direction(strategy),
+        };
+        let mut networks = self.networks();
+        networks.sort_by(|a, b| compare(a, b, strategy, direction));
+        filter(networks, self.top, self.filter_query.as_deref())
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Sorts the networks based on the current sorting strategy, in the direction remembered for it.
+    ///
+    /// Only the names of the table order are moved, the networks stay in place.
+    pub fn sort_networks(&mut self) {
+        let strategy = self.sorting_strategy;
+        let direction = self.sort_direction(strategy);
+        let networks = &self.networks;
+        self.order
+            .sort_by(|a, b| compare(&networks[a], &networks[b], strategy, direction));
+    }
+
+    /// Handles the press of a key without modifiers, see `handle_key`.
+    ///
+    /// ### Arguments
+    /// * `key` - The key code of the user input event.
+    pub fn handle_input(&mut self, key: KeyCode) {
+        self.handle_key(KeyEvent::new(key, KeyModifiers::NONE));
+    }
+
+    /// Handles a resize of the terminal, forgetting the areas of the last rendered frame so that
+    /// no mouse event is matched against the previous layout until the next render.
+    pub fn resized(&mut self) {
+        self.hovered = None;
+        self.info_area = Rect::default();
+    }
+
+    /// Handles a mouse event, captured with `--mouse`. The wheel acts like the keys bound to
+    /// `up` and `down`, scrolling the table or the raw data, and hovering a key hint of the info
+    /// bar describes its action in a tooltip until the mouse moves away.
+    ///
+    /// ### Arguments
+    /// * `event` - The mouse event.
+    ///
+    /// ### Returns
+    /// Whether the event was handled, and the interface has to be redrawn.
+    pub fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        let action = match event.kind {
+            MouseEventKind::ScrollUp => Action::Up,
+            MouseEventKind::ScrollDown => Action::Down,
+            MouseEventKind::Moved => {
+                let previous = self.hovered.and_then(|(c, r)| self.key_hint_at(c, r));
+                let hovered = self.key_hint_at(event.column, event.row);
+                self.hovered = hovered.as_ref().map(|_| (event.column, event.row));
+                return previous.map(|(area, _)| area) != hovered.map(|(area, _)| area);
+            }
+            _ => return false,
+        };
+        let key = self.keys.key(action);
+        self.handle_key(KeyEvent::new(key.code, key.modifiers));
+        true
     }
 
     /// Handles user input related to the TUI functionality.
-    /// The `char` 'q' is handled in the main loop for exiting.`
+    /// The `quit` action is handled in the main loop for exiting.
     ///
-    /// This method responds to user input events to navigate the network table and change the
-    /// sorting strategy. The supported navigation keys are:
+    /// This method resolves the action bound to the key through `keys`, and responds to it by
+    /// navigating the network table or changing the sorting strategy. With the default keys:
     /// - Up arrow: Move the selection to the previous row.
     /// - Down arrow: Move the selection to the next row.
     ///
@@ -126,38 +975,334 @@ impl Tui {
     /// - 'g': Sort by Gas Per Second (GPS).
     /// - 't': Sort by Transactions Per Second (TPS).
     /// - 'k': Sort by Data Per Second (DPS).
+    /// - 'p': Sort by pending transactions.
+    /// - 'a': Sort by gas per transaction.
+    ///
+    /// Pressing the key of the current sorting strategy again reverses its direction.
+    /// While a sorting key is held, the resulting order is previewed and only committed
+    /// once the key is released, on its release event where the terminal reports them,
+    /// otherwise once it stops repeating, see `release_keys`.
+    ///
+    /// The Enter key toggles the detail pane of the selected network, Esc closes it,
+    /// 'd' toggles its "Raw Data" section and 'M' its "Metadata" section, scrolled by the arrows while expanded.
+    /// PgUp and PgDn page through its history table, which 'D' exports as CSV.
+    ///
+    /// The '/' key starts a search filtering the table by name, see `handle_filter_input`,
+    /// and the ':' key jumps to the row of a rank, see `handle_goto_input`.
+    /// Esc clears a locked search once the detail pane is closed, and '/' resumes editing it.
+    /// The keys typed into the search or the rank are never resolved to actions.
+    ///
+    /// The 'e' key opens the block explorer of the selected network in the system browser,
+    /// and the 'x' key exports the table as CSV to the current directory.
     ///
     /// ### Arguments
-    /// * `key` - The key code of the user input event.
-    pub fn handle_input(&mut self, key: KeyCode) {
+    /// * `event` - The user input event.
+    pub fn handle_key(&mut self, event: KeyEvent) {
+        self.handle_key_at(event, Instant::now());
+    }
+
+    /// Handles a key event received at the given time, see `handle_key`.
+    ///
+    /// ### Arguments
+    /// * `event` - The user input event.
+    /// * `now` - The time the event was received, telling key repeats apart.
+    pub fn handle_key_at(&mut self, event: KeyEvent, now: Instant) {
+        let key = event.code;
+        if event.kind == KeyEventKind::Release {
+            // The next press of the key can't be a repeat once it's released
+            self.key_releases = true;
+            self.last_key = None;
+            self.key_repeating = false;
+            let strategy = self.keys.action(&event).and_then(|a| a.sorting_strategy());
+            if strategy.is_some() && strategy == self.sort_preview {
+                self.commit_sort_preview();
+            }
+            return;
+        }
+
+        // Any key press dismisses the previous status message.
+        self.status = None;
+
+        // The same key arriving within the repeat window is being held down, the first repeat
+        // only arriving after the longer delay of the key repeat.
+        let window = match self.key_repeating {
+            true => KEY_REPEAT_WINDOW,
+            false => KEY_REPEAT_DELAY,
+        };
+        let repeat = event.kind == KeyEventKind::Repeat
+            || self.last_key.is_some_and(|(last, at)| {
+                last == key && now.saturating_duration_since(at) <= window
+            });
+        self.key_repeating = repeat;
+        self.last_key = Some((key, now));
+
+        if self.goto_input.is_some() {
+            return self.handle_goto_input(key);
+        }
+        if self.is_typing() {
+            return self.handle_filter_input(key);
+        }
+
+        let Some(action) = self.keys.action(&event) else {
+            // Any other key releases the previewed one.
+            return self.commit_sort_preview();
+        };
+        match action.sorting_strategy() {
+            Some(strategy) => return self.press_sort_key(strategy, repeat),
+            None => self.commit_sort_preview(),
+        }
+
+        match action {
+            Action::Filter => {
+                self.filter_query.get_or_insert_with(String::new);
+                self.filter_locked = false;
+                return;
+            }
+            Action::Goto => {
+                self.goto_input = Some(String::new());
+                return;
+            }
+            Action::Reconnect => {
+                self.reconnect_requested = true;
+                self.status = Some(String::from(RECONNECTING_STATUS));
+                return;
+            }
+            Action::Close if !self.detail_open && self.filter_query.is_some() => {
+                return self.clear_filter();
+            }
+            _ => {}
+        }
+
         if let Some(current) = self.selected.selected() {
-            let network_size = self.networks.len().saturating_sub(1);
-            match key {
-                KeyCode::Up => {
+            let network_size = self.visible_networks().len().saturating_sub(1);
+            match action {
+                // The arrows scroll the expanded section rather than the table
+                Action::Up if self.detail_open && (self.raw_open || self.metadata_open) => {
+                    self.raw_scroll = self.raw_scroll.saturating_sub(1);
+                }
+                Action::Down if self.detail_open && (self.raw_open || self.metadata_open) => {
+                    let last = self.expanded_lines(current).len().saturating_sub(1) as u16;
+                    self.raw_scroll = self.raw_scroll.saturating_add(1).min(last);
+                }
+                Action::RawData if self.detail_open => {
+                    self.raw_open = !self.raw_open;
+                    self.metadata_open = false;
+                    self.raw_scroll = 0;
+                }
+                Action::Metadata if self.detail_open => {
+                    self.metadata_open = !self.metadata_open;
+                    self.raw_open = false;
+                    self.raw_scroll = 0;
+                }
+                // The pages shrink with the pane, so the page is kept within the current ones
+                Action::HistoryPageUp if self.detail_open => {
+                    let last = self.history_pages(current).saturating_sub(1);
+                    self.history_page = self.history_page.min(last).saturating_sub(1);
+                }
+                Action::HistoryPageDown if self.detail_open => {
+                    let last = self.history_pages(current).saturating_sub(1);
+                    self.history_page = self.history_page.saturating_add(1).min(last);
+                }
+                Action::ExportHistory if self.detail_open => self.export_history(current),
+                Action::Up => {
                     self.selected
                         .select(Some(current.saturating_sub(1).min(network_size)));
                 }
-                KeyCode::Down => {
+                Action::Down => {
                     self.selected
                         .select(Some(current.saturating_add(1).min(network_size)));
                 }
-                KeyCode::Char('g') => {
-                    self.sorting_strategy = SortingStrategy::Gps;
-                    self.sort_networks();
+                Action::Details => {
+                    self.detail_open = !self.detail_open;
+                    self.history_page = 0;
                 }
-                KeyCode::Char('t') => {
-                    self.sorting_strategy = SortingStrategy::Tps;
-                    self.sort_networks();
+                Action::Close => self.detail_open = false,
+                Action::Explorer => self.open_explorer(current),
+                Action::Copy => self.copy_network(current),
+                Action::EventCounts => self.event_counts_open = !self.event_counts_open,
+                Action::KeyReference => self.key_reference_open = !self.key_reference_open,
+                Action::Units => {
+                    self.display.units = self.display.units.next();
+                    self.status = Some(format!("Units: {}", self.display.units));
                 }
-                KeyCode::Char('k') => {
-                    self.sorting_strategy = SortingStrategy::Dps;
-                    self.sort_networks();
+                Action::RateUnit => {
+                    self.display.rate_unit = self.display.rate_unit.toggled();
+                    self.status = Some(format!("Rates: {}", self.display.rate_unit));
                 }
+                Action::Export => self.export_csv(),
                 _ => {}
             }
         }
     }
 
+    /// Handles a key while the `/` search query is being typed.
+    ///
+    /// Characters are appended to the query and Backspace removes the last one, filtering the table
+    /// as the query changes. Enter locks the query, or clears it if empty, and Esc clears it.
+    ///
+    /// ### Arguments
+    /// * `key` - The key code of the user input event.
+    fn handle_filter_input(&mut self, key: KeyCode) {
+        let Some(query) = self.filter_query.as_mut() else {
+            return;
+        };
+
+        match key {
+            KeyCode::Char(c) => query.push(c),
+            KeyCode::Backspace => {
+                query.pop();
+            }
+            KeyCode::Enter if query.is_empty() => return self.clear_filter(),
+            KeyCode::Enter => self.filter_locked = true,
+            KeyCode::Esc => return self.clear_filter(),
+            _ => return,
+        }
+        // The matches change, so start over from the first one.
+        self.selected.select(Some(0));
+    }
+
+    /// Handles a key while a rank is being typed after ':'.
+    ///
+    /// Digits are appended to the rank and Backspace removes the last one. Enter selects the row
+    /// of the 1-based rank in the displayed order, clamped to the rows of the table, and Esc cancels.
+    ///
+    /// ### Arguments
+    /// * `key` - The key code of the user input event.
+    fn handle_goto_input(&mut self, key: KeyCode) {
+        let Some(mut input) = self.goto_input.take() else {
+            return;
+        };
+
+        match key {
+            KeyCode::Char(c) if c.is_ascii_digit() => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter if input.is_empty() => return,
+            KeyCode::Enter => {
+                // Ranks with too many digits to parse are past the last row anyway
+                let rank: usize = input.parse().unwrap_or(usize::MAX);
+                let last = self.visible_networks().len().saturating_sub(1);
+                self.selected.select(Some(rank.saturating_sub(1).min(last)));
+                return;
+            }
+            KeyCode::Esc => return,
+            _ => {}
+        }
+        self.goto_input = Some(input);
+    }
+
+    /// Clears the `/` search, displaying every network again.
+    fn clear_filter(&mut self) {
+        self.filter_query = None;
+        self.filter_locked = false;
+        self.selected.select(Some(0));
+    }
+
+    /// Exports the table, in its current order and columns, to a timestamped CSV file
+    /// in the current directory and reports the outcome in the info bar.
+    fn export_csv(&mut self) {
+        let path = format!("roller-{}.csv", crate::output::timestamp_ms());
+        let result = std::fs::File::create(&path)
+            .map_err(Box::<dyn std::error::Error>::from)
+            .and_then(|file| {
+                let mut writer = CsvWriter::new(file, &self.columns, false);
+                writer.write_header()?;
+                for network in self.visible_networks() {
+                    writer.write_network(network)?;
+                }
+                writer.flush()?;
+                Ok(())
+            });
+
+        self.status = Some(match result {
+            Ok(_) => format!("Exported to {path}"),
+            Err(e) => format!("Export failed: {e}"),
+        });
+    }
+
+    /// Exports the history of the network at the given row to a timestamped CSV file in the
+    /// current directory and reports the outcome in the info bar, see `history::write_csv`.
+    ///
+    /// ### Arguments
+    /// * `index` - The row of the network in the table.
+    fn export_history(&mut self, index: usize) {
+        let networks = self.visible_networks();
+        let Some(network) = networks.get(index) else {
+            return;
+        };
+        let path = format!(
+            "roller-{}-history-{}.csv",
+            network.name,
+            crate::output::timestamp_ms()
+        );
+        let result = std::fs::File::create(&path)
+            .map_err(Box::<dyn std::error::Error>::from)
+            .and_then(|file| Ok(crate::history::write_csv(file, &network.history)?));
+
+        self.status = Some(match result {
+            Ok(_) => format!("Exported the history to {path}"),
+            Err(e) => format!("Export failed: {e}"),
+        });
+    }
+
+    /// Returns the number of pages of the history table of the network at the given row,
+    /// at least one even without history, as last rendered.
+    ///
+    /// ### Arguments
+    /// * `index` - The row of the network in the table.
+    fn history_pages(&self, index: usize) -> usize {
+        let networks = self.visible_networks();
+        let len = networks.get(index).map_or(0, |n| n.history.len());
+        len.div_ceil(self.history_rows).max(1)
+    }
+
+    /// Opens the block explorer URL of the network at the given row in the system browser.
+    ///
+    /// The launcher is detached rather than waited for, so a slow or blocking one doesn't freeze
+    /// the interface and the processing of events.
+    /// If the network has no explorer URL, or the browser cannot be opened, a message is shown
+    /// in the info bar instead. In the latter case it includes the URL so it can be copied manually.
+    ///
+    /// ### Arguments
+    /// * `index` - The row of the network in the table.
+    fn open_explorer(&mut self, index: usize) {
+        let networks = self.visible_networks();
+        let explorer = networks
+            .get(index)
+            .and_then(|n| n.explorer.as_deref())
+            .filter(|url| !url.is_empty());
+
+        self.status = match explorer {
+            None => Some(String::from("No explorer URL available")),
+            Some(url) => match open::that_detached(url) {
+                Ok(_) => None,
+                Err(_) => Some(format!("Could not open a browser, explorer: {url}")),
+            },
+        };
+    }
+
+    /// Copies the `--copy-field` of the network at the given row, see `clipboard::copy`,
+    /// and reports the outcome in the info bar.
+    ///
+    /// ### Arguments
+    /// * `index` - The row of the network in the table.
+    fn copy_network(&mut self, index: usize) {
+        let networks = self.visible_networks();
+        let Some(network) = networks.get(index) else {
+            return;
+        };
+
+        self.status = Some(match self.copy_field.value(network) {
+            None => String::from("No explorer URL available"),
+            Some(text) => match clipboard::copy(&text) {
+                Ok(Copied::Clipboard) => format!("Copied: {text}"),
+                Ok(Copied::File(path)) => format!("Copied to {}: {text}", path.display()),
+                Err(e) => format!("Copy failed: {e}"),
+            },
+        });
+    }
+
     /// Renders the network table and the info using the provided `Frame`.
     /// The layout is split vertically into two chunks: the network table and the information bar.
     ///
@@ -165,71 +1310,956 @@ impl Tui {
     /// information bar displays the available user actions, such as quitting the application
     /// and changing the sorting strategy.
     ///
+    /// Frames smaller than `MIN_TERMINAL_SIZE` only display a message asking to enlarge the terminal.
+    ///
     /// ### Arguments
     /// * `f` - The `Frame` to render the TUI.
     pub fn render(&mut self, f: &mut Frame) {
-        // Create the main layout
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(100), Constraint::Min(3)])
-            .split(f.size());
+        self.render_at(f, Instant::now());
+    }
 
-        // Split the layout into two chunks: the network table and the info bar
-        let (network_layout, info_layout) = (chunks[0], chunks[1]);
+    /// Renders the TUI as of the given time, see `render`, dimming the rows of the networks
+    /// not updated within `stale_after` of it.
+    ///
+    /// ### Arguments
+    /// * `f` - The `Frame` to render the TUI.
+    /// * `now` - The current time.
+    pub fn render_at(&mut self, f: &mut Frame, now: Instant) {
+        self.render_frame_count = self.render_frame_count.wrapping_add(1);
+
+        // Reset the whole frame, so no ghost characters of a wider render survive a resize
+        f.render_widget(Clear, f.size());
+
+        let (min_width, min_height) = MIN_TERMINAL_SIZE;
+        if f.size().width < min_width || f.size().height < min_height {
+            self.info_area = Rect::default();
+            return self.render_too_small(f);
+        }
 
-        // Render the network table
-        self.network_table(f, network_layout);
+        // Split the layout into three chunks: the summary, the network table and the info bar
+        let (summary_layout, network_layout, info_layout) =
+            Self::layout(f.size(), self.info_bar_height());
+
+        // Render the summary and the network table
+        self.render_summary(f, summary_layout);
+        match self.sort_preview {
+            Some(strategy) => self.render_sort_preview(f, network_layout, strategy),
+            None => self.render_network_table(f, network_layout, now),
+        }
+        self.render_connection_indicator(f, network_layout);
+        self.info_area = info_layout;
         self.render_info_bar(f, info_layout);
+        if let Some((area, segment)) = self
+            .hovered
+            .and_then(|(column, row)| self.key_hint_at(column, row))
+        {
+            self.render_tooltip(f, area, &segment);
+        }
+
+        // Render the detail pane on top of the table
+        if self.detail_open {
+            let mut area = centered_rect(60, 60, network_layout);
+            let height = DETAIL_PANE_MIN_HEIGHT
+                .min(network_layout.height)
+                .max(area.height);
+            area.y = network_layout.y + (network_layout.height - height) / 2;
+            area.height = height;
+            if let Some(rows) = self.render_detail_pane(f, area) {
+                self.history_rows = rows;
+            }
+        }
+    }
+
+    /// Renders the message displayed in place of the interface in a frame too small for it.
+    fn render_too_small(&self, f: &mut Frame) {
+        let (min_width, min_height) = MIN_TERMINAL_SIZE;
+        let size = f.size();
+        let message = format!(
+            "Terminal too small: {}x{}, at least {min_width}x{min_height} required.",
+            size.width, size.height
+        );
+        let paragraph = Paragraph::new(message)
+            .alignment(Alignment::Center)
+            .style(self.display.theme.caution)
+            .wrap(Wrap { trim: true });
+        f.render_widget(paragraph, size);
+    }
+
+    /// Splits the given area into the summary line, the network table and the info bar.
+    ///
+    /// The summary takes a single row at the top and the info bar keeps a fixed height,
+    /// see `info_bar_height`, while the table fills the remaining space.
+    ///
+    /// ### Arguments
+    /// * `area` - The area of the whole frame.
+    /// * `info_height` - The height of the info bar, including its borders.
+    ///
+    /// ### Returns
+    /// The areas of the summary, the network table and the info bar.
+    pub fn layout(area: Rect, info_height: u16) -> (Rect, Rect, Rect) {
+        let chunks = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(info_height),
+        ])
+        .flex(layout::Flex::Legacy)
+        .split(area);
+
+        (chunks[0], chunks[1], chunks[2])
     }
 
-    fn network_table(&mut self, f: &mut Frame, area: Rect) {
-        // Create the network table block
-        let network_block = Block::default()
+    /// Renders a one-line summary of how many networks are active and the top mover,
+    /// computed on each render.
+    ///
+    /// The summary starts with a heartbeat that pulses with every received event,
+    /// freezing when the stream stalls.
+    fn render_summary(&self, f: &mut Frame, area: Rect) {
+        let active = self
+            .networks
+            .values()
+            .filter(|n| n.is_active(self.display.stale_after))
+            .count();
+
+        let heartbeat = HEARTBEAT[(self.heartbeat % HEARTBEAT.len() as u64) as usize];
+        let mut summary = format!(
+            " {heartbeat} {active} of {} networks active",
+            self.networks.len()
+        );
+        if let Some((network, delta)) = self.top_mover() {
+            let unit = self.sorting_strategy.unit();
+            let delta = format::number(delta as f64, self.display.precision, self.display.units);
+            summary += &format!(" | top mover: {} +{delta} {unit}", network.label);
+        }
+        if self.event_counts_open {
+            summary += &format!(" | {}", self.event_counts);
+        }
+
+        let summary = Paragraph::new(summary).style(self.display.theme.base);
+
+        f.render_widget(summary, area);
+    }
+
+    /// Creates a bordered block with the configured theme and border type, shared by all widgets.
+    fn block(&self) -> Block<'static> {
+        Block::default()
             .borders(Borders::ALL)
-            .style(Self::TABLE_STYLE)
-            .border_type(BorderType::Rounded);
+            .style(self.display.theme.base)
+            .border_type(self.display.border_type)
+    }
+
+    /// Renders the details of the selected network as a popup over the given area.
+    ///
+    /// ### Returns
+    /// The number of updates per page of the history table, `None` if it isn't rendered.
+    fn render_detail_pane(&self, f: &mut Frame, area: Rect) -> Option<usize> {
+        let networks = self.visible_networks();
+        let network = self.selected.selected().and_then(|i| networks.get(i))?;
+        let data = network.data.to_owned().unwrap_or_default();
+        let (precision, units) = (self.display.precision, self.display.units);
+        let rate = self.display.rate_unit;
+        let pending = data
+            .pending_tx_count
+            .map_or(String::from("-"), |p| p.to_string());
+        // The rates in the unit of the table, labeled with it whatever the table headers are
+        let metric = |column: Column| {
+            Line::from(format!(
+                "{}: {}",
+                column.rate_header(rate),
+                column.rate_value(network, precision, units, rate)
+            ))
+        };
+
+        let mut lines = vec![
+            Line::from(format!("Block: {}", data.block_number)),
+            metric(Column::Tps),
+            metric(Column::Gps),
+            metric(Column::Dps),
+            Line::from(format!("Pending TXs: {pending}")),
+            Line::from(""),
+            Line::from(format!("Stack: {}", network.stack)),
+            Line::from(format!(
+                "DA: {}",
+                match network.is_l1() {
+                    true => Column::Da.value(network, precision, units),
+                    false => self.display.da.name(&network.da),
+                }
+            )),
+            Line::from(format!(
+                "Settlement: {}",
+                Column::Settlement.value(network, precision, units)
+            )),
+            Line::from(format!(
+                "Explorer: {}",
+                network.explorer.as_deref().unwrap_or("-")
+            )),
+        ];
+        if let Some(latency) = &self.latency {
+            let pings = latency.history(&network.name);
+            lines.push(Line::from(match pings.and_then(|p| p.back()) {
+                Some(last) => {
+                    let pings = pings.into_iter().flatten();
+                    // Label the sparkline with the range it's scaled over
+                    let legend = match latency::range(pings.clone()) {
+                        Some((min, max)) => {
+                            format!(" min:{} max:{}", Ping::Latency(min), Ping::Latency(max))
+                        }
+                        None => String::new(),
+                    };
+                    format!("Latency: {last} {}{legend}", latency::sparkline(pings))
+                }
+                None if network.website.is_some() => String::from("Latency: measuring..."),
+                None => String::from("Latency: -"),
+            }));
+        }
+
+        // Draw a QR code of the explorer URL below the stats, to open it on a phone,
+        // unless an expanded section takes the space
+        let expanded = self.raw_open || self.metadata_open;
+        let qr_code = network
+            .explorer
+            .as_deref()
+            .filter(|_| self.display.qr_code && !expanded)
+            .and_then(crate::qr::render);
+        if let Some(qr_code) = qr_code {
+            lines.push(Line::from(""));
+            lines.extend(qr_code.into_iter().map(Line::from));
+        }
+
+        lines.push(Line::from(""));
+        let keys = &self.keys;
+        let section = |name, open, action| match open {
+            true => format!(
+                "▾ {name} ([{}] collapse | [{}/{}] scroll)",
+                keys.key(action),
+                keys.key(Action::Up),
+                keys.key(Action::Down)
+            ),
+            false => format!("▸ {name} ([{}] expand)", keys.key(action)),
+        };
+        lines.push(Line::from(section(
+            "Raw Data",
+            self.raw_open,
+            Action::RawData,
+        )));
+        lines.push(Line::from(section(
+            "Metadata",
+            self.metadata_open,
+            Action::Metadata,
+        )));
+
+        let detail_block = self
+            .block()
+            .title(format!(" {} ({}) ", network.label, network.name));
+        let inner = detail_block.inner(area);
+
+        // Clear the table below the popup before rendering it
+        f.render_widget(Clear, area);
+        f.render_widget(detail_block, area);
+
+        // The expanded section fills the space below the stats, scrolling on its own,
+        // otherwise the history table does
+        let [stats_area, raw_area] =
+            Layout::vertical([Constraint::Length(lines.len() as u16), Constraint::Min(0)])
+                .areas(inner);
+        f.render_widget(Paragraph::new(lines), stats_area);
+        match self.selected.selected() {
+            Some(index) if expanded => {
+                let raw = self.expanded_lines(index).into_iter().map(Line::from);
+                f.render_widget(
+                    Paragraph::new(raw.collect::<Vec<_>>()).scroll((self.raw_scroll, 0)),
+                    raw_area,
+                );
+                None
+            }
+            _ => Some(self.render_history_table(f, network, raw_area)),
+        }
+    }
+
+    /// Renders a page of the past updates of a network as a table, the most recent first,
+    /// below a line naming the page and its keys.
+    ///
+    /// A page lists as many updates as fit below the header of the table, up to
+    /// `HISTORY_PAGE_SIZE`, so that paging goes through every one of them.
+    ///
+    /// ### Arguments
+    /// * `f` - The frame to render to.
+    /// * `network` - The network whose history to list.
+    /// * `area` - The area of the line and the table.
+    ///
+    /// ### Returns
+    /// The number of updates per page.
+    fn render_history_table(&self, f: &mut Frame, network: &Network, area: Rect) -> usize {
+        let [title_area, table_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+        let page_size = (table_area.height.saturating_sub(1) as usize).clamp(1, HISTORY_PAGE_SIZE);
+        let pages = network.history.len().div_ceil(page_size).max(1);
+        let page = self.history_page.min(pages - 1);
+        let keys = &self.keys;
+        let title = Line::from(format!(
+            "History, page {}/{pages} ([{}/{}] page | [{}] export)",
+            page + 1,
+            keys.key(Action::HistoryPageUp),
+            keys.key(Action::HistoryPageDown),
+            keys.key(Action::ExportHistory)
+        ));
+        f.render_widget(Paragraph::new(title), title_area);
 
-        // Generate and collect all rows for the network table
-        let row_data = self.networks.iter().map(Network::to_row);
+        let (precision, units) = (self.display.precision, self.display.units);
+        let rate = self.display.rate_unit;
+        let metric = |value: f32| format::number(value as f64 * rate.factor(), precision, units);
+        let len = network.history.len();
+        let rows = network
+            .history
+            .iter()
+            .rev()
+            .enumerate()
+            .skip(page * page_size)
+            .take(page_size)
+            .map(|(i, data)| {
+                Row::new([
+                    format!("-{}", i + 1),
+                    data.block_number.to_string(),
+                    metric(data.tps),
+                    metric(data.gps),
+                    metric(data.dps),
+                    data.pending_tx_count
+                        .map_or(String::from("-"), |p| p.to_string()),
+                ])
+            });
+        let header = Row::new([
+            "#",
+            "Block",
+            Column::Tps.rate_header(rate),
+            Column::Gps.rate_header(rate),
+            Column::Dps.rate_header(rate),
+            "Pending",
+        ])
+        .style(Style::default().add_modifier(self.display.header_style.into()));
+        let widths = [
+            Constraint::Length(len.to_string().len() as u16 + 1),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(8),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .column_spacing(self.display.column_spacing);
+        match len {
+            0 => f.render_widget(Paragraph::new("No past updates yet"), table_area),
+            _ => f.render_widget(table, table_area),
+        }
+        page_size
+    }
+
+    fn render_network_table(&mut self, f: &mut Frame, area: Rect, now: Instant) {
+        // Create the network table block, counting down to the next metadata refresh on its footer
+        let mut network_block = self.block();
+        if let Some(countdown) = self.refresh_countdown() {
+            let title = block::Title::from(format!(" {countdown} "))
+                .position(block::Position::Bottom)
+                .alignment(Alignment::Right);
+            network_block = network_block.title(title);
+        }
+
+        // Generate and collect all rows for the network table, flashing the one updated last
+        // and dimming the stale ones.
+        // Filter through the fields, since the table can't borrow the whole Tui while rendering.
+        let ordered = self.order.iter().filter_map(|name| self.networks.get(name));
+        let networks = filter(ordered.collect(), self.top, self.filter_query.as_deref());
+        let label_width = self.label_width(area, HIGHLIGHT_SYMBOL.chars().count() as u16);
+        let row_data = networks.into_iter().map(|n| {
+            let row = n.to_row(
+                &self.columns,
+                &self.display,
+                self.render_frame_count,
+                label_width,
+            );
+            match self.flash_style(n) {
+                Some(style) => row.style(style),
+                None if n.is_stale(self.display.stale_after, now) => {
+                    row.style(self.display.theme.stale)
+                }
+                None => row,
+            }
+        });
 
         // Initiate the Header row of the table
-        let row_data_header = Row::new(vec![
-            Cell::from("Network"),
-            Cell::from("Block"),
-            Cell::from("TPS"),
-            Cell::from("MGas/s"),
-            Cell::from("KB/s"),
-            Cell::from("Stack"),
-            Cell::from("DA"),
-            Cell::from("Settlement"),
-        ]);
+        let display = &self.display;
+        let row_data_header = Row::new(self.columns.iter().map(|c| Cell::from(display.header(*c))))
+            .style(Style::default().add_modifier(self.display.header_style.into()));
 
         // Create the table widget
-        let table = Table::new(row_data, &Self::TABLE_WIDTHS)
+        let widths = self.columns.iter().map(Column::width);
+        let table = Table::new(row_data, widths)
             .block(network_block)
             .header(row_data_header)
-            .highlight_style(Self::TABLE_HIGHLIGHT_STYLE)
-            .highlight_symbol(">> ");
+            .column_spacing(self.display.column_spacing)
+            .highlight_style(self.display.theme.highlight)
+            .highlight_symbol(HIGHLIGHT_SYMBOL);
 
         // Render it with access to the state, which allows to move through entries
         f.render_stateful_widget(table, area, &mut self.selected);
+
+        // Explain an empty table, so that it isn't mistaken for an outage
+        if let Some(message) = self.empty_table_message() {
+            // Inside the borders, below the header
+            let message_area = area.inner(&Margin::new(1, 2));
+            let message = Paragraph::new(message)
+                .alignment(Alignment::Center)
+                .style(self.display.theme.caution)
+                .wrap(Wrap { trim: true });
+            f.render_widget(message, message_area);
+        }
+    }
+
+    /// Returns the footer of the table counting down to the next metadata refresh, updated on
+    /// every render, e.g. "Next refresh in: 42s".
+    ///
+    /// ### Returns
+    /// The footer, "Refreshing metadata…" during a refresh, or `None` if refreshing is disabled.
+    pub fn refresh_countdown(&self) -> Option<String> {
+        let interval = self.refresh_interval?;
+        Some(match self.refreshing {
+            true => String::from("Refreshing metadata…"),
+            false => format!(
+                "Next refresh in: {}s",
+                next_refresh_countdown(self.last_refresh_attempt, interval.as_secs())
+            ),
+        })
+    }
+
+    /// Returns the message displayed in place of the rows when the table is empty,
+    /// telling apart the networks hidden by a filter from the absence of networks.
+    ///
+    /// ### Returns
+    /// The message, or `None` if any network is displayed.
+    pub fn empty_table_message(&self) -> Option<String> {
+        if !self.visible_networks().is_empty() {
+            return None;
+        }
+
+        Some(match (&self.filter_query, self.hidden_networks) {
+            (Some(query), _) if !self.networks.is_empty() => {
+                format!("No networks match filter '{query}' — press Esc to clear.")
+            }
+            (_, 0) => String::from("No networks available."),
+            (_, hidden) => format!(
+                "No networks match the command-line filters, {hidden} hidden by --include, --exclude or --layer."
+            ),
+        })
+    }
+
+    /// Returns the lines of the expanded section of the detail pane, "Metadata" or "Raw Data".
+    ///
+    /// ### Arguments
+    /// * `index` - The index of the network among the visible ones.
+    fn expanded_lines(&self, index: usize) -> Vec<String> {
+        match self.metadata_open {
+            true => self.metadata_lines(index),
+            false => self.raw_lines(index),
+        }
+    }
+
+    /// Returns the lines of the "Metadata" section of a network, see `Network::metadata_json`.
+    ///
+    /// ### Arguments
+    /// * `index` - The index of the network among the visible ones.
+    fn metadata_lines(&self, index: usize) -> Vec<String> {
+        let networks = self.visible_networks();
+        match networks.get(index).map(|n| n.metadata_json()) {
+            Some(Ok(json)) => json.lines().map(String::from).collect(),
+            Some(Err(e)) => vec![format!("Failed to format the metadata: {e}")],
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the lines of the "Raw Data" section of a network, its last payload pretty-printed.
+    ///
+    /// ### Arguments
+    /// * `index` - The index of the network among the visible ones.
+    fn raw_lines(&self, index: usize) -> Vec<String> {
+        let networks = self.visible_networks();
+        match networks.get(index).and_then(|n| n.last_raw_json.as_deref()) {
+            Some(json) => format::pretty_json(json)
+                .lines()
+                .map(String::from)
+                .collect(),
+            None => vec![String::from("No events received yet")],
+        }
+    }
+
+    /// Resolves the width of the `Network` column in a table drawn over the given area,
+    /// laying out the columns like the `Table` widget does.
+    ///
+    /// ### Arguments
+    /// * `area` - The area of the table, including its borders.
+    /// * `selection_width` - The width reserved for the highlight symbol of the selected row.
+    ///
+    /// ### Returns
+    /// The width of the column, or `None` if the table doesn't display it.
+    fn label_width(&self, area: Rect, selection_width: u16) -> Option<usize> {
+        let index = self.columns.iter().position(|c| *c == Column::Network)?;
+        let inner = Rect::new(0, 0, area.width.saturating_sub(2), 1);
+        let [_, columns_area] =
+            Layout::horizontal([Constraint::Length(selection_width), Constraint::Fill(0)])
+                .areas(inner);
+        let widths = Layout::horizontal(self.columns.iter().map(Column::width))
+            .flex(layout::Flex::Start)
+            .spacing(self.display.column_spacing)
+            .split(columns_area);
+        Some(widths[index].width as usize)
+    }
+
+    /// Returns the style of the row of a network if it has just been updated.
+    ///
+    /// The row flashes for `FLASH_DURATION` and fades out for as long again,
+    /// so that only the latest activity stands out.
+    fn flash_style(&self, network: &Network) -> Option<Style> {
+        let (name, at) = self.last_updated.as_ref()?;
+        if *name != network.name {
+            return None;
+        }
+
+        match at.elapsed() {
+            elapsed if elapsed < FLASH_DURATION => Some(self.display.theme.flash),
+            elapsed if elapsed < FLASH_DURATION * 2 => Some(self.display.theme.flash_fading),
+            _ => None,
+        }
+    }
+
+    /// Renders a dimmed "ghost" of the table in the order the previewed strategy would sort it,
+    /// in place of the table, without changing the current sorting.
+    fn render_sort_preview(&self, f: &mut Frame, area: Rect, strategy: SortingStrategy) {
+        let networks = self.previewed_networks(strategy);
+        let rows = networks.iter().map(|n| {
+            n.to_row(
+                &self.columns,
+                &self.display,
+                self.render_frame_count,
+                self.label_width(area, 0),
+            )
+        });
+        let display = &self.display;
+        let header = Row::new(self.columns.iter().map(|c| Cell::from(display.header(*c))));
+
+        let table = Table::new(rows, self.columns.iter().map(Column::width))
+            .block(self.block().title(" sort preview, release to apply "))
+            .header(header)
+            .column_spacing(self.display.column_spacing)
+            .style(Style::new().add_modifier(Modifier::DIM));
+
+        f.render_widget(table, area);
+    }
+
+    /// Renders the connection LED over the top-right corner of the border of the given area.
+    fn render_connection_indicator(&self, f: &mut Frame, area: Rect) {
+        let mut led = connection_led(self.connection_state());
+        if !self.display.theme.color {
+            led.style = Style::new();
+        }
+
+        let width = led.width() as u16;
+        // Leave the corner of the border visible, and skip areas too narrow to fit the LED.
+        if area.width < width + 2 || area.height == 0 {
+            return;
+        }
+        let led_area = Rect::new(area.right() - width - 1, area.y, width, 1);
+        f.render_widget(Paragraph::new(led), led_area);
     }
 
     fn render_info_bar(&self, f: &mut Frame, area: Rect) {
-        // Create the info bar block
-        let info_block = Block::default()
-            .borders(Borders::ALL)
-            .style(Self::TABLE_STYLE)
-            .border_type(BorderType::Rounded);
+        // Create the info bar block, with the session totals on its top border
+        let (precision, units) = (self.display.precision, self.display.units);
+        let session = format!(
+            " session: {} data, {} ",
+            format::quantity(self.session.bytes, "B", precision, units),
+            format::quantity(self.session.gas, "Gas", precision, units)
+        );
+        let mut info_block = self
+            .block()
+            .title(session)
+            .title_alignment(Alignment::Right);
 
-        // Create the info bar text
-        let info_text = Paragraph::new(
-            "[q] quit | sort: ([g] gas per second | [t] txs per second [k] kb per second)",
-        )
-        .alignment(Alignment::Center)
-        .block(info_block);
+        // Show when the metadata was last refreshed on the left of the top border
+        if let Some(refreshed) = self.last_refresh {
+            let elapsed = Duration::from_secs(refreshed.elapsed().as_secs());
+            let refresh = format!(
+                " metadata refreshed {} ago ",
+                humantime::format_duration(elapsed)
+            );
+            info_block = info_block.title(block::Title::from(refresh).alignment(Alignment::Left));
+        }
+
+        // Summarize the optional modes on the bottom border once there are too many to remember
+        let modes = self.active_modes();
+        if modes.len() > 2 {
+            let summary = format!("active modes: {}", modes.join(" "));
+            let summary = format::truncate_to_width(&summary, area.width as usize / 3);
+            let title = block::Title::from(format!(" {summary} "))
+                .position(block::Position::Bottom)
+                .alignment(Alignment::Left);
+            info_block = info_block.title(title);
+        }
+
+        // Create the info bar text, a status message takes precedence over the search and the key hints
+        let info_text: Text = match (&self.status, &self.goto_input, &self.filter_query) {
+            (Some(status), _, _) => Line::from(status.as_str()).into(),
+            (None, Some(rank), _) => {
+                Line::from(format!("Go to rank: {rank} | [Enter] go | [Esc] cancel")).into()
+            }
+            (None, None, Some(query)) => self.filter_line(query).into(),
+            (None, None, None) if self.key_reference_open => self.key_reference().into(),
+            (None, None, None) => Line::from(self.key_hints().0).into(),
+        };
+        let info_text = Paragraph::new(info_text)
+            .alignment(Alignment::Center)
+            .block(info_block);
 
         // Render the info bar
         f.render_widget(info_text, area);
     }
+
+    /// Returns the optional modes enabled, by the options or at runtime, as a space-separated list,
+    /// e.g. "pending age top latency". Empty when every mode is off.
+    pub fn active_mode_summary(&self) -> String {
+        self.active_modes().join(" ")
+    }
+
+    /// Returns the names of the optional modes enabled, see `active_mode_summary`.
+    fn active_modes(&self) -> Vec<String> {
+        let column = |column, name: &str| self.columns.contains(&column).then(|| name.to_string());
+        let units = self.display.units;
+        [
+            column(Column::Pending, "pending"),
+            column(Column::Age, "age"),
+            column(Column::GasCost, "usd"),
+            self.top.map(|_| String::from("top")),
+            (units != Units::default()).then(|| units.to_string()),
+            (self.display.thresholds != Thresholds::default()).then(|| String::from("thresholds")),
+            self.display
+                .stale_after
+                .is_none()
+                .then(|| String::from("no-stale")),
+            self.latency.as_ref().map(|_| String::from("latency")),
+            self.event_counts_open.then(|| String::from("events")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Returns the height of the info bar: three rows for a line of text and its borders,
+    /// or as many as the groups of the key reference need while it's expanded.
+    pub fn info_bar_height(&self) -> u16 {
+        match self.key_reference_open {
+            true => self.key_reference().len() as u16 + 2,
+            false => 3,
+        }
+    }
+
+    /// Creates the lines of the expanded info bar, the keys bound to the actions grouped by purpose.
+    fn key_reference(&self) -> Vec<Line<'static>> {
+        let group = |name: &str, actions: &[(Action, &str)]| {
+            let keys: Vec<String> = actions
+                .iter()
+                .map(|(action, hint)| format!("[{}] {hint}", self.keys.key(*action)))
+                .collect();
+            Line::from(format!("{name}: {}", keys.join(" | "))).alignment(Alignment::Left)
+        };
+        vec![
+            group(
+                "Navigate",
+                &[
+                    (Action::Up, "up"),
+                    (Action::Down, "down"),
+                    (Action::Goto, "go to rank"),
+                    (Action::Filter, "search"),
+                    (Action::Close, "close"),
+                    (Action::Suspend, "suspend"),
+                ],
+            ),
+            group(
+                "Network",
+                &[
+                    (Action::Details, "details"),
+                    (Action::RawData, "raw data"),
+                    (Action::Metadata, "metadata"),
+                    (Action::ExportHistory, "export history"),
+                    (Action::Explorer, "explorer"),
+                    (Action::Copy, "copy"),
+                ],
+            ),
+            group(
+                "Sort, again to reverse",
+                &[
+                    (Action::SortGps, "gas per second"),
+                    (Action::SortTps, "txs per second"),
+                    (Action::SortDps, "kb per second"),
+                    (Action::SortPending, "pending"),
+                    (Action::SortGasPerTx, "gas per tx"),
+                ],
+            ),
+            group(
+                "View",
+                &[
+                    (Action::EventCounts, "event counts"),
+                    (Action::Units, "units"),
+                    (Action::RateUnit, "rates"),
+                    (Action::Export, "export"),
+                    (Action::Reconnect, "reconnect"),
+                    (Action::KeyReference, "collapse"),
+                    (Action::Quit, "quit"),
+                ],
+            ),
+        ]
+    }
+
+    /// Returns the key hints of the info bar, in order, naming the keys bound to the actions.
+    ///
+    /// The tooltips of the sorting keys name the direction pressing them sorts in, the
+    /// direction the strategy was last used with, or the reverse one for the current strategy.
+    pub fn key_hint_segments(&self) -> Vec<InfoBarSegment> {
+        let segment = |action, short, tooltip: &str| InfoBarSegment {
+            key: self.keys.key(action).to_string(),
+            short,
+            tooltip: tooltip.to_string(),
+        };
+        let sort = |action, short, strategy, metric| {
+            let (verb, direction) = match self.sorting_strategy == strategy {
+                true => ("Reverse the sort", self.sort_direction(strategy).reversed()),
+                false => ("Sort", self.sort_direction(strategy)),
+            };
+            let direction = match direction {
+                SortDirection::Ascending => "ascending",
+                SortDirection::Descending => "descending",
+            };
+            segment(action, short, &format!("{verb} by {metric}, {direction}"))
+        };
+        vec![
+            segment(Action::Quit, "quit", "Quit roller, restoring the terminal"),
+            segment(
+                Action::Explorer,
+                "explorer",
+                "Open the block explorer of the selected network in the browser",
+            ),
+            segment(
+                Action::Copy,
+                "copy",
+                "Copy the selected network to the clipboard, see --copy-field",
+            ),
+            segment(
+                Action::Export,
+                "export",
+                "Export the table to a CSV file in the current directory",
+            ),
+            segment(
+                Action::Details,
+                "details",
+                "Toggle the detail pane of the selected network",
+            ),
+            segment(
+                Action::Filter,
+                "search",
+                "Filter the networks as you type, Enter locks the filter",
+            ),
+            segment(
+                Action::Goto,
+                "go to rank",
+                "Select the network at the typed rank of the table",
+            ),
+            segment(
+                Action::EventCounts,
+                "event counts",
+                "Toggle the counts of the received events on the summary line",
+            ),
+            segment(
+                Action::Units,
+                "units",
+                "Cycle the units of the metrics: in full, abbreviated or with SI prefixes",
+            ),
+            segment(
+                Action::Reconnect,
+                "reconnect",
+                "Reconnect to the event stream, resuming after the last event",
+            ),
+            segment(
+                Action::KeyReference,
+                "all keys",
+                "Expand the info bar into the reference of every key",
+            ),
+            sort(
+                Action::SortGps,
+                "gas per second",
+                SortingStrategy::Gps,
+                "Gas Per Second (GPS)",
+            ),
+            sort(
+                Action::SortTps,
+                "txs per second",
+                SortingStrategy::Tps,
+                "Transactions Per Second (TPS)",
+            ),
+            sort(
+                Action::SortDps,
+                "kb per second",
+                SortingStrategy::Dps,
+                "Data Per Second (DPS)",
+            ),
+            sort(
+                Action::SortPending,
+                "pending",
+                SortingStrategy::PendingTxCount,
+                "pending transactions",
+            ),
+            sort(
+                Action::SortGasPerTx,
+                "gas per tx",
+                SortingStrategy::GasPerTx,
+                "average gas per transaction",
+            ),
+        ]
+    }
+
+    /// Creates the hints of the info bar from `key_hint_segments`, the sorting keys grouped last.
+    ///
+    /// ### Returns
+    /// The line of hints, and the segments along with the range of columns they span in it.
+    fn key_hints(&self) -> (String, Vec<(Range<u16>, InfoBarSegment)>) {
+        let hints = self.key_hint_segments();
+        let sorting = hints.len() - SORT_HINTS;
+        let mut line = String::new();
+        let mut segments = Vec::new();
+        for (i, segment) in hints.into_iter().enumerate() {
+            let separator = match i {
+                0 => "",
+                i if i == sorting => " | sort, again to reverse: (",
+                _ => " | ",
+            };
+            line.push_str(separator);
+            let start = line.chars().count() as u16;
+            line.push_str(&segment.label());
+            segments.push((start..line.chars().count() as u16, segment));
+        }
+        line.push(')');
+        (line, segments)
+    }
+
+    /// Returns the key hint under a position of the screen, while the info bar shows the hints.
+    ///
+    /// ### Arguments
+    /// * `column` - The column of the position.
+    /// * `row` - The row of the position.
+    ///
+    /// ### Returns
+    /// The segment of the hint along with its area on screen, or `None` outside of any hint.
+    fn key_hint_at(&self, column: u16, row: u16) -> Option<(Rect, InfoBarSegment)> {
+        let showing = self.status.is_none()
+            && self.goto_input.is_none()
+            && self.filter_query.is_none()
+            && !self.key_reference_open;
+        // Inside the borders, on the single line of the hints
+        let inner = self.info_area.inner(&Margin::new(1, 1));
+        if !showing || row != inner.y || inner.width == 0 {
+            return None;
+        }
+
+        // Laid out like the centered `Paragraph`, a line too wide starts at the left border
+        let (line, segments) = self.key_hints();
+        let width = line.chars().count() as u16;
+        let x = inner.x + (inner.width / 2).saturating_sub(width / 2);
+        segments.into_iter().find_map(|(range, segment)| {
+            let area =
+                Rect::new(x + range.start, row, range.end - range.start, 1).intersection(inner);
+            (column >= area.x && column < area.right()).then_some((area, segment))
+        })
+    }
+
+    /// Renders the description of a key hint in a floating box above it.
+    ///
+    /// ### Arguments
+    /// * `f` - The frame to render into.
+    /// * `area` - The area of the hint on screen.
+    /// * `segment` - The hovered hint.
+    fn render_tooltip(&self, f: &mut Frame, area: Rect, segment: &InfoBarSegment) {
+        let screen = f.size();
+        let width = (segment.tooltip.chars().count() as u16 + 4).min(screen.width);
+        // Above the info bar, its top border included
+        let y = area.y.saturating_sub(4);
+        let x = area.x.min(screen.right().saturating_sub(width));
+        let tooltip_area = Rect::new(x, y, width, 3).intersection(screen);
+
+        let tooltip = Paragraph::new(segment.tooltip.as_str())
+            .alignment(Alignment::Center)
+            .block(self.block().padding(Padding::horizontal(1)));
+        f.render_widget(Clear, tooltip_area);
+        f.render_widget(tooltip, tooltip_area);
+    }
+
+    /// Creates the info bar line of the `/` search, with the query styled by whether it's locked.
+    fn filter_line(&self, query: &str) -> Line<'static> {
+        let theme = &self.display.theme;
+        match self.filter_locked {
+            true => Line::from(vec![
+                Span::raw("Locked filter: "),
+                Span::styled(query.to_string(), theme.filter_locked),
+                Span::raw(" | [Esc] clear"),
+            ]),
+            false => Line::from(vec![
+                Span::raw("Filter: "),
+                Span::styled(query.to_string(), theme.filter),
+                Span::raw(format!(
+                    " | [Esc] clear | [Enter] lock | {} matches",
+                    self.visible_networks().len()
+                )),
+            ]),
+        }
+    }
+}
+
+/// Compares two networks by the metric of the strategy, in the given direction.
+fn compare(
+    a: &Network,
+    b: &Network,
+    strategy: SortingStrategy,
+    direction: SortDirection,
+) -> Ordering {
+    let default = Data::default();
+    let a_data = a.data.as_ref().unwrap_or(&default);
+    let b_data = b.data.as_ref().unwrap_or(&default);
+
+    let (a, b) = (strategy.metric(a_data), strategy.metric(b_data));
+
+    let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+    match direction {
+        SortDirection::Ascending => ordering,
+        SortDirection::Descending => ordering.reverse(),
+    }
+}
+
+/// Returns the networks among the first `top` whose name or label contains the query, ignoring case.
+fn filter<'a>(
+    networks: Vec<&'a Network>,
+    top: Option<usize>,
+    query: Option<&str>,
+) -> Vec<&'a Network> {
+    let query = query.unwrap_or_default().to_lowercase();
+    networks
+        .into_iter()
+        .take(top.unwrap_or(usize::MAX))
+        .filter(|n| {
+            n.name.to_lowercase().contains(&query) || n.label.to_lowercase().contains(&query)
+        })
+        .collect()
+}
+
+/// Returns a rectangle centered in the given area, sized as a percentage of it.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::vertical([Constraint::Percentage(percent_y)])
+        .flex(layout::Flex::Center)
+        .split(area);
+    Layout::horizontal([Constraint::Percentage(percent_x)])
+        .flex(layout::Flex::Center)
+        .split(vertical[0])[0]
 }
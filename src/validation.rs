@@ -0,0 +1,211 @@
+use crate::{output::OutputFormat, Column, Config, Network};
+use clap::parser::ValueSource;
+use std::fmt;
+
+/// How serious a problem of the configuration is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The configuration works, but likely not as intended.
+    Warning,
+    /// The configuration can't work, roller exits before connecting.
+    Error,
+}
+
+/// A problem found in the configuration by `Config::validate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// What's wrong, and how to fix it when it isn't obvious.
+    pub message: String,
+    /// Where the offending values were taken from, e.g. "--top" or "ROLLER_CONFIG".
+    pub sources: Vec<String>,
+}
+
+impl Problem {
+    fn warning(message: String, sources: Vec<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message,
+            sources,
+        }
+    }
+
+    fn error(message: String, sources: Vec<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message,
+            sources,
+        }
+    }
+
+    /// Checks whether the problem prevents roller from running.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity}: {}", self.message)?;
+        if !self.sources.is_empty() {
+            write!(f, " (from {})", self.sources.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Checks the combinations of options that parse on their own but can't work together,
+    /// or that silently have no effect.
+    ///
+    /// Every problem is collected rather than stopping at the first one, so they can all be
+    /// fixed at once. Runs before any request, see `filters_check` for the checks requiring
+    /// the metadata.
+    ///
+    /// ### Returns
+    /// The problems found, errors and warnings alike, empty for a valid configuration.
+    pub fn validate(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+
+        let include = match self.networks.is_empty() {
+            true => "include",
+            false => "networks",
+        };
+        let excluded: Vec<&str> = self
+            .watchlist()
+            .iter()
+            .filter(|name| self.exclude.contains(name))
+            .map(String::as_str)
+            .collect();
+        if !excluded.is_empty() {
+            problems.push(Problem::error(
+                format!(
+                    "networks both included and excluded are never displayed: {}",
+                    excluded.join(", ")
+                ),
+                vec![self.source(include), self.source("exclude")],
+            ));
+        }
+
+        let sort_column = self.sort.column();
+        let columns = self.columns();
+        let displayed =
+            columns.contains(&sort_column) || (sort_column == Column::Pending && self.pending_txs);
+        if self.top > 0 && !displayed {
+            problems.push(Problem::warning(
+                format!(
+                    "--top keeps the {} busiest networks by {}, which isn't one of the displayed --columns",
+                    self.top,
+                    sort_column.header()
+                ),
+                vec![
+                    self.source("top"),
+                    self.source("columns"),
+                    self.source("sort"),
+                ],
+            ));
+        }
+
+        if self.follow && self.format == OutputFormat::Table {
+            problems.push(Problem::error(
+                String::from("--follow requires a machine-readable --format, such as json or csv"),
+                vec![self.source("follow"), self.source("format")],
+            ));
+        }
+
+        let pending_displayed = self.pending_txs || columns.contains(&Column::Pending);
+        if self.is_set("pending_warn") && !pending_displayed {
+            problems.push(Problem::warning(
+                String::from(
+                    "--pending-warn has no effect without the Pending column, add --pending-txs",
+                ),
+                vec![self.source("pending_warn"), self.source("columns")],
+            ));
+        }
+
+        let unused = |dependency: &str, options: &[&str]| {
+            let set: Vec<String> = options
+                .iter()
+                .filter(|id| self.is_set(id))
+                .map(|id| self.source(id))
+                .collect();
+            (!set.is_empty()).then(|| {
+                Problem::warning(
+                    format!(
+                        "{} {} no effect without --{dependency}",
+                        set.join(" and "),
+                        if set.len() == 1 { "has" } else { "have" }
+                    ),
+                    set.clone(),
+                )
+            })
+        };
+        if self.snapshot_dir.is_none() {
+            problems.extend(unused(
+                "snapshot-dir",
+                &["snapshot_interval_secs", "snapshot_max_files"],
+            ));
+        }
+        if self.log_file.is_none() {
+            problems.extend(unused("log-file", &["log_level", "log_format"]));
+        }
+
+        let token_flag = self.sources.get("auth_token") == Some(&ValueSource::CommandLine);
+        if token_flag && self.auth_token_file.is_some() {
+            problems.push(Problem::error(
+                String::from(
+                    "the token is set twice, keep either --auth-token or --auth-token-file",
+                ),
+                vec![self.source("auth_token"), self.source("auth_token_file")],
+            ));
+        }
+
+        if self.connect_timeout > self.request_timeout {
+            problems.push(Problem::warning(
+                format!(
+                    "--connect-timeout {} is longer than --request-timeout {}, which cuts it short",
+                    humantime::format_duration(self.connect_timeout),
+                    humantime::format_duration(self.request_timeout)
+                ),
+                vec![
+                    self.source("connect_timeout"),
+                    self.source("request_timeout"),
+                ],
+            ));
+        }
+
+        problems
+    }
+
+    /// Checks whether the filters leave any network of the metadata to display.
+    ///
+    /// ### Arguments
+    /// * `networks` - The networks of the metadata, before filtering.
+    ///
+    /// ### Returns
+    /// A warning naming the filters in use if they exclude every network, since the table
+    /// would stay empty.
+    pub fn filters_check(&self, networks: &[Network]) -> Option<Problem> {
+        if networks.is_empty() || networks.iter().any(|n| self.is_included(n)) {
+            return None;
+        }
+
+        let filters = ["include", "networks", "exclude", "layer"]
+            .into_iter()
+            .filter(|id| self.is_set(id))
+            .map(|id| self.source(id))
+            .collect();
+        Some(Problem::warning(
+            format!(
+                "the filters exclude all {} networks of the metadata",
+                networks.len()
+            ),
+            filters,
+        ))
+    }
+}
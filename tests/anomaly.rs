@@ -0,0 +1,98 @@
+mod common;
+
+use clap::Parser;
+use ratatui::{backend::TestBackend, Terminal};
+use roller::{
+    anomaly::{self, ANOMALY_MARKER, HISTORY_LEN},
+    Config, Data, SortingStrategy,
+};
+
+fn data(tps: f32) -> Option<Data> {
+    Some(Data {
+        tps,
+        gps: 1.0,
+        dps: 1.0,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn z_score_requires_a_varying_window() {
+    assert_eq!(anomaly::mean_stddev(&[]), None);
+    assert_eq!(
+        anomaly::mean_stddev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]),
+        Some((5.0, 2.0))
+    );
+
+    let history = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    assert_eq!(anomaly::z_score(&history, 11.0), Some(3.0));
+    assert_eq!(anomaly::z_score(&history, 1.0), Some(-2.0));
+    assert_eq!(anomaly::z_score(&history[..4], 11.0), None);
+    assert_eq!(anomaly::z_score(&[1.0; 10], 100.0), None);
+}
+
+#[test]
+fn spikes_and_drops_are_anomalous() {
+    let mut tui = common::tui();
+    let base = tui.network_mut("base").unwrap();
+    for i in 0..HISTORY_LEN + 5 {
+        base.update_data(data(10.0 + (i % 2) as f32));
+    }
+    assert_eq!(base.history.len(), HISTORY_LEN);
+    assert!(!base.is_anomalous(Some(3.0)));
+
+    base.update_data(data(20.0));
+    assert!(base.z_score(SortingStrategy::Tps).unwrap() > 3.0);
+    assert!(base.is_anomalous(Some(3.0)));
+    assert!(!base.is_anomalous(None));
+
+    base.update_data(data(0.0));
+    assert!(base.is_anomalous(Some(3.0)));
+}
+
+#[test]
+fn anomalous_networks_are_marked() {
+    let mut tui = common::tui();
+    let base = tui.network_mut("base").unwrap();
+    for i in 0..10 {
+        base.update_data(data(10.0 + (i % 2) as f32));
+    }
+    base.update_data(data(50.0));
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert_eq!(screen.matches(ANOMALY_MARKER).count(), 1);
+
+    let config = Config::try_parse_from(["roller", "--anomaly-z", "0"]).unwrap();
+    let mut tui = tui.with_config(&config);
+    terminal.draw(|f| tui.render(f)).unwrap();
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(!screen.contains(ANOMALY_MARKER));
+}
+
+#[test]
+fn anomaly_threshold_is_configurable() {
+    let config = Config::try_parse_from(["roller"]).unwrap();
+    assert_eq!(config.anomaly_z(), Some(3.0));
+    let config = Config::try_parse_from(["roller", "--anomaly-z", "2.5"]).unwrap();
+    assert_eq!(config.anomaly_z(), Some(2.5));
+    assert_eq!(
+        common::tui().with_config(&config).display.anomaly_z,
+        Some(2.5)
+    );
+    assert!(Config::try_parse_from(["roller", "--anomaly-z", "-1"]).is_err());
+    assert!(Config::try_parse_from(["roller", "--anomaly-z", "NaN"]).is_err());
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use clap::Parser;
+use common::MockServer;
+use ratatui::{backend::TestBackend, Terminal};
+use roller::{chain_emoji, ChainRegistry, Config, Section};
+use std::process::Command;
+
+/// Writes a chains file to the temporary directory, unique to the test process and the given name.
+fn chains_file(name: &str, contents: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("roller-{}-{name}", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn file_mappings_override_the_builtins() {
+    let path = chains_file(
+        "override.toml",
+        "# L1\n1 = \"mainnet\"\n\n10 = \"optimism\"\n",
+    );
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = common::roller(
+        &server,
+        &[
+            "--once",
+            "--columns",
+            "network,settlement",
+            "--chains",
+            path.to_str().unwrap(),
+        ],
+    );
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "Network  Settlement\nBase     mainnet\nXai      arbitrum\nZora     mainnet\n"
+    );
+}
+
+#[test]
+fn chains_file_is_read_from_the_environment() {
+    let path = chains_file("env.toml", "42161 = \"arb1\"\n");
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = Command::new(env!("CARGO_BIN_EXE_roller"))
+        .args([
+            "--api-endpoint",
+            &server.url,
+            "--once",
+            "--columns",
+            "network,settlement",
+        ])
+        .env("ROLLER_CHAINS", &path)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Xai      arb1"), "{stdout}");
+}
+
+#[test]
+fn invalid_mappings_name_the_line_and_id() {
+    let mut registry = ChainRegistry::default();
+    let error = registry
+        .extend_from_str("1 = \"mainnet\"\nten = \"optimism\"\n")
+        .unwrap_err();
+    assert_eq!(error, "2: invalid chain id `ten`");
+
+    let error = registry.extend_from_str("\n\n10 = optimism\n").unwrap_err();
+    assert!(
+        error.starts_with("3: ") && error.contains("chain id 10"),
+        "{error}"
+    );
+
+    // Invalid files fail at startup, before any request.
+    let path = chains_file("invalid.toml", "10 optimism\n");
+    let server = MockServer::start("null", vec![]);
+    let output = common::roller(&server, &["--once", "--chains", path.to_str().unwrap()]);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("invalid.toml:1: expected"), "{stderr}");
+}
+
+#[test]
+fn known_chains_have_an_emoji() {
+    for (chain, emoji) in [
+        ("ethereum", "⟠"),
+        ("base", "🔵"),
+        ("arbitrum", "🔴"),
+        ("polygon", "🟣"),
+        ("Ethereum", "⟠"),
+    ] {
+        assert_eq!(chain_emoji(chain), emoji);
+    }
+    assert_eq!(chain_emoji("chain 7777777"), "");
+    assert_eq!(chain_emoji(""), "");
+}
+
+#[test]
+fn settlement_cells_are_prefixed_with_the_emoji() {
+    let settlement = |args: &[&str]| {
+        let config = Config::try_parse_from(
+            ["roller", "--columns", "network,settlement"]
+                .iter()
+                .chain(args),
+        )
+        .unwrap();
+        let mut tui = common::tui().with_config(&config);
+        let mut terminal = Terminal::new(TestBackend::new(200, 12)).unwrap();
+        terminal.draw(|f| tui.render(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..12)
+            .map(|y| {
+                (0..60)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let screen = settlement(&[]);
+    assert!(screen.contains("⟠ ethereum"), "{screen}");
+    assert!(screen.contains('🔴'), "{screen}");
+    let screen = settlement(&["--no-emoji"]);
+    assert!(
+        !screen.contains('⟠') && screen.contains("ethereum"),
+        "{screen}"
+    );
+
+    // The plain-text output is left for scripts.
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = common::roller(&server, &["--once", "--columns", "settlement"]);
+    assert!(!String::from_utf8(output.stdout).unwrap().contains('⟠'));
+}
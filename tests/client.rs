@@ -0,0 +1,291 @@
+mod common;
+
+use clap::Parser;
+use common::MockServer;
+use roller::{
+    parse_endpoint, parse_header, ChainRegistry, Client, ClientSettings, Config, RollerError,
+};
+use std::time::Duration;
+
+#[test]
+fn client_defaults_match_settings() {
+    let config = Config::try_parse_from(["roller"]).unwrap();
+    let client = config.client().unwrap();
+
+    assert_eq!(client.settings(), &ClientSettings::default());
+}
+
+#[test]
+fn client_settings_are_parsed_and_propagated() {
+    let config = Config::try_parse_from([
+        "roller",
+        "--connect-timeout",
+        "500ms",
+        "--request-timeout",
+        "1m 30s",
+        "--retries",
+        "0",
+        "--retry-delay",
+        "250ms",
+    ])
+    .unwrap();
+    let client = config.client().unwrap();
+
+    assert_eq!(
+        client.settings(),
+        &ClientSettings {
+            connect_timeout: Duration::from_millis(500),
+            request_timeout: Duration::from_secs(90),
+            retries: 0,
+            retry_delay: Duration::from_millis(250),
+        }
+    );
+}
+
+#[test]
+fn zero_timeouts_are_rejected() {
+    for flag in ["--connect-timeout", "--request-timeout"] {
+        let result = Config::try_parse_from(["roller", flag, "0s"]);
+        assert!(result.is_err(), "{flag} accepted a zero timeout");
+    }
+}
+
+#[test]
+fn invalid_durations_are_rejected() {
+    let result = Config::try_parse_from(["roller", "--retry-delay", "soon"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn endpoint_trailing_slashes_are_removed() {
+    let endpoint = parse_endpoint("https://api.example.com/v1//").unwrap();
+
+    assert_eq!(endpoint.url, "https://api.example.com/v1");
+    assert_eq!(endpoint.notice, None);
+}
+
+#[test]
+fn endpoint_without_scheme_assumes_https() {
+    let endpoint = parse_endpoint("api.example.com").unwrap();
+
+    assert_eq!(endpoint.url, "https://api.example.com");
+    assert!(endpoint.notice.unwrap().contains("https://api.example.com"));
+}
+
+#[test]
+fn invalid_endpoints_are_rejected() {
+    let error = parse_endpoint("ftp://api.example.com").unwrap_err();
+    assert!(error.contains("`ftp` is not supported"), "{error}");
+
+    let error = parse_endpoint("https://api.example .com").unwrap_err();
+    assert!(error.contains("whitespace"), "{error}");
+
+    let config = Config::try_parse_from(["roller", "--api-endpoint", "http://"]).unwrap();
+    assert!(config.client().is_err());
+}
+
+/// A client of the server retrying quickly, to keep the tests fast.
+fn retrying_client(server: &MockServer, retries: u32) -> Client {
+    Client::builder(server.url.as_str())
+        .retries(retries)
+        .retry_delay(Duration::from_millis(10))
+        .build()
+        .unwrap()
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn metadata_requests_are_retried_on_server_errors() {
+    let server = MockServer::start(&common::metadata(), vec![]);
+    server.fail_metadata_requests(2);
+
+    let networks = retrying_client(&server, 3)
+        .get_networks(&ChainRegistry::default())
+        .await
+        .unwrap();
+    assert_eq!(networks.len(), 3);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn exhausted_retries_report_the_final_status() {
+    let server = MockServer::start(&common::metadata(), vec![]);
+    server.fail_metadata_requests(3);
+
+    let error = retrying_client(&server, 2)
+        .get_networks(&ChainRegistry::default())
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<RollerError>(),
+        Some(&RollerError::HttpError {
+            endpoint: format!("{}/networkMetadata", server.url),
+            status: 503,
+        })
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn endpoints_fall_back_in_order() {
+    let failing = MockServer::start(&common::metadata(), vec![]);
+    failing.fail_metadata_requests(usize::MAX);
+    let server = MockServer::start(&common::metadata(), vec![]);
+
+    let client = Client::builder(failing.url.as_str())
+        .fallback(server.url.as_str())
+        .retries(0)
+        .build()
+        .unwrap();
+    assert_eq!(client.active_endpoint(), failing.url);
+
+    let networks = client
+        .get_networks(&ChainRegistry::default())
+        .await
+        .unwrap();
+    assert_eq!(networks.len(), 3);
+    assert_eq!(client.active_endpoint(), server.url);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn exhausted_fallbacks_report_the_last_error() {
+    let failing = MockServer::start(&common::metadata(), vec![]);
+    failing.fail_metadata_requests(usize::MAX);
+    let client = Client::builder("http://127.0.0.1:9")
+        .fallback(failing.url.as_str())
+        .retries(0)
+        .build()
+        .unwrap();
+
+    let error = client.get_metadata().await.unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<RollerError>(),
+        Some(&RollerError::HttpError {
+            endpoint: format!("{}/networkMetadata", failing.url),
+            status: 503,
+        })
+    );
+}
+
+#[test]
+fn the_api_version_is_checked_on_the_endpoint_serving_the_metadata() {
+    let failing = MockServer::start(&common::metadata(), vec![]);
+    failing.fail_metadata_requests(usize::MAX);
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_roller"))
+        .args(["--once", "--retries", "0"])
+        .args(["--api-endpoint", &failing.url])
+        .args(["--api-endpoint", &server.url])
+        .env_remove("ROLLER_API_ENDPOINT")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(failing.requests(), ["/networkMetadata"]);
+    assert!(
+        server.requests().contains(&String::from("/health")),
+        "{:?}",
+        server.requests()
+    );
+}
+
+#[test]
+fn the_stream_uses_the_endpoint_serving_the_metadata() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_roller"))
+        .args(["--check", "--retries", "0"])
+        .args(["--api-endpoint", "http://127.0.0.1:9"])
+        .args(["--api-endpoint", &server.url])
+        .env_remove("ROLLER_API_ENDPOINT")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with(&format!("endpoint: {}\n", server.url)),
+        "{stdout}"
+    );
+    assert!(stdout.contains("stream: ok"), "{stdout}");
+}
+
+#[test]
+fn headers_are_parsed() {
+    let header = parse_header("X-Api-Key:  secret ").unwrap();
+    assert_eq!(header.name, "x-api-key");
+    assert_eq!(header.value, "secret");
+    assert!(header.value.is_sensitive());
+    assert_eq!(parse_header("x-empty:").unwrap().value, "");
+
+    assert_eq!(
+        parse_header("X-Api-Key secret").unwrap_err(),
+        "invalid header `X-Api-Key secret`: expected `Name: value`"
+    );
+    assert_eq!(
+        parse_header("X Api(Key): secret").unwrap_err(),
+        "invalid header `X Api(Key): secret`: `X Api(Key)` is not a valid header name"
+    );
+    assert!(parse_header(": secret").is_err());
+    assert!(parse_header("x-api-key: caf\u{e9}").is_err());
+
+    let result = Config::try_parse_from(["roller", "--header", "X-Api-Key"]);
+    assert!(result.unwrap_err().to_string().contains("`X-Api-Key`"));
+}
+
+#[test]
+fn header_values_are_redacted() {
+    let config = Config::try_parse_from([
+        "roller",
+        "--header",
+        "X-Api-Key: secret",
+        "--header",
+        "X-Tenant: team",
+    ])
+    .unwrap();
+    assert_eq!(config.headers.len(), 2);
+    assert_eq!(config.headers[0].to_string(), "x-api-key: <redacted>");
+
+    let debug = format!("{config:?}");
+    assert!(debug.contains("x-api-key: <redacted>"), "{debug}");
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(json.contains(r#""headers":["x-api-key: <redacted>","x-tenant: <redacted>"]"#));
+    for formatted in [debug, json] {
+        assert!(!formatted.contains("secret") && !formatted.contains("team"));
+    }
+}
+
+#[test]
+fn headers_are_sent_to_every_endpoint() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let path = std::env::temp_dir().join(format!("roller-headers-{}.log", std::process::id()));
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_roller"))
+        .args(["--check", "--api-endpoint", &server.url])
+        .args(["--header", "X-Tenant: team", "--log-file"])
+        .arg(&path)
+        .env("ROLLER_HEADERS", "X-Api-Key: secret\nX-Region: eu")
+        .output()
+        .unwrap();
+    let log = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.status.success(), "{output:?}");
+
+    // The flag takes precedence over the environment variable, like the other options.
+    assert!(server.headers("x-api-key").is_empty());
+    let headers = server.headers("x-tenant");
+    for path in ["/networkMetadata", "/sse"] {
+        assert!(
+            headers.contains(&(path.to_string(), String::from("team"))),
+            "{headers:?}"
+        );
+    }
+    assert!(log.contains("sending custom headers"), "{log}");
+    assert!(!log.contains("team"), "{log}");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_roller"))
+        .args(["--check", "--api-endpoint", &server.url])
+        .env("ROLLER_HEADERS", "X-Api-Key: secret\nX-Region: eu")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(server.headers("x-api-key").len() >= 2);
+    assert!(server
+        .headers("x-region")
+        .contains(&(String::from("/sse"), String::from("eu"))));
+}
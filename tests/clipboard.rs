@@ -0,0 +1,33 @@
+mod common;
+
+use clap::Parser;
+use roller::{clipboard::CopyField, Config};
+
+#[test]
+fn copy_fields_of_a_network() {
+    let mut tui = common::tui();
+    let base = tui.network_mut("base").unwrap();
+    assert_eq!(CopyField::Name.value(base).as_deref(), Some("base"));
+    assert_eq!(CopyField::Label.value(base).as_deref(), Some("Base"));
+    assert_eq!(CopyField::Explorer.value(base), None);
+
+    base.explorer = Some(String::from("https://basescan.org"));
+    assert_eq!(
+        CopyField::Explorer.value(base).as_deref(),
+        Some("https://basescan.org")
+    );
+    let json: serde_json::Value =
+        serde_json::from_str(&CopyField::Json.value(base).unwrap()).unwrap();
+    assert_eq!(json["name"], "base");
+    assert_eq!(json["data"]["blockNumber"], 100);
+}
+
+#[test]
+fn copy_field_is_configurable() {
+    let config = Config::try_parse_from(["roller"]).unwrap();
+    assert_eq!(config.copy_field, CopyField::Name);
+
+    let config = Config::try_parse_from(["roller", "--copy-field", "explorer"]).unwrap();
+    let tui = common::tui().with_config(&config);
+    assert_eq!(tui.copy_field, CopyField::Explorer);
+}
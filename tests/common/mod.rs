@@ -0,0 +1,229 @@
+// Shared helpers for the integration tests, not every test file uses all of them.
+#![allow(dead_code)]
+
+use eventsource_client::Event;
+use roller::{ChainRegistry, Network, Tui};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    process::{Command, Output},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+/// A minimal HTTP server mocking the `/networkMetadata` and `/sse` endpoints of the API.
+///
+/// Every connection is served on its own thread. The SSE stream sends the configured events
+/// and then keeps the connection open, like the real API does between updates.
+pub struct MockServer {
+    /// The base URL of the server, to be passed as `--api-endpoint`.
+    pub url: String,
+    routes: Arc<Routes>,
+}
+
+#[derive(Default)]
+struct Routes {
+    metadata: Mutex<String>,
+    metadata_failures: AtomicUsize,
+    events: Vec<(String, String)>,
+    headers: Mutex<Vec<(String, String, String)>>,
+}
+
+impl MockServer {
+    /// Starts a server responding with the provided metadata and SSE events.
+    ///
+    /// ### Arguments
+    /// * `metadata` - The JSON body of the `/networkMetadata` endpoint.
+    /// * `events` - The `(event_type, data)` pairs sent through the `/sse` endpoint.
+    pub fn start(metadata: &str, events: Vec<(String, String)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let routes = Arc::new(Routes {
+            metadata: Mutex::new(metadata.to_string()),
+            metadata_failures: AtomicUsize::new(0),
+            events,
+            headers: Mutex::new(Vec::new()),
+        });
+
+        let server_routes = routes.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let routes = server_routes.clone();
+                thread::spawn(move || serve(stream, &routes));
+            }
+        });
+
+        Self { url, routes }
+    }
+
+    /// Makes the next `count` requests to `/networkMetadata` fail with `503 Service Unavailable`.
+    pub fn fail_metadata_requests(&self, count: usize) {
+        self.routes.metadata_failures.store(count, Ordering::SeqCst);
+    }
+
+    /// Replaces the JSON body of the `/networkMetadata` endpoint for the following requests.
+    pub fn set_metadata(&self, metadata: &str) {
+        *self.routes.metadata.lock().unwrap() = metadata.to_string();
+    }
+
+    /// Returns the paths of the requests received so far, in order.
+    pub fn requests(&self) -> Vec<String> {
+        self.headers("host")
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect()
+    }
+
+    /// Returns the `(path, Authorization header)` pairs of the requests received so far,
+    /// for the requests that had the header.
+    pub fn authorizations(&self) -> Vec<(String, String)> {
+        self.headers("authorization")
+    }
+
+    /// Returns the `(path, value)` pairs of a header in the requests received so far,
+    /// for the requests that had the header.
+    ///
+    /// ### Arguments
+    /// * `name` - The name of the header, case-insensitive.
+    pub fn headers(&self, name: &str) -> Vec<(String, String)> {
+        let headers = self.routes.headers.lock().unwrap();
+        headers
+            .iter()
+            .filter(|(_, header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(path, _, value)| (path.clone(), value.clone()))
+            .collect()
+    }
+}
+
+fn serve(mut stream: TcpStream, routes: &Routes) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+    // Record the request headers, for the tests checking what was sent.
+    let mut line = String::new();
+    while reader.read_line(&mut line).is_ok() && line.trim() != "" {
+        if let Some((name, value)) = line.split_once(':') {
+            let header = (
+                path.to_string(),
+                name.trim().to_string(),
+                value.trim().to_string(),
+            );
+            routes.headers.lock().unwrap().push(header);
+        }
+        line.clear();
+    }
+
+    match path {
+        "/networkMetadata"
+            if routes
+                .metadata_failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok() =>
+        {
+            let _ = write!(
+                stream,
+                "HTTP/1.1 503 Service Unavailable\r\nContent-Type: text/html\r\nContent-Length: 12\r\nConnection: close\r\n\r\n<h1>503</h1>"
+            );
+        }
+        "/networkMetadata" => {
+            let metadata = routes.metadata.lock().unwrap().clone();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                metadata.len(),
+                metadata
+            );
+        }
+        "/sse" => {
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\r\n"
+            );
+            for (event_type, data) in &routes.events {
+                let _ = write!(stream, "event: {event_type}\ndata: {data}\n\n");
+            }
+            let _ = stream.flush();
+            // Keep the stream open until the client goes away.
+            while write!(stream, ":\n\n").and_then(|_| stream.flush()).is_ok() {
+                thread::sleep(Duration::from_millis(100));
+            }
+        }
+        _ => {
+            let _ = write!(
+                stream,
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            );
+        }
+    }
+}
+
+/// The metadata of three networks, keyed by name like the real API.
+pub fn metadata() -> String {
+    r#"{
+        "base": {"name": "base", "label": "Base", "parentChain": "1", "da": "ethereum", "stack": "op"},
+        "zora": {"name": "zora", "label": "Zora", "parentChain": "1", "da": "ethereum", "stack": "op"},
+        "xai": {"name": "xai", "label": "Xai", "parentChain": "42161", "da": "anytrust", "stack": "orbit"}
+    }"#
+    .to_string()
+}
+
+/// Builds an SSE event updating the data of a network.
+pub fn event(name: &str, block_number: u64, tps: f32, gps: f32, dps: f32) -> (String, String) {
+    (
+        name.to_string(),
+        format!(
+            r#"{{"blockNumber": {block_number}, "tps": "{tps}", "gps": "{gps}", "dps": "{dps}"}}"#
+        ),
+    )
+}
+
+/// One event for each of the networks in `metadata()`.
+pub fn events() -> Vec<(String, String)> {
+    vec![
+        event("base", 100, 12.5, 20.25, 3.5),
+        event("zora", 200, 2.5, 1.5, 0.5),
+        event("xai", 300, 7.0, 5.75, 1.25),
+    ]
+}
+
+/// Runs the roller binary against the server with the provided arguments.
+pub fn roller(server: &MockServer, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_roller"))
+        .arg("--api-endpoint")
+        .arg(&server.url)
+        .args(args)
+        .output()
+        .expect("run roller")
+}
+
+/// Builds an SSE `Event` from the `(event_type, data)` pairs used by the server.
+pub fn sse_event((event_type, data): (String, String)) -> Event {
+    Event {
+        event_type,
+        data,
+        id: None,
+        retry: None,
+    }
+}
+
+/// The networks of `metadata()`, without data.
+pub fn networks() -> Vec<Network> {
+    let metadata = serde_json::from_str(&metadata()).unwrap();
+    roller::networks_from_metadata(metadata, &ChainRegistry::default()).unwrap()
+}
+
+/// A Tui with the networks of `metadata()`, updated with `events()`.
+pub fn tui() -> Tui {
+    let mut tui = Tui::new(networks());
+    for event in events() {
+        tui.update_networks(sse_event(event));
+    }
+    tui
+}
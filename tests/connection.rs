@@ -0,0 +1,42 @@
+// The LED colors are compiled out by the `no-color` feature.
+#![cfg(not(feature = "no-color"))]
+
+mod common;
+
+use ratatui::{backend::TestBackend, style::Color, Terminal};
+use roller::{connection_led, ConnectionState, Theme};
+use std::time::Instant;
+
+#[test]
+fn led_reflects_the_connection_state() {
+    let led = |connected, last_event_ms_ago| {
+        connection_led(ConnectionState {
+            connected,
+            last_event_ms_ago,
+        })
+        .style
+        .fg
+    };
+
+    assert_eq!(led(true, 0), Some(Color::Green));
+    assert_eq!(led(true, 5_000), Some(Color::Green));
+    assert_eq!(led(true, 5_001), Some(Color::Yellow));
+    assert_eq!(led(false, 0), Some(Color::Red));
+}
+
+#[test]
+fn led_is_rendered_in_the_top_right_corner_of_the_table() {
+    let mut tui = common::tui();
+    tui.display.theme = Theme::colored();
+    tui.connected = true;
+    tui.last_event = Some(Instant::now());
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+
+    // The table starts below the summary line, the LED leaves the corner of the border visible.
+    let buffer = terminal.backend().buffer();
+    assert_eq!(buffer.get(79, 1).symbol(), "╮");
+    assert_eq!(buffer.get(77, 1).symbol(), "●");
+    assert_eq!(buffer.get(77, 1).fg, Color::Green);
+}
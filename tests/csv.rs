@@ -0,0 +1,123 @@
+mod common;
+
+use clap::{Parser, ValueEnum};
+use common::MockServer;
+use roller::{Column, ColumnPreset, Config};
+use std::{
+    io::{BufRead, BufReader},
+    process::{Command, Stdio},
+};
+
+/// Parses CSV output into its records, including the header.
+fn parse(output: &[u8]) -> Vec<Vec<String>> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(output)
+        .records()
+        .map(|record| record.unwrap().iter().map(String::from).collect())
+        .collect()
+}
+
+#[test]
+fn once_prints_csv_with_quoting_and_full_precision() {
+    let metadata = r#"{
+        "base": {"name": "base", "label": "Base, by Coinbase", "parentChain": "1", "da": "ethereum", "stack": "op"}
+    }"#;
+    let events = vec![common::event("base", 100, 12.345678, 20.25, 3.5)];
+    let server = MockServer::start(metadata, events);
+
+    let output = common::roller(
+        &server,
+        &[
+            "--once",
+            "--format",
+            "csv",
+            "--columns",
+            "network,block,tps,gps",
+        ],
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        parse(&output.stdout),
+        [
+            vec!["Network", "Block", "TPS", "MGas/s"],
+            vec!["Base, by Coinbase", "100", "12.345678", "20.25"],
+        ]
+    );
+}
+
+#[test]
+fn once_respects_the_sort_order() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = common::roller(
+        &server,
+        &[
+            "--once",
+            "--format",
+            "csv",
+            "--columns",
+            "network",
+            "--sort",
+            "tps",
+        ],
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        parse(&output.stdout),
+        [vec!["Network"], vec!["Base"], vec!["Xai"], vec!["Zora"]]
+    );
+}
+
+#[test]
+fn presets_select_their_columns() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = common::roller(
+        &server,
+        &["--once", "--format", "csv", "--preset", "minimal"],
+    );
+
+    assert!(output.status.success());
+    assert_eq!(parse(&output.stdout)[0], ["Network", "TPS", "Block"]);
+
+    let config = Config::try_parse_from(["roller", "--preset", "economic"]).unwrap();
+    assert_eq!(
+        config.columns(),
+        [Column::Network, Column::Gps, Column::GasPerTx]
+    );
+    assert_eq!(ColumnPreset::Full.columns(), Column::value_variants());
+    let config = Config::try_parse_from(["roller"]).unwrap();
+    assert_eq!(config.columns(), Column::DEFAULT);
+    assert!(Config::try_parse_from(["roller", "--preset", "full", "--columns", "tps"]).is_err());
+}
+
+#[test]
+fn follow_appends_a_timestamped_row_per_update() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let mut child = Command::new(env!("CARGO_BIN_EXE_roller"))
+        .args(["--api-endpoint", &server.url, "--format", "csv", "--follow"])
+        .args(["--columns", "network,block"])
+        .stdout(Stdio::piped())
+        // Without a terminal on stderr, the interface isn't drawn along
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let lines: Vec<String> = BufReader::new(child.stdout.take().unwrap())
+        .lines()
+        .take(4)
+        .map(Result::unwrap)
+        .collect();
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    let records = parse(lines.join("\n").as_bytes());
+    assert_eq!(records[0], ["timestamp", "Network", "Block"]);
+    assert_eq!(records[1][1..], ["Base", "100"]);
+    assert_eq!(records[2][1..], ["Zora", "200"]);
+    assert_eq!(records[3][1..], ["Xai", "300"]);
+    for record in &records[1..] {
+        assert!(record[0].parse::<u64>().is_ok());
+    }
+}
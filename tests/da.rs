@@ -0,0 +1,57 @@
+mod common;
+
+#[cfg(not(feature = "no-color"))]
+use ratatui::{backend::TestBackend, style::Color, Terminal};
+#[cfg(not(feature = "no-color"))]
+use roller::Theme;
+use roller::{DaRegistry, Section};
+
+#[test]
+fn known_layers_are_normalized_and_unknown_ones_pass_through() {
+    let mut registry = DaRegistry::default();
+    assert_eq!(registry.name("ethereum"), "Ethereum");
+    assert_eq!(registry.name("Blobs"), "Ethereum (blob)");
+    assert_eq!(registry.name("eigenda"), "EigenDA");
+    assert_eq!(registry.name("nearda"), "nearda");
+
+    registry
+        .extend_from_str(
+            "[keys]\nquit = \"x\"\n\n[da]\n\"near da\" = \"NEAR\"\nanytrust = \"Arbitrum AnyTrust\"\n",
+        )
+        .unwrap();
+    assert_eq!(registry.name("NEAR DA"), "NEAR");
+    assert_eq!(registry.name("anytrust"), "Arbitrum AnyTrust");
+
+    assert_eq!(
+        registry.extend_from_str("[da]\ncelestia = Celestia\n"),
+        Err(String::from(
+            "2: the display name of `celestia` must be a non-empty quoted string"
+        ))
+    );
+}
+
+#[test]
+#[cfg(not(feature = "no-color"))]
+fn da_cells_are_colored_by_layer() {
+    let mut tui = common::tui();
+    tui.display.theme = Theme::colored();
+    let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+    let buffer = terminal.backend().buffer();
+
+    // The style of the first cell of a layer, in the row of its network.
+    let fg = |row: &str, name: &str| {
+        let lines: Vec<Vec<&str>> = (0..20)
+            .map(|y| (0..120).map(|x| buffer.get(x, y).symbol()).collect())
+            .collect();
+        let y = lines.iter().position(|l| l.concat().contains(row)).unwrap();
+        let prefix = lines[y].concat();
+        let x = prefix[..prefix.find(name).unwrap()].chars().count();
+        buffer.get(x as u16, y as u16).fg
+    };
+    // Zora, as Base is the selected row.
+    assert_eq!(fg("Zora", "Ethereum"), Color::LightBlue);
+    // Unknown layers keep the style of their row.
+    let unknown = Some(fg("Xai", "AnyTrust"));
+    assert!(Theme::colored().da_layers.iter().all(|s| s.fg != unknown));
+}
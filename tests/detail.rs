@@ -0,0 +1,209 @@
+mod common;
+
+use crossterm::event::KeyCode;
+use ratatui::{backend::TestBackend, Terminal};
+
+/// Renders the Tui and returns the lines of the buffer.
+fn lines(tui: &mut roller::Tui) -> Vec<String> {
+    lines_at(tui, 40)
+}
+
+/// Renders the Tui on a terminal of the given height and returns the lines of the buffer.
+fn lines_at(tui: &mut roller::Tui, height: u16) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(120, height)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..height)
+        .map(|y| (0..120).map(|x| buffer.get(x, y).symbol()).collect())
+        .collect()
+}
+
+#[test]
+fn raw_data_section_pretty_prints_the_last_payload() {
+    let mut tui = common::tui();
+    tui.handle_input(KeyCode::Enter);
+    assert!(lines(&mut tui).iter().any(|l| l.contains("▸ Raw Data")));
+    assert!(!lines(&mut tui).iter().any(|l| l.contains("blockNumber")));
+
+    tui.handle_input(KeyCode::Char('d'));
+    assert!(tui.raw_open);
+    let lines = lines(&mut tui);
+    assert!(lines.iter().any(|l| l.contains("▾ Raw Data")));
+    assert!(
+        lines.iter().any(|l| l.contains(r#"  "blockNumber": 100,"#)),
+        "{lines:#?}"
+    );
+    assert_eq!(
+        tui.networks()[0].last_raw_json.as_deref(),
+        Some(r#"{"blockNumber": 100, "tps": "12.5", "gps": "20.25", "dps": "3.5"}"#)
+    );
+}
+
+#[test]
+fn arrows_scroll_the_expanded_raw_data() {
+    let mut tui = common::tui();
+    tui.handle_input(KeyCode::Enter);
+    tui.handle_input(KeyCode::Char('d'));
+
+    // The payload spans 6 lines, the scroll stops at the last one and the selection stays.
+    for _ in 0..10 {
+        tui.handle_input(KeyCode::Down);
+    }
+    assert_eq!(tui.raw_scroll, 5);
+    assert_eq!(tui.selected.selected(), Some(0));
+    assert!(lines(&mut tui).iter().any(|l| l.contains('}')));
+    tui.handle_input(KeyCode::Up);
+    assert_eq!(tui.raw_scroll, 4);
+
+    // Collapsed, the arrows move the selection again.
+    tui.handle_input(KeyCode::Char('d'));
+    assert_eq!(tui.raw_scroll, 0);
+    tui.handle_input(KeyCode::Down);
+    assert_eq!(tui.selected.selected(), Some(1));
+}
+
+#[test]
+fn history_table_pages_through_the_past_updates() {
+    let mut tui = common::tui();
+    let base = tui.network_mut("base").unwrap();
+    for block in 101..=125 {
+        base.update_data(Some(roller::Data {
+            block_number: block,
+            tps: 1.0,
+            ..Default::default()
+        }));
+    }
+    // The data of block 125 is current, the 25 updates since block 100 are history.
+    assert_eq!(base.history.len(), 25);
+    tui.handle_input(KeyCode::Enter);
+
+    // The first page lists the most recent updates, as many as fit in the pane.
+    let page = lines(&mut tui);
+    assert!(page.iter().any(|l| l.contains("History, page 1/5")));
+    assert!(page.iter().any(|l| l.contains("│-1  124 ")), "{page:#?}");
+    assert!(page.iter().any(|l| l.contains("│-5  120 ")));
+    assert!(!page.iter().any(|l| l.contains("│-6 ")));
+
+    // Paging stops at the last page, which holds the oldest updates.
+    for _ in 0..6 {
+        tui.handle_input(KeyCode::PageDown);
+    }
+    assert_eq!(tui.history_page, 4);
+    let page = lines(&mut tui);
+    assert!(page.iter().any(|l| l.contains("History, page 5/5")));
+    assert!(page.iter().any(|l| l.contains("│-21 104 ")));
+    assert!(page.iter().any(|l| l.contains("│-25 100 ")));
+    assert!(!page.iter().any(|l| l.contains("│-20 ")));
+    for _ in 0..4 {
+        tui.handle_input(KeyCode::PageUp);
+    }
+    assert_eq!(tui.history_page, 0);
+
+    // Closing the detail pane starts over, and without it the keys do nothing.
+    tui.handle_input(KeyCode::PageDown);
+    tui.handle_input(KeyCode::Enter);
+    tui.handle_input(KeyCode::PageDown);
+    tui.handle_input(KeyCode::Enter);
+    assert_eq!(tui.history_page, 0);
+
+    tui.handle_input(KeyCode::Down);
+    assert!(lines(&mut tui)
+        .iter()
+        .any(|l| l.contains("No past updates yet")));
+}
+
+#[test]
+fn history_pages_reach_every_update_of_a_short_pane() {
+    let mut tui = common::tui();
+    let base = tui.network_mut("base").unwrap();
+    for block in 101..=125 {
+        base.update_data(Some(roller::Data {
+            block_number: block,
+            ..Default::default()
+        }));
+    }
+    tui.handle_input(KeyCode::Enter);
+
+    // Every page lists the updates following the previous one, none of them clipped.
+    let mut listed = Vec::new();
+    loop {
+        let page = lines_at(&mut tui, 24);
+        listed.extend(page.iter().filter_map(|l| {
+            let (_, row) = l.split_once("│-")?;
+            row.split(' ').next()?.parse::<usize>().ok()
+        }));
+        let previous = tui.history_page;
+        tui.handle_input(KeyCode::PageDown);
+        if tui.history_page == previous {
+            break;
+        }
+    }
+    assert_eq!(listed, (1..=25).collect::<Vec<_>>());
+}
+
+#[test]
+fn detail_pane_follows_the_rate_unit() {
+    let mut tui = common::tui();
+    let base = tui.network_mut("base").unwrap();
+    base.update_data(Some(roller::Data {
+        block_number: 101,
+        tps: 1.0,
+        ..Default::default()
+    }));
+    tui.handle_input(KeyCode::Enter);
+    let page = lines(&mut tui);
+    assert!(page.iter().any(|l| l.contains("TPS: 1.00")), "{page:#?}");
+    assert!(page.iter().any(|l| l.contains("│#  Block")));
+
+    // Per minute, the stats and the history are converted and relabeled.
+    tui.handle_input(KeyCode::Char('m'));
+    let page = lines(&mut tui);
+    assert!(page.iter().any(|l| l.contains("TPM: 60.00")), "{page:#?}");
+    assert!(page.iter().any(|l| l.contains("MGas/min: 0.00")));
+    assert!(page.iter().any(|l| l.contains("KB/min: 0.00")));
+    let header = page.iter().find(|l| l.contains("│#  Block")).unwrap();
+    assert!(
+        header.contains("TPM") && header.contains("KB/min"),
+        "{header}"
+    );
+    assert!(
+        page.iter().any(|l| l.contains("│-1 100          750.00")),
+        "{page:#?}"
+    );
+}
+
+#[test]
+fn metadata_section_pretty_prints_the_network() {
+    let mut tui = common::tui();
+    tui.handle_input(KeyCode::Enter);
+    tui.handle_input(KeyCode::Char('d'));
+    assert!(lines(&mut tui).iter().any(|l| l.contains("▸ Metadata")));
+
+    // Expanding the metadata collapses the raw data, they share the space below the stats.
+    tui.handle_input(KeyCode::Char('M'));
+    assert!(tui.metadata_open && !tui.raw_open);
+    let lines = lines_at(&mut tui, 60);
+    assert!(lines.iter().any(|l| l.contains("▾ Metadata")));
+    assert!(lines.iter().any(|l| l.contains("▸ Raw Data")));
+    for field in [
+        r#""name": "base","#,
+        r#""label": "Base","#,
+        r#""stack": "op","#,
+    ] {
+        assert!(
+            lines.iter().any(|l| l.contains(field)),
+            "{field}: {lines:#?}"
+        );
+    }
+    assert!(!lines.iter().any(|l| l.contains(r#""data""#)));
+
+    // The arrows scroll it up to its last line.
+    for _ in 0..20 {
+        tui.handle_input(KeyCode::Down);
+    }
+    let json = tui.networks()[0].metadata_json().unwrap();
+    assert_eq!(tui.raw_scroll as usize, json.lines().count() - 1);
+    tui.handle_input(KeyCode::Char('d'));
+    assert!(tui.raw_open && !tui.metadata_open);
+    assert_eq!(tui.raw_scroll, 0);
+}
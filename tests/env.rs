@@ -0,0 +1,181 @@
+mod common;
+
+use clap::Parser;
+use common::MockServer;
+use roller::Config;
+use std::process::{Command, Output};
+
+/// Runs `roller --once` with the provided environment variables and arguments.
+fn roller(env: &[(&str, &str)], args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_roller"))
+        .arg("--once")
+        .args(args)
+        .env_remove("ROLLER_API_ENDPOINT")
+        .env_remove("ROLLER_API_TOKEN")
+        .env_remove("ROLLER_AUTH_TOKEN")
+        .envs(env.iter().copied())
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn api_endpoint_is_read_from_the_environment_below_the_flag() {
+    let server = MockServer::start(&common::metadata(), common::events());
+
+    let output = roller(&[("ROLLER_API_ENDPOINT", &server.url)], &[]);
+    assert!(output.status.success(), "{output:?}");
+
+    // The flag takes precedence over an unreachable endpoint in the environment.
+    let output = roller(
+        &[("ROLLER_API_ENDPOINT", "http://127.0.0.1:9")],
+        &["--api-endpoint", &server.url],
+    );
+    assert!(output.status.success(), "{output:?}");
+}
+
+#[test]
+fn auth_token_is_sent_to_every_endpoint() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = roller(
+        &[
+            ("ROLLER_API_ENDPOINT", &server.url),
+            ("ROLLER_API_TOKEN", "secret"),
+        ],
+        &[],
+    );
+    assert!(output.status.success(), "{output:?}");
+
+    let authorizations = server.authorizations();
+    for path in ["/networkMetadata", "/sse"] {
+        assert!(
+            authorizations.contains(&(path.to_string(), String::from("Bearer secret"))),
+            "{authorizations:?}"
+        );
+    }
+
+    // The token never shows up in the help output.
+    let help = Command::new(env!("CARGO_BIN_EXE_roller"))
+        .arg("--help")
+        .env("ROLLER_API_TOKEN", "secret")
+        .output()
+        .unwrap();
+    let help = String::from_utf8(help.stdout).unwrap();
+    assert!(help.contains("ROLLER_API_TOKEN") && !help.contains("secret"));
+}
+
+#[test]
+fn auth_token_sources_follow_their_precedence() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let path = std::env::temp_dir().join(format!("roller-token-{}", std::process::id()));
+    std::fs::write(&path, "from-file\n").unwrap();
+    let file = path.to_str().unwrap();
+    let env = [
+        ("ROLLER_API_ENDPOINT", server.url.as_str()),
+        ("ROLLER_API_TOKEN", "from-env"),
+    ];
+
+    // The file takes precedence over the environment, and the flag over both.
+    let output = roller(&env, &["--auth-token-file", file]);
+    assert!(output.status.success(), "{output:?}");
+    let output = roller(&env, &["--auth-token", "from-flag"]);
+    assert!(output.status.success(), "{output:?}");
+    let tokens: Vec<String> = server
+        .authorizations()
+        .into_iter()
+        .map(|(_, a)| a)
+        .collect();
+    assert!(
+        tokens.contains(&String::from("Bearer from-file")),
+        "{tokens:?}"
+    );
+    assert!(
+        tokens.contains(&String::from("Bearer from-flag")),
+        "{tokens:?}"
+    );
+    assert!(
+        !tokens.contains(&String::from("Bearer from-env")),
+        "{tokens:?}"
+    );
+
+    // Both flags are ambiguous.
+    let output = roller(
+        &env,
+        &["--auth-token", "from-flag", "--auth-token-file", file],
+    );
+    std::fs::remove_file(&path).unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("keep either --auth-token or --auth-token-file"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn deprecated_auth_token_variable_is_read_last() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let legacy = ("ROLLER_AUTH_TOKEN", "from-legacy");
+    let output = roller(&[("ROLLER_API_ENDPOINT", &server.url), legacy], &[]);
+    assert!(output.status.success(), "{output:?}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("ROLLER_AUTH_TOKEN is deprecated, use ROLLER_API_TOKEN instead"),
+        "{stderr}"
+    );
+
+    assert!(server.authorizations().contains(&(
+        String::from("/networkMetadata"),
+        String::from("Bearer from-legacy")
+    )));
+
+    // Any other source takes precedence over it.
+    let server = MockServer::start(&common::metadata(), common::events());
+    let env = [
+        ("ROLLER_API_ENDPOINT", server.url.as_str()),
+        ("ROLLER_API_TOKEN", "from-env"),
+        legacy,
+    ];
+    let output = roller(&env, &[]);
+    assert!(output.status.success(), "{output:?}");
+    let authorizations = server.authorizations();
+    assert!(!authorizations.is_empty());
+    assert!(
+        authorizations.iter().all(|(_, a)| a == "Bearer from-env"),
+        "{authorizations:?}"
+    );
+}
+
+#[test]
+fn unreadable_auth_token_files_are_reported() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = roller(
+        &[("ROLLER_API_ENDPOINT", &server.url)],
+        &["--auth-token-file", "/nonexistent/roller-token"],
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("failed to read --auth-token-file /nonexistent/roller-token"),
+        "{stderr}"
+    );
+    assert!(server.authorizations().is_empty());
+}
+
+#[test]
+fn auth_token_is_redacted_from_the_config() {
+    let config = Config::try_parse_from(["roller", "--auth-token", "secret"]).unwrap();
+    assert_eq!(config.auth_token().unwrap().as_deref(), Some("secret"));
+
+    let debug = format!("{config:?}");
+    assert!(
+        debug.contains("auth_token: Some(Secret(<redacted>))"),
+        "{debug}"
+    );
+    assert!(!debug.contains("secret"), "{debug}");
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(
+        !json.contains("auth_token") && !json.contains("secret"),
+        "{json}"
+    );
+}
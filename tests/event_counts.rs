@@ -0,0 +1,42 @@
+mod common;
+
+use crossterm::event::KeyCode;
+use ratatui::{backend::TestBackend, Terminal};
+use roller::EventCounts;
+
+#[test]
+fn events_are_counted_by_outcome_and_shown_on_demand() {
+    let mut tui = common::tui();
+    tui.update_networks(common::sse_event((
+        String::from("base"),
+        String::from("{\"blockNumber\": "),
+    )));
+    tui.update_networks(common::sse_event(common::event(
+        "optimism", 1, 1.0, 1.0, 1.0,
+    )));
+    assert_eq!(
+        tui.event_counts,
+        EventCounts {
+            parsed: 3,
+            failed: 1,
+            untracked: 1,
+        }
+    );
+
+    let summary = |tui: &mut roller::Tui| {
+        let mut terminal = Terminal::new(TestBackend::new(160, 20)).unwrap();
+        terminal.draw(|f| tui.render(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..160)
+            .map(|x| buffer.get(x, 0).symbol())
+            .collect::<String>()
+    };
+    assert!(!summary(&mut tui).contains("events:"));
+
+    tui.handle_input(KeyCode::Char('v'));
+    assert!(
+        summary(&mut tui).contains("| events: 3 parsed, 1 failed to parse, 1 untracked"),
+        "{}",
+        summary(&mut tui)
+    );
+}
@@ -0,0 +1,210 @@
+mod common;
+
+use clap::Parser;
+use crossterm::event::KeyCode;
+use ratatui::{backend::TestBackend, Terminal};
+use roller::{
+    format::{self, RateUnit, Units},
+    output::{self, OutputFormat},
+    Column, Config, Section,
+};
+
+#[test]
+fn decimals_follow_the_precision() {
+    for (precision, expected) in [(0, "12"), (2, "12.35"), (6, "12.345678")] {
+        assert_eq!(format::decimal(12.345678, precision), expected);
+    }
+    assert_eq!(format::decimal(0.5, 0), "0");
+    assert_eq!(format::decimal(1_500.0, 2), "1500.00");
+}
+
+#[test]
+fn precision_applies_to_the_abbreviated_mantissa() {
+    for (precision, expected) in [(0, "1 MB"), (2, "1.23 MB"), (6, "1.234567 MB")] {
+        assert_eq!(format::si(1_234_567.0, "B", precision), expected);
+    }
+    assert_eq!(format::si(999.0, "Gas", 2), "999.00 Gas");
+}
+
+#[test]
+fn metric_cells_follow_the_precision() {
+    let tui = common::tui();
+    let base = tui.network("base").unwrap();
+
+    for (precision, expected) in [(0, "20"), (2, "20.25"), (6, "20.250000")] {
+        assert_eq!(Column::Gps.value(base, precision, Units::Raw), expected);
+    }
+    // Integer columns aren't affected.
+    assert_eq!(Column::Block.value(base, 6, Units::Raw), "100");
+}
+
+#[test]
+fn precision_is_limited_to_six_decimals() {
+    assert_eq!(Config::try_parse_from(["roller"]).unwrap().precision, 2);
+    assert!(Config::try_parse_from(["roller", "--precision", "6"]).is_ok());
+    assert!(Config::try_parse_from(["roller", "--precision", "7"]).is_err());
+}
+
+#[test]
+fn truncation_appends_an_ellipsis() {
+    assert_eq!(format::truncate_to_width("Base", 4), "Base");
+    assert_eq!(format::truncate_to_width("Arbitrum Nova", 8), "Arbitru…");
+    assert_eq!(format::truncate_to_width("Zōra Network", 5), "Zōra…");
+    assert_eq!(format::truncate_to_width("Xai", 1), "…");
+    assert_eq!(format::truncate_to_width("Xai", 0), "");
+}
+
+#[test]
+fn wrapping_breaks_at_word_boundaries() {
+    assert_eq!(format::wrap_words("Base", 4), ["Base"]);
+    assert_eq!(
+        format::wrap_words("Arbitrum Nova One", 13),
+        ["Arbitrum Nova", "One"]
+    );
+    assert_eq!(
+        format::wrap_words("Zōra Network", 4),
+        ["Zōra", "Netw", "ork"]
+    );
+    assert_eq!(format::wrap_words("", 4), [""]);
+    assert_eq!(format::wrap_words("Xai", 0), ["Xai"]);
+}
+
+#[test]
+fn json_is_pretty_printed_unless_invalid() {
+    assert_eq!(
+        format::pretty_json(r#"{"tps":"1.5"}"#),
+        "{\n  \"tps\": \"1.5\"\n}"
+    );
+    assert_eq!(format::pretty_json("{\"tps\":"), "{\"tps\":");
+}
+
+#[test]
+fn age_bars_grow_over_thirty_seconds() {
+    assert_eq!(format::age_bar(0.0, 8), "█       ");
+    assert_eq!(format::age_bar(15.0, 8), "████    ");
+    assert_eq!(format::age_bar(29.0, 8), "████████");
+    assert_eq!(format::age_bar(120.0, 8), "████████");
+    assert_eq!(format::age_bar(-1.0, 4).chars().count(), 4);
+}
+
+#[test]
+fn age_bar_flag_adds_the_age_column() {
+    let config = Config::try_parse_from(["roller", "--age-bar"]).unwrap();
+    let mut tui = common::tui().with_config(&config);
+    assert_eq!(tui.columns.last(), Some(&Column::Age));
+
+    let base = tui.network_mut("base").unwrap();
+    base.updated_at = None;
+    assert_eq!(Column::Age.value(base, 2, Units::Raw), "-");
+    assert_eq!(Column::Age.raw(base), "");
+    base.updated_at = Some(std::time::Instant::now());
+    assert_eq!(Column::Age.value(base, 2, Units::Raw), "█       ");
+    assert_eq!(Column::Age.raw(base), "0.0");
+}
+
+#[test]
+fn units_write_the_same_value_differently() {
+    for (units, expected) in [
+        (Units::Raw, "4560000000.00"),
+        (Units::Human, "4.56B"),
+        (Units::Si, "4.56G"),
+    ] {
+        assert_eq!(format::number(4_560_000_000.0, 2, units), expected);
+    }
+    // Numbers below a thousand aren't abbreviated.
+    assert_eq!(format::number(999.5, 1, Units::Human), "999.5");
+    assert_eq!(
+        format::quantity(1_234_567.0, "B", 2, Units::Raw),
+        "1234567.00 B"
+    );
+    assert_eq!(
+        format::quantity(1_234_567.0, "B", 2, Units::Human),
+        "1.23 MB"
+    );
+}
+
+#[test]
+fn units_only_affect_the_display() {
+    let mut tui = common::tui();
+    tui.network_mut("base").unwrap().data.as_mut().unwrap().gps = 1_500_000.0;
+    let base = tui.network("base").unwrap();
+    assert_eq!(Column::Gps.value(base, 1, Units::Human), "1.5M");
+    assert_eq!(Column::Gps.value(base, 1, Units::Raw), "1500000.0");
+
+    let networks = [base];
+    let columns = [Column::Network, Column::Gps];
+    for units in [Units::Raw, Units::Human, Units::Si] {
+        let json = output::render(OutputFormat::Json, &networks, &columns, 1, units).unwrap();
+        assert!(json.contains("\"gps\":1500000.0"), "{json}");
+        let csv = output::render(OutputFormat::Csv, &networks, &columns, 1, units).unwrap();
+        assert_eq!(csv, "Network,MGas/s\nBase,1500000\n");
+    }
+    let table = output::render(OutputFormat::Table, &networks, &columns, 1, Units::Si).unwrap();
+    assert_eq!(table, "Network  MGas/s\nBase     1.5M\n");
+}
+
+#[test]
+fn units_are_configurable_and_toggled_with_a_key() {
+    let config = Config::try_parse_from(["roller", "--units", "raw"]).unwrap();
+    let mut tui = common::tui().with_config(&config);
+    assert_eq!(tui.display.units, Units::Raw);
+
+    tui.handle_input(KeyCode::Char('u'));
+    assert_eq!(tui.display.units, Units::Human);
+    assert_eq!(tui.status.as_deref(), Some("Units: human"));
+    tui.handle_input(KeyCode::Char('u'));
+    tui.handle_input(KeyCode::Char('u'));
+    assert_eq!(tui.display.units, Units::Raw);
+}
+
+#[test]
+fn rates_are_toggled_per_minute_with_a_key() {
+    let mut tui = common::tui();
+    let base = tui.network("base").unwrap();
+    assert_eq!(
+        Column::Tps.rate_value(base, 2, Units::Raw, RateUnit::Minute),
+        "750.00"
+    );
+    assert_eq!(
+        Column::Block.rate_value(base, 2, Units::Raw, RateUnit::Minute),
+        "100"
+    );
+    assert_eq!(Column::Gps.rate_header(RateUnit::Minute), "MGas/min");
+    assert_eq!(Column::Pending.rate_header(RateUnit::Minute), "Pending");
+
+    let screen = |tui: &mut roller::Tui| -> String {
+        let mut terminal = Terminal::new(TestBackend::new(160, 20)).unwrap();
+        terminal.draw(|f| tui.render(f)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect()
+    };
+    assert!(screen(&mut tui).contains("TPS"));
+
+    tui.handle_input(KeyCode::Char('m'));
+    assert_eq!(tui.display.rate_unit, RateUnit::Minute);
+    assert_eq!(tui.status.as_deref(), Some("Rates: per minute"));
+    let rows = screen(&mut tui);
+    assert!(rows.contains("TPM") && rows.contains("KB/min"), "{rows}");
+    assert!(rows.contains("750.00"), "{rows}");
+    // The data itself stays per second.
+    assert_eq!(
+        tui.network("base").unwrap().data.as_ref().unwrap().tps,
+        12.5
+    );
+
+    // Relabeled headers are kept as configured.
+    tui.display
+        .headers
+        .extend_from_str("[headers]\ntps = \"Tx\"\n")
+        .unwrap();
+    assert_eq!(tui.display.header(Column::Tps), "Tx");
+    assert_eq!(tui.display.header(Column::Dps), "KB/min");
+
+    tui.handle_input(KeyCode::Char('m'));
+    assert_eq!(tui.display.rate_unit, RateUnit::Second);
+}
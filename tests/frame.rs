@@ -0,0 +1,85 @@
+use clap::Parser;
+use roller::{Config, FrameLimiter};
+use std::time::{Duration, Instant};
+
+#[test]
+fn frames_are_limited_to_the_budget() {
+    let start = Instant::now();
+    let at = |ms| start + Duration::from_millis(ms);
+    let mut frames = FrameLimiter::new(Duration::from_millis(100));
+
+    // The first frame is drawn right away, the next ones once the budget has elapsed.
+    assert!(frames.ready(at(0)));
+    assert!(!frames.ready(at(10)));
+    assert!(!frames.ready(at(99)));
+    assert!(frames.ready(at(100)));
+
+    // Skipped frames don't count towards the budget.
+    assert!(!frames.ready(at(150)));
+    assert!(frames.ready(at(230)));
+    assert!(!frames.ready(at(329)));
+}
+
+#[test]
+fn input_is_polled_more_often_than_frames_are_drawn() {
+    let config = Config::try_parse_from(["roller"]).unwrap();
+    assert_eq!(config.input_poll(), Duration::from_millis(50));
+    assert_eq!(config.frame_budget(), Duration::from_secs(1) / 30);
+    assert_eq!(config.deprecation_warning(), None);
+
+    // Polling input at 10ms while drawing at most 4 frames per second.
+    let config =
+        Config::try_parse_from(["roller", "--input-poll-ms", "10", "--max-fps", "4"]).unwrap();
+    let start = Instant::now();
+    let mut frames = FrameLimiter::new(config.frame_budget());
+    let drawn = (0..100)
+        .map(|i| start + config.input_poll() * i)
+        .filter(|&now| frames.ready(now))
+        .count();
+    assert_eq!(drawn, 4);
+}
+
+#[test]
+fn out_of_range_timings_are_rejected() {
+    for args in [
+        ["--input-poll-ms", "0"],
+        ["--input-poll-ms", "5000"],
+        ["--max-fps", "0"],
+        ["--max-fps", "500"],
+    ] {
+        let result = Config::try_parse_from(["roller", args[0], args[1]]);
+        assert!(result.is_err(), "{args:?} was accepted");
+    }
+}
+
+#[test]
+fn deprecated_interval_maps_onto_the_new_options() {
+    let config = Config::try_parse_from(["roller", "-i", "200"]).unwrap();
+    assert_eq!(config.input_poll(), Duration::from_millis(200));
+    assert_eq!(config.frame_budget(), Duration::from_millis(200));
+    assert_eq!(
+        config.deprecation_warning().unwrap(),
+        "--interval-ms is deprecated, use --input-poll-ms 200 --max-fps 5 instead"
+    );
+
+    // Shorter intervals map onto a frame rate `--max-fps` accepts.
+    let config = Config::try_parse_from(["roller", "-i", "0"]).unwrap();
+    assert_eq!(config.input_poll(), Duration::from_millis(10));
+    assert_eq!(config.frame_budget(), Duration::from_millis(9));
+    let warning = config.deprecation_warning().unwrap();
+    assert_eq!(
+        warning,
+        "--interval-ms is deprecated, use --input-poll-ms 10 --max-fps 111 instead"
+    );
+    let advised = warning
+        .split(", use ")
+        .nth(1)
+        .unwrap()
+        .trim_end_matches(" instead");
+    let args = ["roller"].into_iter().chain(advised.split(' '));
+    assert!(Config::try_parse_from(args).is_ok());
+
+    // It can't be combined with the options replacing it.
+    let result = Config::try_parse_from(["roller", "--interval-ms", "200", "--max-fps", "10"]);
+    assert!(result.is_err());
+}
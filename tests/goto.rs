@@ -0,0 +1,52 @@
+mod common;
+
+use crossterm::event::KeyCode;
+use roller::Tui;
+
+/// Types ':' followed by the rank and presses the final key.
+fn goto(tui: &mut Tui, rank: &str, key: KeyCode) {
+    tui.handle_input(KeyCode::Char(':'));
+    rank.chars()
+        .for_each(|c| tui.handle_input(KeyCode::Char(c)));
+    tui.handle_input(key);
+}
+
+#[test]
+fn enter_selects_the_rank_clamped_to_the_table() {
+    let mut tui = common::tui();
+
+    goto(&mut tui, "2", KeyCode::Enter);
+    assert_eq!(tui.selected.selected(), Some(1));
+    assert_eq!(tui.goto_input, None);
+
+    // Ranks past either end select the first or the last row.
+    goto(&mut tui, "70", KeyCode::Enter);
+    assert_eq!(tui.selected.selected(), Some(2));
+    goto(&mut tui, "0", KeyCode::Enter);
+    assert_eq!(tui.selected.selected(), Some(0));
+    goto(&mut tui, "99999999999999999999999", KeyCode::Enter);
+    assert_eq!(tui.selected.selected(), Some(2));
+
+    // The rank applies to the filtered view.
+    tui.filter_query = Some(String::from("a"));
+    tui.filter_locked = true;
+    goto(&mut tui, "3", KeyCode::Enter);
+    assert_eq!(tui.selected.selected(), Some(2));
+}
+
+#[test]
+fn esc_cancels_and_other_keys_are_ignored() {
+    let mut tui = common::tui();
+
+    // Letters neither enter the rank nor trigger their usual actions.
+    tui.handle_input(KeyCode::Char(':'));
+    "3g".chars()
+        .for_each(|c| tui.handle_input(KeyCode::Char(c)));
+    assert_eq!(tui.goto_input.as_deref(), Some("3"));
+    assert_eq!(tui.sort_preview, None);
+    assert!(tui.is_typing());
+
+    tui.handle_input(KeyCode::Esc);
+    assert_eq!(tui.goto_input, None);
+    assert_eq!(tui.selected.selected(), Some(0));
+}
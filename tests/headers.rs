@@ -0,0 +1,48 @@
+mod common;
+
+use ratatui::{backend::TestBackend, Terminal};
+use roller::{Column, Headers, Section};
+
+#[test]
+fn labels_fall_back_to_the_builtin_headers() {
+    let mut headers = Headers::default();
+    assert_eq!(headers.label(Column::Network), "Network");
+
+    headers
+        .extend_from_str("[da]\nblobs = \"Blobs\"\n\n[headers]\nnetwork = \"Red\"\n\"gas-per-tx\" = \"Gas/tx\"\n")
+        .unwrap();
+    assert_eq!(headers.label(Column::Network), "Red");
+    assert_eq!(headers.label(Column::GasPerTx), "Gas/tx");
+    assert_eq!(headers.label(Column::Tps), "TPS");
+
+    assert_eq!(
+        headers.extend_from_str("[headers]\nblock = Bloque\n"),
+        Err(String::from(
+            "2: the label of `block` must be a non-empty quoted string"
+        ))
+    );
+    let error = headers
+        .extend_from_str("[headers]\nheight = \"Altura\"\n")
+        .unwrap_err();
+    assert!(error.starts_with("2: unknown column `height`, expected one of network, name, block"));
+}
+
+#[test]
+fn table_header_uses_the_labels() {
+    let mut tui = common::tui();
+    tui.display
+        .headers
+        .extend_from_str("[headers]\nnetwork = \"Red\"\ntps = \"TPS/s\"\n")
+        .unwrap();
+    let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+
+    let header: String = (0..120)
+        .map(|x| terminal.backend().buffer().get(x, 2).symbol().to_string())
+        .collect();
+    assert!(header.contains("Red"), "{header}");
+    assert!(header.contains("TPS/s"), "{header}");
+    assert!(!header.contains("Network"), "{header}");
+    // Metric semantics are unchanged, only the display strings.
+    assert_eq!(Column::Tps.header(), "TPS");
+}
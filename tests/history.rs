@@ -0,0 +1,86 @@
+mod common;
+
+use clap::Parser;
+use roller::{anomaly::HISTORY_LEN, history, Config, Data};
+use std::{collections::HashMap, time::Duration};
+
+fn data(block_number: u64) -> Option<Data> {
+    Some(Data {
+        block_number,
+        tps: block_number as f32 / 2.0,
+        gps: 1.5,
+        dps: 0.25,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn history_is_restored_in_the_next_session() {
+    let path = std::env::temp_dir()
+        .join(format!("roller-history-{}", std::process::id()))
+        .join("history.json");
+    let mut tui = common::tui();
+    let base = tui.network_mut("base").unwrap();
+    for block in 101..=140 {
+        base.update_data(data(block));
+    }
+    history::save(&path, &tui.networks()).unwrap();
+
+    let loaded = history::load(&path, history::MAX_AGE).unwrap();
+    // Every network of `common::tui` received a single update, base a few more.
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded["base"].len(), HISTORY_LEN);
+    assert_eq!(loaded["base"].back().unwrap().block_number, 139);
+    assert_eq!(loaded["base"].back().unwrap().tps, 69.5);
+
+    let mut next = common::tui();
+    assert!(next.network("base").unwrap().history.is_empty());
+    assert_eq!(next.restore_history(loaded), 1);
+    let restored: Vec<u64> = next
+        .network("base")
+        .unwrap()
+        .history
+        .iter()
+        .map(|d| d.block_number)
+        .collect();
+    assert_eq!(restored, (110..=139).collect::<Vec<_>>());
+
+    // A history older than the limit is discarded.
+    std::thread::sleep(Duration::from_millis(1100));
+    assert!(history::load(&path, Duration::ZERO).unwrap().is_empty());
+
+    std::fs::write(&path, "{").unwrap();
+    assert!(history::load(&path, history::MAX_AGE).is_err());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    assert!(history::load(&path, history::MAX_AGE).unwrap().is_empty());
+}
+
+#[test]
+fn histories_of_untracked_networks_are_ignored() {
+    let mut tui = common::tui();
+    let history = HashMap::from([(String::from("mode"), data(1).into_iter().collect())]);
+    assert_eq!(tui.restore_history(history), 0);
+}
+
+#[test]
+fn persisting_is_opt_in() {
+    let config = Config::try_parse_from(["roller"]).unwrap();
+    assert_eq!(config.history_path(), None);
+    let config = Config::try_parse_from(["roller", "--persist-history"]).unwrap();
+    assert_eq!(config.history_path(), history::default_path());
+}
+
+#[test]
+fn history_is_exported_as_csv() {
+    let mut tui = common::tui();
+    let base = tui.network_mut("base").unwrap();
+    base.update_data(data(101));
+    base.update_data(data(102));
+
+    let mut csv = Vec::new();
+    history::write_csv(&mut csv, &base.history).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "blockNumber,tps,gps,dps,pendingTxCount\n100,12.5,20.25,3.5,\n101,50.5,1.5,0.25,\n"
+    );
+}
@@ -0,0 +1,77 @@
+use clap::ValueEnum;
+use roller::{init, Column, Config, DaRegistry, KeyMap, PriceRegistry, Thresholds};
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Returns a path in the temporary directory, unique to the test process and the given name.
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("roller-init-{}-{name}", std::process::id()))
+}
+
+/// Loads a configuration file, without anything else set.
+fn load(path: &Path) -> Config {
+    Config::try_load_from(["roller", "--config", path.to_str().unwrap()]).unwrap()
+}
+
+/// Serializes a configuration without the path of its file, to compare its values.
+fn values(mut config: Config) -> serde_json::Value {
+    config.config = None;
+    serde_json::to_value(config).unwrap()
+}
+
+#[test]
+fn template_round_trips_to_the_defaults() {
+    let defaults = values(Config::try_load_from(["roller"]).unwrap());
+    let template = init::template();
+    // Every setting uncommented, with the descriptions and examples left as they are.
+    let uncommented: String = template
+        .lines()
+        .map(|line| line.strip_prefix("# ").unwrap_or(line))
+        .map(|line| format!("{line}\n"))
+        .collect();
+    assert!(uncommented.contains("\ncolumns = [\"network\""));
+    assert!(uncommented.contains("\n[keys]\nquit = \"q\"\n"));
+
+    for (name, contents) in [("commented", template), ("uncommented", uncommented)] {
+        let path = temp_path(name);
+        std::fs::write(&path, contents).unwrap();
+        let config = load(&path);
+        let sections = config.sections().unwrap();
+        assert_eq!(sections.keys, KeyMap::default(), "{name}");
+        assert_eq!(sections.da, DaRegistry::default(), "{name}");
+        assert_eq!(sections.prices, PriceRegistry::default(), "{name}");
+        assert_eq!(sections.thresholds, Thresholds::default(), "{name}");
+        let headers = sections.headers;
+        for column in Column::value_variants() {
+            assert_eq!(headers.label(*column), column.header(), "{name}");
+        }
+        assert_eq!(values(config), defaults, "{name}");
+    }
+}
+
+#[test]
+fn init_refuses_to_overwrite_without_force() {
+    let path = temp_path("dir").join("config.toml");
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    let init = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_roller"))
+            .args(["init", "--path", path.to_str().unwrap()])
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    assert!(init(&[]).status.success());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), init::template());
+
+    std::fs::write(&path, "top = 5\n").unwrap();
+    let output = init(&[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pass --force to overwrite it"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "top = 5\n");
+
+    assert!(init(&["--force"]).status.success());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), init::template());
+}
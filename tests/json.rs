@@ -0,0 +1,127 @@
+mod common;
+
+use common::MockServer;
+use serde_json::Value;
+use std::{
+    io::{BufRead, BufReader},
+    process::{Command, Stdio},
+};
+
+/// Asserts that a JSON document contains every field documented in the README.
+fn assert_schema(document: &Value) {
+    assert!(document["timestamp"].is_u64());
+    for key in ["networks", "tps", "gps", "dps"] {
+        assert!(document["totals"][key].is_number(), "totals.{key}");
+    }
+
+    let networks = document["networks"].as_array().unwrap();
+    for network in networks {
+        for key in ["name", "label", "parentChain", "da", "stack"] {
+            assert!(network[key].is_string(), "{key}");
+        }
+        assert!(network.get("explorer").is_some());
+        for key in ["blockNumber", "tps", "gps", "dps"] {
+            assert!(network["data"][key].is_number(), "data.{key}");
+        }
+    }
+}
+
+#[test]
+fn once_prints_a_json_snapshot() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = common::roller(&server, &["--once", "--format", "json"]);
+
+    assert!(output.status.success());
+    let document: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_schema(&document);
+
+    assert_eq!(document["totals"]["networks"], 3);
+    assert_eq!(document["totals"]["tps"], 22.0);
+    assert_eq!(document["networks"][0]["name"], "base");
+    assert_eq!(document["networks"][0]["parentChain"], "ethereum");
+    assert_eq!(document["networks"][0]["data"]["blockNumber"], 100);
+}
+
+#[test]
+fn follow_prints_one_json_document_per_update() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let mut child = Command::new(env!("CARGO_BIN_EXE_roller"))
+        .args([
+            "--api-endpoint",
+            &server.url,
+            "--format",
+            "json",
+            "--follow",
+        ])
+        .stdout(Stdio::piped())
+        // Without a terminal on stderr, the interface isn't drawn along
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let lines: Vec<Value> = BufReader::new(child.stdout.take().unwrap())
+        .lines()
+        .take(3)
+        .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+        .collect();
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert_eq!(lines.len(), 3);
+    for document in &lines {
+        assert_eq!(document["totals"]["networks"], 3);
+    }
+    // Every line reflects one more update than the previous one.
+    let updated = |document: &Value| {
+        document["networks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|n| !n["data"].is_null())
+            .count()
+    };
+    assert_eq!(lines.iter().map(updated).collect::<Vec<_>>(), [1, 2, 3]);
+    assert_schema(&lines[2]);
+}
+
+#[test]
+fn follow_requires_a_machine_readable_format() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = common::roller(&server, &["--follow"]);
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn top_limits_the_networks_of_the_snapshot() {
+    let metadata = common::metadata().replacen(
+        '{',
+        r#"{
+        "mode": {"name": "mode", "label": "Mode", "parentChain": "1", "da": "ethereum", "stack": "op"},
+        "lyra": {"name": "lyra", "label": "Lyra", "parentChain": "1", "da": "celestia", "stack": "op"},"#,
+        1,
+    );
+    let mut events = common::events();
+    events.push(common::event("mode", 400, 9.0, 3.0, 1.0));
+    events.push(common::event("lyra", 500, 1.0, 0.5, 0.25));
+    let server = MockServer::start(&metadata, events);
+    let output = common::roller(
+        &server,
+        &["--once", "--top", "3", "--sort", "tps", "--format", "json"],
+    );
+
+    assert!(output.status.success());
+    let document: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_schema(&document);
+
+    let names: Vec<_> = document["networks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["base", "mode", "xai"]);
+    assert_eq!(document["totals"]["networks"], 3);
+    assert_eq!(document["totals"]["tps"], 28.5);
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+use roller::{Action, KeyBinding, KeyMap, Profiles, Section, SortingStrategy};
+
+#[test]
+fn keys_are_parsed_with_their_modifiers() {
+    let key = |code, modifiers| KeyBinding::new(code, modifiers);
+
+    assert_eq!(
+        KeyBinding::parse("ctrl+d"),
+        Ok(key(KeyCode::Char('d'), KeyModifiers::CONTROL))
+    );
+    assert_eq!(
+        KeyBinding::parse("Ctrl+Alt+PageUp"),
+        Ok(key(
+            KeyCode::PageUp,
+            KeyModifiers::CONTROL | KeyModifiers::ALT
+        ))
+    );
+    assert_eq!(
+        KeyBinding::parse("f5"),
+        Ok(key(KeyCode::F(5), KeyModifiers::NONE))
+    );
+    assert_eq!(
+        KeyBinding::parse("alt++"),
+        Ok(key(KeyCode::Char('+'), KeyModifiers::ALT))
+    );
+    assert_eq!(KeyBinding::parse("ctrl+d").unwrap().to_string(), "ctrl+d");
+
+    assert_eq!(
+        KeyBinding::parse("hyper+d"),
+        Err(String::from("unknown modifier `hyper` in `hyper+d`"))
+    );
+    assert_eq!(
+        KeyBinding::parse("f13"),
+        Err(String::from("unknown key `f13`"))
+    );
+}
+
+#[test]
+fn unbound_actions_keep_their_defaults() {
+    let mut keys = KeyMap::default();
+    keys.extend_from_str(
+        "# rebinds\n[keys]\nsort_tps = \"ctrl+t\"\nquit = \"esc\"\nclose = \"backspace\"\n",
+    )
+    .unwrap();
+
+    let press = |code, modifiers| keys.action(&KeyEvent::new(code, modifiers));
+    assert_eq!(
+        press(KeyCode::Char('t'), KeyModifiers::CONTROL),
+        Some(Action::SortTps)
+    );
+    assert_eq!(press(KeyCode::Char('t'), KeyModifiers::NONE), None);
+    assert_eq!(press(KeyCode::Esc, KeyModifiers::NONE), Some(Action::Quit));
+    assert_eq!(
+        press(KeyCode::Char('z'), KeyModifiers::CONTROL),
+        Some(Action::Suspend)
+    );
+    assert_eq!(press(KeyCode::Char('z'), KeyModifiers::NONE), None);
+    assert_eq!(
+        press(KeyCode::Char('g'), KeyModifiers::NONE),
+        Some(Action::SortGps)
+    );
+}
+
+#[test]
+fn duplicates_and_unknown_actions_are_rejected() {
+    let error = |contents| KeyMap::default().extend_from_str(contents).unwrap_err();
+
+    assert_eq!(
+        error("[keys]\nquit = \"x\"\n\nquit = \"y\"\n"),
+        "4: `quit` is already assigned on line 2"
+    );
+    // Taking the default key of an action that isn't rebound.
+    assert_eq!(
+        error("[keys]\nsort_tps = \"g\"\n"),
+        "2: `g` is bound to both `sort_gps` and `sort_tps`"
+    );
+    assert_eq!(
+        error("[keys]\nsort_speed = \"s\"\n"),
+        "2: unknown action `sort_speed`"
+    );
+    assert_eq!(
+        error("[keys]\nquit = \"ctrl+nope\"\n"),
+        "2: unknown key `nope`"
+    );
+    // Lines before any section are options, left to `Profiles`.
+    assert_eq!(
+        Profiles::parse("quit = \"x\"\n"),
+        Err(String::from("1: unknown option `quit`"))
+    );
+    assert_eq!(error("[colors]\n"), "1: unknown section `[colors]`");
+}
+
+#[test]
+fn rebound_keys_drive_the_interface_and_its_hints() {
+    let mut tui = common::tui();
+    tui.keys
+        .extend_from_str("[keys]\nsort_tps = \"ctrl+t\"\nexport = \"f5\"\n")
+        .unwrap();
+
+    tui.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+    tui.commit_sort_preview();
+    assert!(matches!(tui.sorting_strategy, SortingStrategy::Tps));
+
+    let mut terminal = Terminal::new(TestBackend::new(300, 20)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let info: String = (0..300).map(|x| buffer.get(x, 18).symbol()).collect();
+    assert!(info.contains("[f5] export"), "{info}");
+    assert!(info.contains("[ctrl+t] txs per second"), "{info}");
+}
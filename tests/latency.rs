@@ -0,0 +1,67 @@
+mod common;
+
+use crossterm::event::KeyCode;
+use ratatui::{backend::TestBackend, Terminal};
+use roller::latency::{self, LatencyMonitor, Ping, PING_HISTORY};
+use std::{net::TcpListener, time::Duration};
+
+#[tokio::test]
+async fn ping_measures_any_response_and_times_out_without_one() {
+    // Any status counts, the mock server answers `/` with a 404.
+    let server = common::MockServer::start(&common::metadata(), Vec::new());
+    let http = reqwest::Client::new();
+    let ping = latency::ping(&http, &server.url, Duration::from_secs(5)).await;
+    assert!(matches!(ping, Ping::Latency(_)), "{ping:?}");
+
+    // Connections are queued by the listener, but never answered.
+    let silent = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", silent.local_addr().unwrap());
+    let ping = latency::ping(&http, &url, Duration::from_millis(100)).await;
+    assert_eq!(ping, Ping::Timeout);
+    assert_eq!(ping.to_string(), "timeout");
+}
+
+#[test]
+fn sparkline_scales_the_kept_pings() {
+    let ms = |ms| Ping::Latency(Duration::from_millis(ms));
+    let mut monitor = LatencyMonitor::default();
+    for ping in [ms(999), ms(100), Ping::Timeout, ms(450), ms(800)] {
+        monitor.record("base", ping);
+    }
+    let pings = monitor.history("base").unwrap();
+    assert_eq!(latency::sparkline(pings), "█▁×▄▆");
+    assert_eq!(
+        latency::range(pings),
+        Some((Duration::from_millis(100), Duration::from_millis(999)))
+    );
+    assert_eq!(latency::range(&[Ping::Timeout, Ping::Failed]), None);
+
+    for _ in 0..PING_HISTORY {
+        monitor.record("base", ms(100));
+    }
+    assert_eq!(monitor.history("base").unwrap().len(), PING_HISTORY);
+    assert_eq!(monitor.history("zora"), None);
+}
+
+#[test]
+fn detail_pane_shows_the_last_latency() {
+    let mut tui = common::tui();
+    let mut monitor = LatencyMonitor::default();
+    monitor.record("base", Ping::Latency(Duration::from_millis(120)));
+    monitor.record("base", Ping::Timeout);
+    tui.latency = Some(monitor);
+    tui.handle_input(KeyCode::Enter);
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let lines: Vec<String> = (0..40)
+        .map(|y| (0..120).map(|x| buffer.get(x, y).symbol()).collect())
+        .collect();
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("Latency: timeout ▁× min:120 ms max:120 ms")),
+        "{lines:#?}"
+    );
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::MockServer;
+
+/// The metadata of `common::metadata()` with an L1, which has an empty parent chain.
+fn metadata() -> String {
+    common::metadata().replacen(
+        '{',
+        r#"{
+        "ethereum": {"name": "ethereum", "label": "Ethereum", "parentChain": "", "da": "ethereum", "stack": "evm"},"#,
+        1,
+    )
+}
+
+/// The events of `common::events()` with one for the L1.
+fn events() -> Vec<(String, String)> {
+    let mut events = common::events();
+    events.push(common::event("ethereum", 500, 15.0, 1.25, 0.75));
+    events
+}
+
+#[test]
+fn l1_rows_have_no_settlement_or_da() {
+    let server = MockServer::start(&metadata(), events());
+    let output = common::roller(&server, &["--once", "--columns", "network,da,settlement"]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Ethereum  —         —\n"), "{stdout}");
+    assert!(
+        stdout.contains("Base      ethereum  ethereum\n"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn layer_filters_l1s_and_l2s() {
+    let server = MockServer::start(&metadata(), events());
+    let names = |layer| {
+        let output = common::roller(
+            &server,
+            &["--once", "--columns", "network", "--layer", layer],
+        );
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert_eq!(names("l1"), "Network\nEthereum\n");
+    assert_eq!(names("l2"), "Network\nBase\nXai\nZora\n");
+    assert_eq!(names("all"), "Network\nBase\nXai\nZora\nEthereum\n");
+}
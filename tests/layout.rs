@@ -0,0 +1,247 @@
+mod common;
+
+use clap::Parser;
+use crossterm::event::KeyCode;
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+use roller::{Column, Config, Tui};
+
+#[test]
+fn table_fills_the_space_between_the_summary_and_info_bar() {
+    for (width, height) in [(20, 10), (80, 24), (200, 50)] {
+        let (summary, table, info) = Tui::layout(Rect::new(0, 0, width, height), 3);
+
+        assert_eq!((summary.y, summary.height), (0, 1));
+        assert_eq!(info.height, 3);
+        assert_eq!(table.height, height - 4);
+        assert_eq!((table.width, info.width), (width, width));
+        assert_eq!(table.y, summary.bottom());
+        // The info bar is placed right below the table, at the bottom of the frame.
+        assert_eq!(info.y, table.bottom());
+        assert_eq!(info.bottom(), height);
+    }
+}
+
+#[test]
+fn renders_at_various_sizes() {
+    for (width, height) in [(20, 10), (80, 24), (200, 50)] {
+        let mut tui = common::tui();
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| tui.render(f)).unwrap();
+
+        // The bottom border of the info bar is drawn on the last row.
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer.get(0, height - 1).symbol(), "╰");
+        assert_eq!(buffer.get(0, height - 3).symbol(), "╭");
+    }
+}
+
+#[test]
+fn resizing_leaves_no_ghost_characters() {
+    let mut tui = common::tui();
+    let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    for (width, height) in [(120, 20), (60, 10), (160, 30), (120, 20)] {
+        terminal.backend_mut().resize(width, height);
+        terminal.draw(|f| tui.render(f)).unwrap();
+    }
+
+    // The frame after the resizes matches the one of a fresh terminal of the same size.
+    let mut fresh = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    fresh.draw(|f| tui.render(f)).unwrap();
+    assert_eq!(terminal.backend().buffer(), fresh.backend().buffer());
+}
+
+#[test]
+fn summary_counts_active_networks() {
+    let mut tui = common::tui();
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+
+    let summary: String = (0..30)
+        .map(|x| terminal.backend().buffer().get(x, 0).symbol().to_string())
+        .collect();
+    assert_eq!(summary.trim(), "• 3 of 3 networks active");
+}
+
+#[test]
+fn networks_without_data_show_a_loading_spinner() {
+    let mut tui = Tui::new(common::networks());
+    tui.columns = vec![Column::Network, Column::Block, Column::Tps, Column::Stack];
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+
+    let mut row = || {
+        terminal.draw(|f| tui.render(f)).unwrap();
+        // The first row of the table, below the summary line, the border and the header.
+        let buffer = terminal.backend().buffer();
+        (0..80)
+            .map(|x| buffer.get(x, 3).symbol().to_string())
+            .collect::<String>()
+    };
+
+    let first = row();
+    assert!(first.contains("loading…"), "{first}");
+    assert!(first.contains('⠙'), "{first}");
+    // The spinner advances with every rendered frame.
+    assert!(row().contains('⠹'));
+}
+
+#[test]
+fn summary_shows_the_top_mover() {
+    let mut tui = common::tui();
+    for (name, gps) in [("zora", 2.0), ("xai", 15.75)] {
+        tui.update_networks(common::sse_event(common::event(name, 400, 1.0, gps, 1.0)));
+    }
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+
+    let summary: String = (0..80)
+        .map(|x| terminal.backend().buffer().get(x, 0).symbol().to_string())
+        .collect();
+    assert_eq!(
+        summary.trim(),
+        "• 3 of 3 networks active | top mover: Xai +10.00 MGas/s"
+    );
+}
+
+#[test]
+fn column_spacing_separates_the_columns() {
+    let header = |spacing| {
+        let mut tui = common::tui();
+        tui.columns = vec![Column::Network, Column::Block];
+        tui.display.column_spacing = spacing;
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| tui.render(f)).unwrap();
+
+        // The header row, below the summary line and the border.
+        let buffer = terminal.backend().buffer();
+        (0..80)
+            .map(|x| buffer.get(x, 2).symbol().to_string())
+            .collect::<String>()
+    };
+
+    let gap = |spacing| {
+        let header = header(spacing);
+        header.find("Block").unwrap() - header.find("Network").unwrap()
+    };
+    assert_eq!(gap(4) - gap(0), 4);
+}
+
+#[test]
+fn heartbeat_pulses_with_events_only() {
+    let mut tui = Tui::new(common::networks());
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let mut heartbeat = |tui: &mut Tui| {
+        terminal.draw(|f| tui.render(f)).unwrap();
+        terminal.backend().buffer().get(1, 0).symbol().to_string()
+    };
+
+    assert_eq!(heartbeat(&mut tui), "·");
+    tui.update_networks(common::sse_event(common::event("base", 101, 1.0, 1.0, 1.0)));
+    assert_eq!(heartbeat(&mut tui), "•");
+    tui.update_networks(common::sse_event(common::event("zora", 201, 1.0, 1.0, 1.0)));
+    assert_eq!(heartbeat(&mut tui), "●");
+
+    // Without events the heartbeat freezes, however often the interface is redrawn.
+    assert_eq!(heartbeat(&mut tui), "●");
+}
+
+#[test]
+fn long_labels_are_truncated_to_their_column() {
+    let mut tui = common::tui();
+    let base = tui.network_mut("base").unwrap();
+    base.label = String::from("Base Mainnet Network");
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+
+    let rows: Vec<String> = (3..6)
+        .map(|y| {
+            (0..80)
+                .map(|x| terminal.backend().buffer().get(x, y).symbol().to_string())
+                .collect()
+        })
+        .collect();
+    // 15% of the 75 columns left inside the borders and after the highlight symbol.
+    assert!(
+        rows.iter().any(|row| row.contains("Base Mainn… ")),
+        "{rows:?}"
+    );
+}
+
+#[test]
+fn wrapped_labels_grow_their_row() {
+    let config = Config::try_parse_from(["roller", "--wrap-labels"]).unwrap();
+    let mut tui = common::tui().with_config(&config);
+    let base = tui.network_mut("base").unwrap();
+    base.label = String::from("Base Mainnet Network");
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+
+    let rows: Vec<String> = (0..24)
+        .map(|y| {
+            (0..80)
+                .map(|x| terminal.backend().buffer().get(x, y).symbol().to_string())
+                .collect()
+        })
+        .collect();
+    let y = rows.iter().position(|row| row.contains("Mainnet")).unwrap();
+    assert!(rows[y - 1].contains("Base "), "{rows:?}");
+    assert!(rows[y + 1].contains("Network"), "{rows:?}");
+    // The other cells are centered on the middle line of the row.
+    assert!(rows[y].contains("100"), "{rows:?}");
+    assert!(!rows[y - 1].contains("100"), "{rows:?}");
+}
+
+#[test]
+fn info_bar_summarizes_more_than_two_active_modes() {
+    let bottom_row = |tui: &mut Tui, width: u16| {
+        let mut terminal = Terminal::new(TestBackend::new(width, 20)).unwrap();
+        terminal.draw(|f| tui.render(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..width)
+            .map(|x| buffer.get(x, 19).symbol())
+            .collect::<String>()
+    };
+
+    let config = Config::try_parse_from(["roller", "--pending-txs", "--age-bar"]).unwrap();
+    let mut tui = common::tui().with_config(&config);
+    assert_eq!(tui.active_mode_summary(), "pending age");
+    assert!(!bottom_row(&mut tui, 120).contains("active modes"));
+
+    let config = Config::try_parse_from(["roller", "--pending-txs", "--age-bar", "--top", "2"]);
+    let mut tui = common::tui().with_config(&config.unwrap());
+    assert_eq!(tui.active_mode_summary(), "pending age top");
+    let row = bottom_row(&mut tui, 120);
+    assert!(
+        row.starts_with("╰ active modes: pending age top ─"),
+        "{row}"
+    );
+    // Truncated to a third of the width.
+    let row = bottom_row(&mut tui, 60);
+    assert!(row.starts_with("╰ active modes: pendi… ─"), "{row}");
+}
+
+#[test]
+fn info_bar_expands_into_the_key_reference() {
+    let mut tui = common::tui();
+    let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    tui.handle_input(KeyCode::Char('h'));
+    assert_eq!(tui.info_bar_height(), 6);
+    terminal.draw(|f| tui.render(f)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let row = |y| {
+        (0..120)
+            .map(|x| buffer.get(x, y).symbol())
+            .collect::<String>()
+    };
+    assert_eq!(buffer.get(0, 14).symbol(), "╭");
+    assert!(
+        row(15).starts_with("│Navigate: [up] up | [down] down"),
+        "{}",
+        row(15)
+    );
+    assert!(row(17).contains("Sort, again to reverse: [g] gas per second"));
+    assert!(row(18).contains("[h] collapse | [q] quit"), "{}", row(18));
+
+    tui.handle_input(KeyCode::Char('h'));
+    assert_eq!(tui.info_bar_height(), 3);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::MockServer;
+
+/// Runs `roller list` with the provided arguments and returns its stdout.
+fn list(args: &[&str]) -> String {
+    let server = MockServer::start(&common::metadata(), vec![]);
+    let output = common::roller(&server, &[&["list"], args].concat());
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn lists_the_networks_sorted_by_name() {
+    assert_eq!(
+        list(&[]),
+        "Name  Network  Stack  DA        Settlement\n\
+         base  Base     op     ethereum  ethereum\n\
+         xai   Xai      orbit  anytrust  arbitrum\n\
+         zora  Zora     op     ethereum  ethereum\n"
+    );
+}
+
+#[test]
+fn lists_in_every_format() {
+    assert_eq!(
+        list(&["--format", "csv"]),
+        "Name,Network,Stack,DA,Settlement\n\
+         base,Base,op,ethereum,ethereum\n\
+         xai,Xai,orbit,anytrust,arbitrum\n\
+         zora,Zora,op,ethereum,ethereum\n"
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&list(&["--format", "json"])).unwrap();
+    let names: Vec<_> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["base", "xai", "zora"]);
+    assert_eq!(json[1]["parentChain"], "arbitrum");
+}
+
+#[test]
+fn filters_by_metadata_fields() {
+    assert_eq!(
+        list(&["--stack", "OP"]),
+        "Name  Network  Stack  DA        Settlement\n\
+         base  Base     op     ethereum  ethereum\n\
+         zora  Zora     op     ethereum  ethereum\n"
+    );
+    assert_eq!(
+        list(&["--settlement", "arbitrum"]),
+        "Name  Network  Stack  DA        Settlement\n\
+         xai   Xai      orbit  anytrust  arbitrum\n"
+    );
+}
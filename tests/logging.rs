@@ -0,0 +1,120 @@
+mod common;
+
+use common::MockServer;
+use serde_json::Value;
+
+#[test]
+fn log_file_records_the_session_events() {
+    let mut events = vec![
+        (String::from("base"), String::from("not json")),
+        common::event("unknown", 1, 1.0, 1.0, 1.0),
+    ];
+    events.extend(common::events());
+    let server = MockServer::start(&common::metadata(), events);
+
+    let path = std::env::temp_dir().join(format!("roller-log-{}.json", std::process::id()));
+    let output = common::roller(
+        &server,
+        &[
+            "--once",
+            "--log-file",
+            path.to_str().unwrap(),
+            "--log-level",
+            "debug",
+            "--log-format",
+            "json",
+        ],
+    );
+    let log = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    let messages: Vec<String> = log
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .map(|line| line["fields"]["message"].as_str().unwrap().to_string())
+        .collect();
+
+    for expected in [
+        "starting roller",
+        "fetched network metadata",
+        "connecting to the event stream",
+        "failed to parse event data",
+        "dropped event of an untracked network",
+    ] {
+        assert!(
+            messages.iter().any(|m| m == expected),
+            "{expected}: {messages:?}"
+        );
+    }
+}
+
+#[test]
+fn nothing_is_logged_below_the_level() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let path = std::env::temp_dir().join(format!("roller-log-{}.txt", std::process::id()));
+    let output = common::roller(
+        &server,
+        &[
+            "--once",
+            "--log-file",
+            path.to_str().unwrap(),
+            "--log-level",
+            "error",
+        ],
+    );
+    let log = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    assert!(log.is_empty(), "{log}");
+}
+
+#[test]
+fn spans_time_the_requests_and_events() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let path = std::env::temp_dir().join(format!("roller-spans-{}.json", std::process::id()));
+    let output = common::roller(
+        &server,
+        &[
+            "--once",
+            "--log-file",
+            path.to_str().unwrap(),
+            "--log-level",
+            "trace",
+            "--log-format",
+            "json",
+        ],
+    );
+    let log = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    // The spans stay in the log file, the snapshot is all that's printed.
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("time.busy"));
+    let lines: Vec<Value> = log
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let closed = |name: &str| {
+        lines.iter().any(|line| {
+            line["fields"]["message"] == "close"
+                && line["span"]["name"] == name
+                && line["fields"]["time.busy"].is_string()
+        })
+    };
+    for name in ["get_metadata", "get_metadata_from", "get_stream", "event"] {
+        assert!(closed(name), "{name}: {log}");
+    }
+
+    // The messages logged within a span carry its fields.
+    let fetched = lines
+        .iter()
+        .find(|line| line["fields"]["message"] == "fetched network metadata")
+        .unwrap();
+    assert_eq!(fetched["span"]["name"], "get_metadata_from");
+    assert!(fetched["span"]["url"].is_string());
+    assert!(lines.iter().any(|line| {
+        line["fields"]["message"] == "received event" && line["span"]["network"] == "base"
+    }));
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "prometheus")]
+
+mod common;
+
+use common::MockServer;
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Returns a port that was free a moment ago.
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// Requests `/metrics` and parses the samples of the exposition format, keyed by metric and labels.
+fn scrape(port: u16) -> Option<HashMap<String, f64>> {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).ok()?;
+    write!(stream, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").ok()?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).ok()?;
+
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    assert!(head.starts_with("HTTP/1.1 200 OK"), "{head}");
+    let samples = body
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let (series, value) = line.rsplit_once(' ').expect(line);
+            (series.to_string(), value.parse().expect(line))
+        })
+        .collect();
+    Some(samples)
+}
+
+#[test]
+fn metrics_are_served_during_a_session() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let port = free_port();
+    let mut roller = Command::new(env!("CARGO_BIN_EXE_roller"))
+        .args([
+            "--api-endpoint",
+            &server.url,
+            "--follow",
+            "--format",
+            "json",
+        ])
+        .args(["--prometheus-port", &port.to_string()])
+        .stdout(Stdio::null())
+        // Without a terminal on stderr, the interface isn't drawn along
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    // Scrape until every event of the mock stream has been received.
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let samples = loop {
+        match scrape(port) {
+            Some(samples) if samples.get("roller_events_received_total") == Some(&3.0) => {
+                break samples
+            }
+            _ if Instant::now() > deadline => panic!("the metrics never reported the events"),
+            _ => thread::sleep(Duration::from_millis(50)),
+        }
+    };
+    roller.kill().unwrap();
+    roller.wait().unwrap();
+
+    assert_eq!(samples[r#"roller_tps{network="base"}"#], 12.5);
+    assert_eq!(samples[r#"roller_gps{network="zora"}"#], 1.5);
+    assert_eq!(samples[r#"roller_dps{network="xai"}"#], 1.25);
+    assert_eq!(samples[r#"roller_block_number{network="xai"}"#], 300.0);
+    assert_eq!(samples["roller_reconnects_total"], 0.0);
+}
+
+#[test]
+fn a_port_in_use_fails_at_startup() {
+    let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = taken.local_addr().unwrap().port().to_string();
+    let server = MockServer::start(&common::metadata(), common::events());
+
+    let output = common::roller(
+        &server,
+        &["--follow", "--format", "json", "--prometheus-port", &port],
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains(&format!("failed to serve the metrics on port {port}")),
+        "{stderr}"
+    );
+}
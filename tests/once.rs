@@ -0,0 +1,91 @@
+mod common;
+
+use common::MockServer;
+
+#[test]
+fn prints_sorted_table_once_every_network_is_updated() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = common::roller(&server, &["--once"]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("Network"));
+    // Sorted by gas per second, in descending order.
+    assert!(lines[1].starts_with("Base"));
+    assert!(lines[2].starts_with("Xai"));
+    assert!(lines[3].starts_with("Zora"));
+    // No terminal control sequences should be written in this mode.
+    assert!(!stdout.contains('\x1b'));
+}
+
+#[test]
+fn respects_columns_sort_and_filters() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = common::roller(
+        &server,
+        &[
+            "--once",
+            "--columns",
+            "network,tps",
+            "--sort",
+            "tps",
+            "--exclude",
+            "base",
+        ],
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "Network  TPS\nXai      7.00\nZora     2.50\n");
+}
+
+#[test]
+fn fails_when_nothing_is_received() {
+    let server = MockServer::start(&common::metadata(), vec![]);
+    let output = common::roller(&server, &["--once", "--once-timeout-ms", "500"]);
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn falls_back_to_plain_output_when_stdout_is_not_a_terminal() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    // The output of the process is captured, so its stdout is a pipe.
+    let output = common::roller(&server, &[]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 4);
+    assert!(!stdout.contains('\x1b'));
+}
+
+#[test]
+fn exports_an_html_table() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let path = std::env::temp_dir().join(format!("roller-export-{}.html", std::process::id()));
+    let output = common::roller(
+        &server,
+        &[
+            "--columns",
+            "network,tps",
+            "--export-html",
+            path.to_str().unwrap(),
+        ],
+    );
+    let html = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // The flag implies --once, which still prints the table.
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 4);
+    assert!(html.starts_with("<!DOCTYPE html>"), "{html}");
+    assert!(html.contains(">Network</th><th"), "{html}");
+    assert!(html.contains(">Xai</td><td style=\"padding: 4px 12px; text-align: right\">7.00</td>"));
+    assert_eq!(html.matches("<tr>").count(), 4);
+    // Self-contained, without stylesheets or scripts to fetch.
+    assert!(!html.contains("<link") && !html.contains("<script"));
+}
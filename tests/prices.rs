@@ -0,0 +1,62 @@
+mod common;
+
+use roller::{format::Units, Column, GasPrice, PriceRegistry, Section};
+
+#[test]
+fn prices_are_read_from_the_prices_section() {
+    let mut registry = PriceRegistry::default();
+    registry
+        .extend_from_str(
+            "[da]\nblobs = \"Blobs\"\n\n[prices]\nbase = [0.005, 3400]\n\"xai\" = [0.1,0.25]\n",
+        )
+        .unwrap();
+    assert_eq!(
+        registry.get("base"),
+        Some(GasPrice {
+            gas_price_gwei: 0.005,
+            token_usd: 3400.0,
+        })
+    );
+    assert_eq!(registry.get("xai").unwrap().token_usd, 0.25);
+    assert_eq!(registry.get("zora"), None);
+
+    for (entry, error) in [
+        (
+            "base = 0.005",
+            "2: expected `network = [gas price in gwei, token price in USD]`, got `base = 0.005`",
+        ),
+        (
+            "base = [0.005]",
+            "2: expected `network = [gas price in gwei, token price in USD]`, got `base = [0.005]`",
+        ),
+        (
+            "base = [-1, 3400]",
+            "2: the prices of `base` must be positive numbers",
+        ),
+    ] {
+        let contents = format!("[prices]\n{entry}\n");
+        assert_eq!(
+            registry.extend_from_str(&contents),
+            Err(String::from(error))
+        );
+    }
+}
+
+#[test]
+fn gas_cost_is_derived_from_the_gas_per_second() {
+    // 20.25 MGas/s at 0.005 gwei is 0.00010125 ETH/s, or ~0.34 USD/s at 3400 USD.
+    let price = GasPrice {
+        gas_price_gwei: 0.005,
+        token_usd: 3400.0,
+    };
+    assert!((price.usd_per_second(20.25) - 0.34425).abs() < 1e-9);
+
+    let mut tui = common::tui();
+    let base = tui.network_mut("base").unwrap();
+    assert_eq!(Column::GasCost.value(base, 2, Units::Raw), "—");
+    assert_eq!(Column::GasCost.raw(base), "");
+
+    base.gas_price = Some(price);
+    assert_eq!(Column::GasCost.value(base, 2, Units::Raw), "0.34");
+    assert_eq!(Column::GasCost.value(base, 4, Units::Raw), "0.3443");
+}
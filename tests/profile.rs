@@ -0,0 +1,196 @@
+mod common;
+
+use common::MockServer;
+use roller::{BorderStyle, Config, Profiles};
+use std::{path::PathBuf, process::Command};
+
+/// Writes a configuration file to the temporary directory.
+fn config_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("roller-{name}-{}.toml", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+/// The arguments of `--top` resolved for the file, with its layer, or `None` if left out.
+fn merged_top(
+    base: Option<&str>,
+    profile: Option<&str>,
+    selected: bool,
+    set: bool,
+) -> Option<(Vec<String>, Option<String>)> {
+    let mut contents = String::new();
+    if let Some(top) = base {
+        contents += &format!("top = {top}\n");
+    }
+    contents += "[profile.staging]\n";
+    if let Some(top) = profile {
+        contents += &format!("top = {top}\n");
+    }
+    let profiles = Profiles::parse(&contents).unwrap();
+    let layered = profiles
+        .merge(selected.then_some("staging"), |id| set && id == "top")
+        .unwrap();
+    layered
+        .into_iter()
+        .find(|l| l.id == "top")
+        .map(|l| (l.args, l.profile))
+}
+
+#[test]
+fn layers_are_merged_in_order_of_precedence() {
+    let args = |top: &str| vec![String::from("--top"), top.to_string()];
+    let staging = Some(String::from("staging"));
+    // (top level, profile, profile selected, set by the environment or the command line)
+    let cases = [
+        ((None, None, false, false), None),
+        ((Some("3"), None, false, false), Some((args("3"), None))),
+        (
+            (Some("3"), Some("5"), false, false),
+            Some((args("3"), None)),
+        ),
+        (
+            (Some("3"), Some("5"), true, false),
+            Some((args("5"), staging.clone())),
+        ),
+        (
+            (None, Some("5"), true, false),
+            Some((args("5"), staging.clone())),
+        ),
+        ((Some("3"), None, true, false), Some((args("3"), None))),
+        ((Some("3"), Some("5"), true, true), None),
+        ((Some("3"), None, false, true), None),
+    ];
+    for ((base, profile, selected, set), expected) in cases {
+        assert_eq!(
+            merged_top(base, profile, selected, set),
+            expected,
+            "{base:?} {profile:?} {selected} {set}"
+        );
+    }
+}
+
+#[test]
+fn values_are_converted_to_arguments() {
+    let profiles = Profiles::parse(
+        "no-color = true\nonce = false\ninclude = [\"base\", \"zora\"]\nborder-style = \"double\"\n\n[profile.all]\ninclude = []\n",
+    )
+    .unwrap();
+    let args: Vec<Vec<String>> = profiles
+        .merge(None, |_| false)
+        .unwrap()
+        .into_iter()
+        .map(|l| l.args)
+        .collect();
+    assert_eq!(
+        args,
+        [
+            vec!["--no-color"],
+            vec![],
+            vec!["--include", "base", "--include", "zora"],
+            vec!["--border-style", "double"],
+        ]
+    );
+
+    // An empty array in a profile clears the value of the top level.
+    let layered = profiles.merge(Some("all"), |_| false).unwrap();
+    let include = layered.iter().find(|l| l.id == "include").unwrap();
+    assert!(include.args.is_empty());
+}
+
+#[test]
+fn invalid_files_and_unknown_profiles_are_rejected() {
+    for (contents, error) in [
+        ("tpo = 5\n", "1: unknown option `tpo`"),
+        ("profile = \"prod\"\n", "1: unknown option `profile`"),
+        ("no-color = 1\n", "1: `no-color` is a switch, expected true or false"),
+        ("top = five\n", "1: invalid value of `top`, expected a quoted string, a number, a boolean or an array of them"),
+        ("[profile.]\n", "1: unknown section `[profile.]`"),
+        ("[keys]\n[profile.prod]\ntop\n", "3: expected `option = value`, got `top`"),
+    ] {
+        assert_eq!(Profiles::parse(contents), Err(String::from(error)), "{contents}");
+    }
+
+    let profiles = Profiles::parse("[profile.prod]\n[profile.home-lab]\ntop = 1\n").unwrap();
+    assert_eq!(profiles.names(), ["prod", "home-lab"]);
+    assert_eq!(
+        profiles.merge(Some("staging"), |_| false),
+        Err(String::from(
+            "unknown profile `staging`, available profiles: prod, home-lab"
+        ))
+    );
+    assert_eq!(
+        Profiles::default().merge(Some("staging"), |_| false),
+        Err(String::from(
+            "unknown profile `staging`, the config file has none"
+        ))
+    );
+}
+
+#[test]
+fn command_line_overrides_the_profile_and_the_file() {
+    let path = config_file(
+        "profiles",
+        "top = 3\nborder-style = \"plain\"\n\n[keys]\nquit = \"x\"\n\n[profile.staging]\ntop = 5\nborder-style = \"double\"\n",
+    );
+    let load = |args: &[&str]| {
+        let config = ["roller", "--config", path.to_str().unwrap()];
+        Config::try_load_from(config.iter().chain(args)).unwrap()
+    };
+
+    let config = load(&[]);
+    assert_eq!((config.top, config.border_style), (3, BorderStyle::Plain));
+    assert_eq!(config.source("top"), "--top of the config file");
+
+    let config = load(&["--profile", "staging", "--top", "7"]);
+    assert_eq!((config.top, config.border_style), (7, BorderStyle::Double));
+    assert_eq!(config.source("top"), "--top");
+    assert_eq!(
+        config.source("border_style"),
+        "--border-style of [profile.staging]"
+    );
+
+    let error = Config::try_load_from([
+        "roller",
+        "--config",
+        path.to_str().unwrap(),
+        "--profile",
+        "prod",
+    ])
+    .unwrap_err()
+    .to_string();
+    std::fs::remove_file(&path).unwrap();
+    assert!(
+        error.contains("unknown profile `prod`, available profiles: staging"),
+        "{error}"
+    );
+}
+
+#[test]
+fn environment_overrides_the_profile() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let path = config_file(
+        "profiles-env",
+        "api-endpoint = \"http://127.0.0.1:9\"\nretries = 0\n\n[profile.prod]\nretry-delay = \"10ms\"\n",
+    );
+    let roller = |env: &[(&str, &str)]| {
+        Command::new(env!("CARGO_BIN_EXE_roller"))
+            .args(["--check", "--config", path.to_str().unwrap()])
+            .env_remove("ROLLER_API_ENDPOINT")
+            .env_remove("ROLLER_PROFILE")
+            .envs(env.iter().copied())
+            .output()
+            .unwrap()
+    };
+
+    let output = roller(&[
+        ("ROLLER_PROFILE", "prod"),
+        ("ROLLER_API_ENDPOINT", &server.url),
+    ]);
+    assert!(output.status.success(), "{output:?}");
+
+    let output = roller(&[("ROLLER_PROFILE", "staging")]);
+    std::fs::remove_file(&path).unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("available profiles: prod"), "{stderr}");
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use ratatui::{backend::TestBackend, Terminal};
+use roller::qr;
+
+#[test]
+fn renders_a_version_2_code_with_half_blocks() {
+    let lines = qr::render("https://explorer.zora.energy").unwrap();
+
+    // 25 modules and a quiet zone of 4 on each side, two rows of modules per line.
+    assert_eq!(lines.len(), 17);
+    for line in &lines {
+        assert_eq!(line.chars().count(), 33, "{line}");
+        assert!(line.chars().all(|c| " █▀▄".contains(c)), "{line}");
+    }
+}
+
+#[test]
+fn long_urls_are_truncated_to_their_domain() {
+    let url = "https://explorer.zora.energy/address/0x4200000000000000000000000000000000000006";
+    assert_eq!(qr::domain(url), "explorer.zora.energy");
+    assert_eq!(
+        qr::domain("explorer.zora.energy?tab=txs"),
+        "explorer.zora.energy"
+    );
+    assert_eq!(qr::render(url), qr::render("explorer.zora.energy"));
+
+    // Domains too long for the code are skipped.
+    let domain = "a".repeat(40);
+    assert_eq!(qr::render(&format!("https://{domain}.io/tx")), None);
+}
+
+#[test]
+fn detail_pane_shows_the_qr_code_unless_disabled() {
+    let blocks = |qr_code| {
+        let mut tui = common::tui();
+        tui.network_mut("base").unwrap().explorer =
+            Some(String::from("https://explorer.zora.energy"));
+        tui.detail_open = true;
+        tui.display.qr_code = qr_code;
+        let mut terminal = Terminal::new(TestBackend::new(120, 80)).unwrap();
+        terminal.draw(|f| tui.render(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .iter()
+            .filter(|c| c.symbol() == "█")
+            .count()
+    };
+
+    assert!(blocks(true) > 0);
+    assert_eq!(blocks(false), 0);
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::MockServer;
+use crossterm::event::KeyCode;
+use futures::StreamExt;
+use roller::Client;
+
+#[test]
+fn reconnect_key_requests_a_new_stream_once() {
+    let mut tui = common::tui();
+    tui.connected = true;
+    tui.handle_input(KeyCode::Char('R'));
+    assert_eq!(tui.status.as_deref(), Some("Reconnecting…"));
+    assert!(tui.take_reconnect());
+    assert!(!tui.take_reconnect());
+
+    // The status lasts until the new stream delivers a message, the data is kept.
+    tui.reconnected(Ok(()));
+    assert!(!tui.connected);
+    assert_eq!(tui.status.as_deref(), Some("Reconnecting…"));
+    tui.mark_connected();
+    assert!(tui.connected);
+    assert_eq!(tui.status, None);
+    assert!(tui.network("base").unwrap().data.is_some());
+}
+
+#[test]
+fn failed_reconnects_are_reported() {
+    let mut tui = common::tui();
+    tui.handle_input(KeyCode::Char('R'));
+    assert!(tui.take_reconnect());
+    tui.reconnected(Err(String::from("connection refused")));
+    assert_eq!(
+        tui.status.as_deref(),
+        Some("Reconnect failed: connection refused")
+    );
+
+    // Other statuses aren't cleared by the next message.
+    tui.mark_connected();
+    assert!(tui.status.is_some());
+}
+
+#[test]
+fn reconnect_key_is_typed_into_the_search() {
+    let mut tui = common::tui();
+    tui.handle_input(KeyCode::Char('/'));
+    tui.handle_input(KeyCode::Char('R'));
+    assert!(!tui.take_reconnect());
+    assert_eq!(tui.filter_query.as_deref(), Some("R"));
+}
+
+#[test]
+fn last_event_id_is_kept_to_resume_from() {
+    let mut tui = common::tui();
+    assert_eq!(tui.last_event_id, None);
+
+    let mut event = common::sse_event(common::event("base", 101, 1.0, 1.0, 1.0));
+    event.id = Some(String::from("41"));
+    tui.update_networks(event);
+    assert_eq!(tui.last_event_id.as_deref(), Some("41"));
+
+    // Events without an ID don't reset it.
+    tui.update_networks(common::sse_event(common::event("zora", 201, 1.0, 1.0, 1.0)));
+    assert_eq!(tui.last_event_id.as_deref(), Some("41"));
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn streams_resume_after_the_last_event_id() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let client = Client::new(&server.url);
+    client.get_stream().await.unwrap().next().await;
+    assert!(server.headers("last-event-id").is_empty());
+
+    client.set_last_event_id(Some(String::from("41")));
+    client.get_stream().await.unwrap().next().await;
+    assert_eq!(
+        server.headers("last-event-id"),
+        [(String::from("/sse"), String::from("41"))]
+    );
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use clap::Parser;
+use common::MockServer;
+use ratatui::{backend::TestBackend, Terminal};
+use roller::{ChainRegistry, Client, Config, NEW_BADGE, NEW_BADGE_DURATION};
+use std::time::{Duration, Instant};
+
+#[test]
+fn refresh_interval_is_parsed() {
+    let config = Config::try_parse_from(["roller", "--refresh-metadata", "5m"]).unwrap();
+    assert_eq!(
+        config.metadata_refresh_interval(),
+        Some(Duration::from_secs(300))
+    );
+
+    // Intervals under 30 seconds would hammer the API.
+    assert!(Config::try_parse_from(["roller", "--refresh-metadata", "10s"]).is_err());
+}
+
+#[test]
+fn refresh_is_disabled_by_default_and_by_off_or_zero() {
+    for args in [
+        &[][..],
+        &["--refresh-metadata", "off"],
+        &["--refresh-metadata", "0"],
+    ] {
+        let config = Config::try_parse_from(["roller"].iter().chain(args)).unwrap();
+        assert_eq!(config.metadata_refresh_interval(), None, "{args:?}");
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn network_added_between_refreshes_appears_in_the_table() {
+    let server = MockServer::start(&common::metadata(), vec![]);
+    let client = Client::new(server.url.as_str());
+    let chains = ChainRegistry::default();
+
+    let mut tui = roller::Tui::new(client.get_networks(&chains).await.unwrap());
+    assert_eq!(tui.networks().len(), 3);
+    assert!(tui.last_refresh.is_none());
+
+    let metadata = common::metadata().replacen(
+        '{',
+        r#"{"mode": {"name": "mode", "label": "Mode", "parentChain": "1", "da": "ethereum", "stack": "op"},"#,
+        1,
+    );
+    server.set_metadata(&metadata);
+    tui.merge_networks(client.get_networks(&chains).await.unwrap());
+
+    assert_eq!(tui.networks().len(), 4);
+    assert!(tui.networks().iter().any(|n| n.label == "Mode"));
+    assert!(tui.last_refresh.is_some());
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn networks_added_between_refreshes_are_badged_as_new() {
+    let server = MockServer::start(&common::metadata(), vec![]);
+    let client = Client::new(server.url.as_str());
+    let chains = ChainRegistry::default();
+
+    let mut tui = roller::Tui::new(client.get_networks(&chains).await.unwrap());
+    let metadata = common::metadata().replacen(
+        '{',
+        r#"{"mode": {"name": "mode", "label": "Mode", "parentChain": "1", "da": "ethereum", "stack": "op"},"#,
+        1,
+    );
+    server.set_metadata(&metadata);
+    tui.merge_networks(client.get_networks(&chains).await.unwrap());
+
+    let now = Instant::now();
+    let new: Vec<&str> = tui
+        .networks()
+        .into_iter()
+        .filter(|n| n.is_new(now))
+        .map(|n| n.name.as_str())
+        .collect();
+    assert_eq!(new, ["mode"]);
+    assert!(!tui
+        .network("mode")
+        .unwrap()
+        .is_new(now + NEW_BADGE_DURATION));
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert_eq!(screen.matches(NEW_BADGE).count(), 1);
+    assert!(screen.contains(&format!("Mode{NEW_BADGE}")));
+}
+
+#[test]
+fn next_refresh_is_counted_down_on_the_footer() {
+    let ago = |secs| {
+        Instant::now()
+            .checked_sub(Duration::from_secs(secs))
+            .unwrap()
+    };
+    assert_eq!(roller::next_refresh_countdown(ago(15), 60), 45);
+    assert_eq!(roller::next_refresh_countdown(ago(90), 60), 0);
+
+    let screen = |tui: &mut roller::Tui| -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|f| tui.render(f)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect()
+    };
+
+    let mut tui = common::tui();
+    assert_eq!(tui.refresh_countdown(), None);
+    assert!(!screen(&mut tui).contains("Next refresh"));
+
+    let config = Config::try_parse_from(["roller", "--refresh-metadata", "1m"]).unwrap();
+    let mut tui = common::tui().with_config(&config);
+    tui.last_refresh_attempt = ago(15);
+    assert!(screen(&mut tui).contains("Next refresh in: 45s"));
+
+    tui.refreshing = true;
+    assert!(screen(&mut tui).contains("Refreshing metadata…"));
+}
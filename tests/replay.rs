@@ -0,0 +1,94 @@
+mod common;
+
+use clap::{error::ErrorKind, Parser};
+use common::MockServer;
+use roller::{Config, Recording};
+use std::{path::PathBuf, process::Output};
+
+/// A path in the temporary directory, unique to the test process and the given name.
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("roller-{}-{name}", std::process::id()))
+}
+
+/// Runs the roller binary against an API that would fail any metadata request,
+/// so replays only succeed without network access.
+fn replay(args: &[&str]) -> Output {
+    let server = MockServer::start("null", vec![]);
+    common::roller(&server, args)
+}
+
+#[test]
+fn record_and_replay_are_mutually_exclusive() {
+    let error = Config::try_parse_from(["roller", "--record", "a", "--replay", "b"]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ArgumentConflict);
+}
+
+#[test]
+fn replay_options_require_replay() {
+    for args in [["--replay-speed", "5"], ["--metadata", "m.json"]] {
+        let error = Config::try_parse_from(["roller"].into_iter().chain(args)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    let config =
+        Config::try_parse_from(["roller", "--replay", "in.ndjson", "--replay-speed", "5"]).unwrap();
+    assert_eq!(config.replay, Some(PathBuf::from("in.ndjson")));
+    assert_eq!(config.replay_speed, 5.0);
+
+    for speed in ["0", "-1", "fast"] {
+        let result = Config::try_parse_from(["roller", "--replay", "in", "--replay-speed", speed]);
+        assert!(result.is_err(), "accepted a replay speed of {speed}");
+    }
+}
+
+#[test]
+fn recorded_session_replays_without_network_access() {
+    let path = temp_path("session.ndjson");
+    let server = MockServer::start(&common::metadata(), common::events());
+    let recorded = common::roller(&server, &["--once", "--record", path.to_str().unwrap()]);
+    assert!(recorded.status.success());
+
+    let recording = Recording::read(&path).unwrap();
+    assert!(recording.metadata.is_some());
+    assert_eq!(recording.events.len(), 3);
+
+    let replayed = replay(&["--once", "--replay", path.to_str().unwrap()]);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(replayed.status.success());
+    assert_eq!(replayed.stdout, recorded.stdout);
+}
+
+#[test]
+fn replay_without_header_falls_back_to_the_metadata_file() {
+    let recording = temp_path("headless.ndjson");
+    let metadata = temp_path("metadata.json");
+    let events: String = common::events()
+        .into_iter()
+        .map(|(event, data)| {
+            let line = serde_json::json!({ "offsetMs": 0, "event": event, "data": data });
+            format!("{line}\n")
+        })
+        .collect();
+    std::fs::write(&recording, events).unwrap();
+    std::fs::write(&metadata, common::metadata()).unwrap();
+
+    let without_metadata = replay(&["--once", "--replay", recording.to_str().unwrap()]);
+    let with_metadata = replay(&[
+        "--once",
+        "--replay",
+        recording.to_str().unwrap(),
+        "--metadata",
+        metadata.to_str().unwrap(),
+    ]);
+    std::fs::remove_file(&recording).unwrap();
+    std::fs::remove_file(&metadata).unwrap();
+
+    assert!(!without_metadata.status.success());
+    let stderr = String::from_utf8(without_metadata.stderr).unwrap();
+    assert!(stderr.contains("--metadata"), "{stderr}");
+
+    assert!(with_metadata.status.success());
+    let stdout = String::from_utf8(with_metadata.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 4);
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use crossterm::event::KeyCode;
+use ratatui::{backend::TestBackend, style::Modifier, Terminal};
+use roller::{Theme, Tui};
+
+/// Types each character of the text into the Tui.
+fn type_text(tui: &mut Tui, text: &str) {
+    text.chars()
+        .for_each(|c| tui.handle_input(KeyCode::Char(c)));
+}
+
+/// Renders the Tui and returns the text of the info bar and the modifiers of its first query character.
+fn info_bar(tui: &mut Tui) -> (String, Modifier) {
+    let mut terminal = Terminal::new(TestBackend::new(200, 24)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+
+    // The line of text between the borders of the info bar, at the bottom of the frame.
+    let buffer = terminal.backend().buffer();
+    let text: String = (0..200)
+        .map(|x| buffer.get(x, 22).symbol().to_string())
+        .collect();
+    let query = text.find(": ").map_or(0, |i| text[..i + 2].chars().count()) as u16;
+    let text = text.trim_matches(|c: char| c.is_whitespace() || c == '│');
+    (text.to_string(), buffer.get(query, 22).modifier)
+}
+
+#[test]
+fn typing_filters_the_networks_in_real_time() {
+    let mut tui = common::tui();
+    tui.handle_input(KeyCode::Char('/'));
+    type_text(&mut tui, "A");
+
+    // Matches the labels of every network, ignoring case.
+    assert_eq!(tui.visible_networks().len(), 3);
+    let (text, _) = info_bar(&mut tui);
+    assert_eq!(text, "Filter: A | [Esc] clear | [Enter] lock | 3 matches");
+
+    type_text(&mut tui, "i");
+    assert_eq!(
+        info_bar(&mut tui).0,
+        "Filter: Ai | [Esc] clear | [Enter] lock | 1 matches"
+    );
+
+    tui.handle_input(KeyCode::Backspace);
+    assert_eq!(tui.visible_networks().len(), 3);
+
+    // Sorting and quitting keys are typed into the query instead.
+    type_text(&mut tui, "gq");
+    assert_eq!(tui.filter_query.as_deref(), Some("Agq"));
+    assert!(tui.is_typing());
+}
+
+#[test]
+fn enter_locks_the_filter_and_esc_clears_it() {
+    let mut tui = common::tui();
+    tui.display.theme = Theme::monochrome();
+    tui.handle_input(KeyCode::Char('/'));
+    type_text(&mut tui, "zora");
+    assert_eq!(
+        info_bar(&mut tui).1,
+        Theme::monochrome().filter.add_modifier
+    );
+
+    tui.handle_input(KeyCode::Enter);
+    assert!(!tui.is_typing());
+    let (text, modifier) = info_bar(&mut tui);
+    assert_eq!(text, "Locked filter: zora | [Esc] clear");
+    assert_eq!(modifier, Theme::monochrome().filter_locked.add_modifier);
+
+    // Keys return to their usual actions while the filter stays applied.
+    tui.handle_input(KeyCode::Enter);
+    assert!(tui.detail_open);
+    assert_eq!(tui.visible_networks()[0].name, "zora");
+
+    // The first Esc closes the detail pane, the second clears the filter.
+    tui.handle_input(KeyCode::Esc);
+    assert_eq!(tui.visible_networks().len(), 1);
+    tui.handle_input(KeyCode::Esc);
+    assert_eq!(tui.filter_query, None);
+    assert_eq!(tui.visible_networks().len(), 3);
+}
+
+#[test]
+fn empty_table_explains_why() {
+    let mut tui = common::tui();
+    assert_eq!(tui.empty_table_message(), None);
+
+    tui.handle_input(KeyCode::Char('/'));
+    type_text(&mut tui, "optimism");
+    let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+    // The first line inside the table, below the summary line, the border and the header.
+    let line: String = (0..100)
+        .map(|x| terminal.backend().buffer().get(x, 3).symbol().to_string())
+        .collect();
+    assert_eq!(
+        line.trim_matches(|c: char| c.is_whitespace() || c == '│'),
+        "No networks match filter 'optimism' — press Esc to clear."
+    );
+
+    // Without networks, the command-line filters are told apart from an empty metadata.
+    let mut tui = Tui::new(Vec::new());
+    assert_eq!(
+        tui.empty_table_message().as_deref(),
+        Some("No networks available.")
+    );
+    tui.hidden_networks = 3;
+    assert!(tui
+        .empty_table_message()
+        .unwrap()
+        .starts_with("No networks match the command-line filters, 3 hidden"));
+}
@@ -0,0 +1,352 @@
+mod common;
+
+use clap::{Parser, ValueEnum};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::{backend::TestBackend, style::Modifier, Terminal};
+use roller::{format::Units, Column, Config, SortDirection, SortingStrategy, Tui};
+use std::time::{Duration, Instant};
+
+/// Presses and releases a key.
+fn press(tui: &mut Tui, key: char) {
+    tui.handle_input(KeyCode::Char(key));
+    tui.handle_key(key_event(key, KeyEventKind::Release));
+}
+
+/// A key event of the given kind, without modifiers.
+fn key_event(key: char, kind: KeyEventKind) -> KeyEvent {
+    KeyEvent::new_with_kind(KeyCode::Char(key), KeyModifiers::NONE, kind)
+}
+
+/// Presses a key at the given time since `start`, as a terminal without key releases reports it.
+fn press_at(tui: &mut Tui, key: char, start: Instant, millis: u64) {
+    let event = key_event(key, KeyEventKind::Press);
+    tui.handle_key_at(event, start + Duration::from_millis(millis));
+}
+
+/// The names of the networks, in table order.
+fn order(tui: &Tui) -> Vec<&str> {
+    tui.networks().iter().map(|n| n.name.as_str()).collect()
+}
+
+#[test]
+fn pressing_the_current_sort_key_reverses_the_direction() {
+    let mut tui = common::tui();
+    assert_eq!(order(&tui), ["base", "xai", "zora"]);
+
+    press(&mut tui, 'g');
+    assert_eq!(
+        tui.sort_direction(SortingStrategy::Gps),
+        SortDirection::Ascending
+    );
+    assert_eq!(order(&tui), ["zora", "xai", "base"]);
+
+    press(&mut tui, 'g');
+    assert_eq!(order(&tui), ["base", "xai", "zora"]);
+}
+
+#[test]
+fn each_strategy_remembers_its_direction() {
+    let mut tui = common::tui();
+
+    // Reverse TPS, then switch to GPS, which keeps its default direction.
+    press(&mut tui, 't');
+    press(&mut tui, 't');
+    assert_eq!(order(&tui), ["zora", "xai", "base"]);
+    press(&mut tui, 'g');
+    assert_eq!(order(&tui), ["base", "xai", "zora"]);
+
+    // Switching back to TPS restores the ascending direction.
+    press(&mut tui, 't');
+    assert_eq!(
+        tui.sort_direction(SortingStrategy::Tps),
+        SortDirection::Ascending
+    );
+    assert_eq!(order(&tui), ["zora", "xai", "base"]);
+}
+
+#[test]
+fn held_sort_key_is_previewed_until_released() {
+    let mut tui = common::tui();
+    let start = Instant::now();
+
+    // Holding `t` produces repeats within the key repeat window, which keep the preview.
+    for i in 0..3 {
+        press_at(&mut tui, 't', start, i * 30);
+        assert_eq!(tui.sort_preview, Some(SortingStrategy::Tps));
+        assert!(matches!(tui.sorting_strategy, SortingStrategy::Gps));
+    }
+
+    // While the key is held, the dimmed preview replaces the table.
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let title: String = (0..80).map(|x| buffer.get(x, 1).symbol()).collect();
+    assert!(title.contains("sort preview"), "{title}");
+    assert!(buffer.get(3, 3).modifier.contains(Modifier::DIM));
+
+    // The key is released once it stops repeating.
+    assert!(!tui.release_keys_at(start + Duration::from_millis(100)));
+    assert!(tui.release_keys_at(start + Duration::from_millis(200)));
+    assert_eq!(tui.sort_preview, None);
+    assert!(matches!(tui.sorting_strategy, SortingStrategy::Tps));
+    assert_eq!(order(&tui), ["base", "xai", "zora"]);
+}
+
+#[test]
+fn holding_a_sort_key_past_the_repeat_delay_keeps_its_direction() {
+    let mut tui = common::tui();
+    let start = Instant::now();
+
+    // The first press waits for the key repeat to start, a few hundred milliseconds later,
+    // while the main loop keeps looking for released keys.
+    press_at(&mut tui, 't', start, 0);
+    for millis in [100, 200, 300, 400] {
+        assert!(!tui.release_keys_at(start + Duration::from_millis(millis)));
+        assert_eq!(tui.sort_preview, Some(SortingStrategy::Tps));
+    }
+
+    // The repeats keep the same preview, rather than reselecting the strategy.
+    for i in 0..5 {
+        press_at(&mut tui, 't', start, 500 + i * 33);
+        assert_eq!(tui.sort_preview, Some(SortingStrategy::Tps));
+    }
+
+    // Released once the repeats stop, selecting TPS in its default direction.
+    assert!(tui.release_keys_at(start + Duration::from_millis(700)));
+    assert!(matches!(tui.sorting_strategy, SortingStrategy::Tps));
+    assert_eq!(
+        tui.sort_direction(SortingStrategy::Tps),
+        SortDirection::Descending
+    );
+
+    // A late repeat reported after the preview was committed doesn't reverse it either.
+    let event = key_event('t', KeyEventKind::Repeat);
+    tui.handle_key_at(event, start + Duration::from_millis(720));
+    assert_eq!(tui.sort_preview, None);
+    assert_eq!(
+        tui.sort_direction(SortingStrategy::Tps),
+        SortDirection::Descending
+    );
+}
+
+#[test]
+fn reported_key_releases_end_the_preview() {
+    let mut tui = common::tui();
+    tui.key_releases = true;
+    let start = Instant::now();
+
+    // Reported repeats keep the preview however long the key is held.
+    press_at(&mut tui, 't', start, 0);
+    for i in 1..4 {
+        let event = key_event('t', KeyEventKind::Repeat);
+        tui.handle_key_at(event, start + Duration::from_millis(i * 500));
+        assert_eq!(tui.sort_preview, Some(SortingStrategy::Tps));
+    }
+    assert!(!tui.release_keys_at(start + Duration::from_secs(10)));
+
+    // The release commits it right away.
+    let release = key_event('t', KeyEventKind::Release);
+    tui.handle_key_at(release, start + Duration::from_millis(1600));
+    assert_eq!(tui.sort_preview, None);
+    assert!(matches!(tui.sorting_strategy, SortingStrategy::Tps));
+
+    // A quick second press is a new one, reversing the direction.
+    press_at(&mut tui, 't', start, 1650);
+    tui.handle_key_at(release, start + Duration::from_millis(1700));
+    assert_eq!(
+        tui.sort_direction(SortingStrategy::Tps),
+        SortDirection::Ascending
+    );
+}
+
+#[test]
+fn sort_tooltips_name_the_direction_of_the_next_press() {
+    let mut tui = common::tui();
+    let tooltip = |tui: &Tui, short| {
+        let segments = tui.key_hint_segments();
+        let segment = segments.into_iter().find(|s| s.short == short).unwrap();
+        segment.tooltip
+    };
+    assert_eq!(
+        tooltip(&tui, "gas per second"),
+        "Reverse the sort by Gas Per Second (GPS), ascending"
+    );
+    assert_eq!(
+        tooltip(&tui, "txs per second"),
+        "Sort by Transactions Per Second (TPS), descending"
+    );
+
+    // Each strategy is named with the direction it was last used with.
+    press(&mut tui, 't');
+    press(&mut tui, 't');
+    press(&mut tui, 'g');
+    assert_eq!(
+        tooltip(&tui, "txs per second"),
+        "Sort by Transactions Per Second (TPS), ascending"
+    );
+    assert_eq!(
+        tooltip(&tui, "gas per second"),
+        "Reverse the sort by Gas Per Second (GPS), ascending"
+    );
+}
+
+#[test]
+fn a_different_key_commits_the_preview() {
+    let mut tui = common::tui();
+
+    tui.handle_input(KeyCode::Char('t'));
+    tui.handle_input(KeyCode::Char('k'));
+    assert!(matches!(tui.sorting_strategy, SortingStrategy::Tps));
+    assert_eq!(tui.sort_preview, Some(SortingStrategy::Dps));
+
+    tui.handle_input(KeyCode::Down);
+    assert!(matches!(tui.sorting_strategy, SortingStrategy::Dps));
+    assert_eq!(tui.sort_preview, None);
+}
+
+#[test]
+fn gas_per_tx_ranks_idle_networks_last() {
+    let mut tui = common::tui();
+    // An idle network without transactions still reporting gas.
+    tui.update_networks(common::sse_event(common::event("xai", 301, 0.0, 9.0, 1.0)));
+
+    press(&mut tui, 'a');
+    assert!(matches!(tui.sorting_strategy, SortingStrategy::GasPerTx));
+    assert_eq!(order(&tui), ["base", "zora", "xai"]);
+
+    // 20.25 MGas/s over 12.5 tx/s and the guarded division of the idle network.
+    assert_eq!(
+        Column::GasPerTx.value(tui.networks()[0], 2, Units::Raw),
+        "1620.00"
+    );
+    assert_eq!(
+        Column::GasPerTx.value(tui.networks()[2], 2, Units::Raw),
+        "0.00"
+    );
+}
+
+#[test]
+fn updates_keep_the_order_of_a_full_sort() {
+    let mut tui = common::tui();
+    press(&mut tui, 't');
+    press(&mut tui, 't');
+
+    // Including updates that tie with other networks, or leave their metric unchanged.
+    for (name, tps) in [
+        ("base", 0.5),
+        ("zora", 7.0),
+        ("xai", 7.0),
+        ("base", 7.0),
+        ("zora", 30.0),
+    ] {
+        tui.update_networks(common::sse_event(common::event(name, 1, tps, 1.0, 1.0)));
+
+        let incremental = order(&tui).join(",");
+        tui.sort_networks();
+        assert_eq!(order(&tui).join(","), incremental);
+        for (i, network) in tui.networks().iter().enumerate() {
+            assert_eq!(tui.position(&network.name), Some(i));
+        }
+    }
+    assert_eq!(tui.position("optimism"), None);
+}
+
+/// The position of a strategy in `SortingStrategy::ALL`.
+///
+/// The match has no wildcard, so adding a variant fails to compile until it's listed here,
+/// and the test below fails until it's listed in `ALL` too.
+fn position(strategy: SortingStrategy) -> usize {
+    match strategy {
+        SortingStrategy::Gps => 0,
+        SortingStrategy::Tps => 1,
+        SortingStrategy::Dps => 2,
+        SortingStrategy::PendingTxCount => 3,
+        SortingStrategy::GasPerTx => 4,
+    }
+}
+
+#[test]
+fn every_strategy_sorts_and_has_a_key() {
+    for (i, strategy) in SortingStrategy::ALL.into_iter().enumerate() {
+        assert_eq!(position(strategy), i);
+        assert_eq!(strategy.action().sorting_strategy(), Some(strategy));
+
+        let mut tui = common::tui();
+        tui.sorting_strategy = strategy;
+        tui.sort_networks();
+        assert_eq!(tui.networks().len(), 3);
+    }
+}
+
+#[test]
+fn strategies_round_trip_through_their_names() {
+    for strategy in SortingStrategy::ALL {
+        let name = strategy.to_possible_value().unwrap().get_name().to_string();
+        assert_eq!(SortingStrategy::from_str(&name, false), Ok(strategy));
+
+        let json = serde_json::to_string(&strategy).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SortingStrategy>(&json).unwrap(),
+            strategy
+        );
+    }
+}
+
+#[test]
+fn invalid_metrics_are_replaced_with_zero() {
+    let mut tui = common::tui();
+    tui.update_networks(common::sse_event(common::event(
+        "zora",
+        201,
+        f32::NAN,
+        f32::INFINITY,
+        0.5,
+    )));
+    tui.update_networks(common::sse_event(common::event(
+        "base", 101, -12.5, -20.25, 3.5,
+    )));
+
+    let zora = tui.network("zora").unwrap().data.clone().unwrap();
+    assert_eq!((zora.tps, zora.gps, zora.dps), (0.0, 0.0, 0.5));
+    let base = tui.network("base").unwrap().data.clone().unwrap();
+    assert_eq!((base.tps, base.gps, base.dps), (0.0, 0.0, 3.5));
+    assert_eq!(tui.event_counts.parsed, 5);
+
+    // Ranked as idle, in the same order after a full sort, and last in either direction.
+    assert_eq!(order(&tui)[0], "xai");
+    let updated: Vec<String> = order(&tui).into_iter().map(String::from).collect();
+    tui.sort_networks();
+    assert_eq!(order(&tui), updated);
+    press(&mut tui, 't');
+    press(&mut tui, 't');
+    assert_eq!(order(&tui)[2], "xai");
+
+    let cell = Column::Tps.value(tui.network("zora").unwrap(), 2, Units::Raw);
+    assert_eq!(cell, "0.00");
+}
+
+#[test]
+fn top_networks_follow_the_live_standings() {
+    let config = Config::try_parse_from(["roller", "--top", "2"]).unwrap();
+    let mut tui = common::tui().with_config(&config);
+    let visible = |tui: &Tui| -> Vec<String> {
+        tui.visible_networks()
+            .iter()
+            .map(|n| n.name.clone())
+            .collect()
+    };
+    assert_eq!(visible(&tui), ["base", "xai"]);
+
+    // The selection stops at the last of the top networks.
+    tui.handle_input(KeyCode::End);
+    tui.handle_input(KeyCode::Down);
+    assert_eq!(tui.selected.selected(), Some(1));
+
+    // A network overtaking the others enters the top, pushing the last one out.
+    tui.update_networks(common::sse_event(common::event(
+        "zora", 201, 2.5, 50.0, 0.5,
+    )));
+    assert_eq!(visible(&tui), ["zora", "base"]);
+    tui.handle_input(KeyCode::Down);
+    assert_eq!(tui.selected.selected(), Some(1));
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use clap::Parser;
+use ratatui::{backend::TestBackend, style::Modifier, Terminal};
+use roller::{Config, Theme, Tui};
+use std::time::{Duration, Instant};
+
+/// Checks whether the row of a network is rendered dimmed as of the given time.
+fn dimmed(tui: &mut Tui, label: &str, now: Instant) -> bool {
+    let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    terminal.draw(|f| tui.render_at(f, now)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let y = (0..20)
+        .find(|y| {
+            let line: String = (0..120).map(|x| buffer.get(x, *y).symbol()).collect();
+            line.contains(label)
+        })
+        .unwrap();
+    let line: Vec<&str> = (0..120).map(|x| buffer.get(x, y).symbol()).collect();
+    let x = line.concat().find(label).unwrap();
+    let x = line.concat()[..x].chars().count() as u16;
+    buffer.get(x, y).modifier.contains(Modifier::DIM)
+}
+
+#[test]
+fn stale_threshold_is_parsed_with_a_minimum() {
+    let threshold = |args: &[&str]| {
+        Config::try_parse_from(std::iter::once(&"roller").chain(args))
+            .map(|config| config.stale_threshold())
+    };
+    assert_eq!(threshold(&[]).unwrap(), Some(Duration::from_secs(60)));
+    assert_eq!(
+        threshold(&["--stale-threshold", "90s"]).unwrap(),
+        Some(Duration::from_secs(90))
+    );
+    assert_eq!(threshold(&["--stale-threshold", "off"]).unwrap(), None);
+    assert_eq!(threshold(&["--stale-threshold", "0"]).unwrap(), None);
+    assert!(threshold(&["--stale-threshold", "2s"]).is_err());
+}
+
+#[test]
+fn threshold_is_respected_with_an_injected_clock() {
+    let tui = common::tui();
+    let base = tui.network("base").unwrap();
+    let updated_at = base.updated_at.unwrap();
+    let threshold = Some(Duration::from_secs(90));
+
+    assert!(!base.is_stale(threshold, updated_at + Duration::from_secs(89)));
+    assert!(base.is_stale(threshold, updated_at + Duration::from_secs(90)));
+    assert!(!base.is_stale(None, updated_at + Duration::from_secs(3600)));
+}
+
+#[test]
+fn off_disables_the_dimming_of_stale_rows() {
+    let config = Config::try_parse_from(["roller", "--stale-threshold", "off"]).unwrap();
+    let mut tui = common::tui().with_config(&config);
+    tui.display.theme = Theme::monochrome();
+    tui.last_updated = None;
+    // Zora was last updated 10 minutes before the render, Xai 10 seconds before.
+    let updated_at = tui.network("zora").unwrap().updated_at.unwrap();
+    let now = updated_at + Duration::from_secs(600);
+    tui.network_mut("xai").unwrap().updated_at = Some(updated_at + Duration::from_secs(590));
+    assert!(!dimmed(&mut tui, "Zora", now));
+
+    tui.display.stale_after = Some(Duration::from_secs(60));
+    assert!(dimmed(&mut tui, "Zora", now));
+    // Recently updated networks aren't dimmed.
+    assert!(!dimmed(&mut tui, "Xai", now));
+}
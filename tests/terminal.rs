@@ -0,0 +1,373 @@
+mod common;
+
+use clap::Parser;
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture, KeyModifiers, MouseEvent, MouseEventKind},
+    execute,
+};
+use ratatui::backend::TestBackend;
+use roller::{
+    output::{FollowWriter, OutputFormat},
+    Column, Config, EnterOptions, Terminal, Tui, UiOutput, MIN_TERMINAL_SIZE,
+};
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+/// A writer keeping what's written in a shared buffer, readable after the terminal took it.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn terminal_renders_to_any_writer() {
+    let buffer = SharedBuffer::default();
+    let mut terminal = Terminal::with_writer(buffer.clone(), 120, 20).unwrap();
+    let mut tui = common::tui();
+    let frame = terminal.draw(|f| tui.render(f)).unwrap();
+    assert_eq!((frame.area.width, frame.area.height), (120, 20));
+
+    let output = String::from_utf8_lossy(&buffer.0.lock().unwrap()).into_owned();
+    for text in ["Network", "Base", "Zora"] {
+        assert!(output.contains(text), "{text}: {output:?}");
+    }
+
+    // Exiting doesn't touch the terminal mode, which was never entered.
+    terminal.exit().unwrap();
+}
+
+#[test]
+fn mouse_capture_is_opt_in() {
+    let commands = |options: EnterOptions| {
+        let (mut enter, mut exit) = (Vec::new(), Vec::new());
+        options.write_enter(&mut enter).unwrap();
+        options.write_exit(&mut exit).unwrap();
+        (
+            String::from_utf8(enter).unwrap(),
+            String::from_utf8(exit).unwrap(),
+        )
+    };
+    let mut captured = Vec::new();
+    execute!(captured, EnableMouseCapture).unwrap();
+    let captured = String::from_utf8(captured).unwrap();
+    let mut released = Vec::new();
+    execute!(released, DisableMouseCapture).unwrap();
+    let released = String::from_utf8(released).unwrap();
+    let alternate = "\x1b[?1049h";
+
+    // Without --mouse, the capture is released on enter as before, and exiting only leaves the screen.
+    let (enter, exit) = commands(EnterOptions::default());
+    assert_eq!(enter, format!("{alternate}{released}"));
+    assert_eq!(exit, "\x1b[?1049l");
+
+    let mouse = EnterOptions {
+        mouse: true,
+        ..Default::default()
+    };
+    let (enter, exit) = commands(mouse);
+    assert_eq!(enter, format!("{alternate}{captured}"));
+    assert_eq!(exit, format!("{released}\x1b[?1049l"));
+
+    // An inline viewport stays on the main screen.
+    let inline = EnterOptions {
+        inline: Some(12),
+        ..mouse
+    };
+    assert_eq!(commands(inline), (captured, released.clone()));
+    let inline = EnterOptions {
+        inline: Some(12),
+        ..Default::default()
+    };
+    assert_eq!(commands(inline).1, "");
+
+    // The release of keys is asked for last and reverted first.
+    let key_releases = EnterOptions {
+        key_releases: true,
+        ..Default::default()
+    };
+    let (enter, exit) = commands(key_releases);
+    assert_eq!(enter, format!("{alternate}{released}\x1b[>2u"));
+    assert_eq!(exit, "\x1b[<1u\x1b[?1049l");
+}
+
+#[test]
+fn mouse_wheel_moves_the_selection() {
+    let mut tui = common::tui();
+    let event = |kind| MouseEvent {
+        kind,
+        column: 0,
+        row: 0,
+        modifiers: KeyModifiers::NONE,
+    };
+    assert!(tui.handle_mouse(event(MouseEventKind::ScrollDown)));
+    assert_eq!(tui.selected.selected(), Some(1));
+    assert!(tui.handle_mouse(event(MouseEventKind::ScrollUp)));
+    assert_eq!(tui.selected.selected(), Some(0));
+    assert!(!tui.handle_mouse(event(MouseEventKind::Moved)));
+}
+
+#[test]
+fn hovering_a_key_hint_shows_its_tooltip() {
+    let mut tui = common::tui();
+    let mut terminal = ratatui::Terminal::new(TestBackend::new(320, 20)).unwrap();
+    let screen = |terminal: &mut ratatui::Terminal<TestBackend>, tui: &mut Tui| {
+        terminal.draw(|f| tui.render(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..20)
+            .map(|y| (0..320).map(|x| buffer.get(x, y).symbol()).collect())
+            .collect::<Vec<String>>()
+    };
+    let event = |column, row| MouseEvent {
+        kind: MouseEventKind::Moved,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    };
+
+    let rows = screen(&mut terminal, &mut tui);
+    let index = rows[18].find("[g] gas per second").unwrap();
+    let x = rows[18][..index].chars().count() as u16;
+    // GPS being the current strategy, its key reverses the direction.
+    let tooltip = "Reverse the sort by Gas Per Second (GPS), ascending";
+    assert!(tui.handle_mouse(event(x + 4, 18)));
+    // Moving within the same hint keeps the tooltip as it is.
+    assert!(!tui.handle_mouse(event(x + 5, 18)));
+    let rows = screen(&mut terminal, &mut tui);
+    assert!(
+        rows[14..17].iter().any(|row| row.contains(tooltip)),
+        "{rows:#?}"
+    );
+
+    assert!(tui.handle_mouse(event(x, 10)));
+    let rows = screen(&mut terminal, &mut tui);
+    assert!(!rows.iter().any(|row| row.contains(tooltip)));
+}
+
+#[test]
+fn resizing_renders_at_every_size() {
+    let mut tui = common::tui();
+    let mut terminal = ratatui::Terminal::new(TestBackend::new(320, 20)).unwrap();
+    let hover = MouseEvent {
+        kind: MouseEventKind::Moved,
+        column: 160,
+        row: 18,
+        modifiers: KeyModifiers::NONE,
+    };
+    terminal.draw(|f| tui.render(f)).unwrap();
+    assert!(tui.handle_mouse(hover));
+
+    let (min_width, min_height) = MIN_TERMINAL_SIZE;
+    let sizes = [
+        (80, 24),
+        (min_width - 1, min_height),
+        (min_width, min_height - 1),
+        (0, 0),
+        (1, 1),
+        (min_width, min_height),
+        (200, 50),
+        (320, 20),
+    ];
+    for (width, height) in sizes {
+        tui.resized();
+        assert_eq!(tui.hovered, None);
+        terminal.backend_mut().resize(width, height);
+        terminal.draw(|f| tui.render(f)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        let too_small = width < min_width || height < min_height;
+        // The info bar is laid out at the bottom of every frame large enough
+        assert_eq!(
+            screen.ends_with('╯'),
+            !too_small,
+            "{width}x{height}: {screen}"
+        );
+        if too_small && width >= 10 {
+            assert!(screen.contains("Terminal"), "{width}x{height}: {screen}");
+        }
+        // The hint hovered at first is only hit again once rendered back at the same size
+        let hovered = tui.handle_mouse(MouseEvent {
+            row: height.saturating_sub(2),
+            ..hover
+        });
+        assert_eq!(hovered, width == 320, "{width}x{height}");
+    }
+}
+
+#[test]
+fn restoring_is_safe_to_repeat() {
+    let options = EnterOptions {
+        mouse: true,
+        ..Default::default()
+    };
+    let restore = || {
+        let mut buffer = Vec::new();
+        options.restore(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    };
+
+    let restored = restore();
+    // Leaves the alternate screen and shows the cursor, without the raw mode having been enabled.
+    assert!(restored.contains("\x1b[?1049l"), "{restored:?}");
+    assert!(restored.ends_with("\x1b[?25h"), "{restored:?}");
+    assert_eq!(restore(), restored);
+}
+
+#[test]
+fn dropping_restores_the_terminal_once() {
+    let show_cursor = "\x1b[?25h";
+    let output =
+        |buffer: &SharedBuffer| String::from_utf8_lossy(&buffer.0.lock().unwrap()).into_owned();
+
+    // Dropped without exiting, as on an early return.
+    let buffer = SharedBuffer::default();
+    let terminal = Terminal::with_writer(buffer.clone(), 80, 10).unwrap();
+    assert!(!output(&buffer).contains(show_cursor));
+    drop(terminal);
+    assert!(
+        output(&buffer).ends_with(show_cursor),
+        "{:?}",
+        output(&buffer)
+    );
+
+    // Exited, then dropped without issuing the commands again.
+    let buffer = SharedBuffer::default();
+    let mut terminal = Terminal::with_writer(buffer.clone(), 80, 10).unwrap();
+    terminal.exit().unwrap();
+    let exited = output(&buffer);
+    assert_eq!(exited.matches(show_cursor).count(), 1);
+    terminal.exit().unwrap();
+    drop(terminal);
+    assert_eq!(output(&buffer), exited);
+}
+
+#[test]
+fn suspending_restores_the_terminal_until_resumed() {
+    let show_cursor = "\x1b[?25h";
+    // Terminals of a fixed size are cleared line by line, from the cursor down.
+    let clear = "\x1b[J";
+    let buffer = SharedBuffer::default();
+    let output = || String::from_utf8_lossy(&buffer.0.lock().unwrap()).into_owned();
+    let mut terminal = Terminal::with_writer(buffer.clone(), 80, 10).unwrap();
+    let mut tui = common::tui();
+    terminal.draw(|f| tui.render(f)).unwrap();
+
+    terminal.suspend().unwrap();
+    assert!(terminal.is_suspended());
+    assert!(output().ends_with(show_cursor), "{:?}", output());
+    // Suspending again has no effect.
+    let suspended = output();
+    terminal.suspend().unwrap();
+    assert_eq!(output(), suspended);
+
+    // Resuming clears the screen, so the next frame is drawn in full.
+    terminal.resume().unwrap();
+    assert!(!terminal.is_suspended());
+    assert!(output()[suspended.len()..].contains(clear));
+    let resumed = output().len();
+    terminal.draw(|f| tui.render(f)).unwrap();
+    assert!(output()[resumed..].contains("Base"));
+
+    // Once exited, the terminal can't be suspended or resumed.
+    terminal.suspend().unwrap();
+    terminal.exit().unwrap();
+    assert!(terminal.is_suspended());
+    let exited = output();
+    terminal.resume().unwrap();
+    terminal.suspend().unwrap();
+    drop(terminal);
+    assert_eq!(output(), exited);
+}
+
+#[test]
+fn inline_viewport_renders_within_its_height() {
+    let mut tui = common::tui();
+    let options = ratatui::TerminalOptions {
+        viewport: EnterOptions {
+            inline: Some(12),
+            ..Default::default()
+        }
+        .viewport(),
+    };
+    let mut terminal = ratatui::Terminal::with_options(TestBackend::new(100, 30), options).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let row = |y| {
+        (0..100)
+            .map(|x| buffer.get(x, y).symbol())
+            .collect::<String>()
+    };
+    // The info bar closes the viewport, the lines below are left to the shell.
+    assert!(row(11).starts_with('╰'), "{}", row(11));
+    assert!(row(3).contains("Base"), "{}", row(3));
+    assert!((12..30).all(|y| row(y).trim().is_empty()));
+}
+
+#[test]
+fn follow_records_stay_apart_from_the_interface() {
+    let (ui, data) = (SharedBuffer::default(), SharedBuffer::default());
+    let mut terminal = Terminal::with_writer(ui.clone(), 120, 20).unwrap();
+    let mut tui = common::tui();
+    let columns = [Column::Network, Column::Block];
+    let mut follow = FollowWriter::new(
+        data.clone(),
+        OutputFormat::Csv,
+        &columns,
+        tui.display.precision,
+        tui.display.units,
+    )
+    .unwrap();
+
+    for block in [101, 102] {
+        tui.update_networks(common::sse_event(common::event(
+            "base", block, 1.0, 1.0, 1.0,
+        )));
+        follow.write(&tui.top_networks(), "base").unwrap();
+        terminal.draw(|f| tui.render(f)).unwrap();
+    }
+
+    let records = String::from_utf8(data.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = records.lines().collect();
+    assert_eq!(lines.len(), 3, "{records:?}");
+    assert_eq!(lines[0], "timestamp,Network,Block");
+    assert!(lines[1].ends_with(",Base,101") && lines[2].ends_with(",Base,102"));
+    assert!(!records.contains('\x1b'));
+
+    let screen = String::from_utf8_lossy(&ui.0.lock().unwrap()).into_owned();
+    assert!(screen.contains('\x1b') && screen.contains("Base"));
+    assert!(!screen.contains("timestamp"));
+}
+
+#[test]
+fn interface_output_is_selectable() {
+    assert_eq!(EnterOptions::default().output, UiOutput::Stdout);
+    let config = Config::try_parse_from(["roller"]).unwrap();
+    assert_eq!(config.ui_output(), UiOutput::Stdout);
+    let config = Config::try_parse_from(["roller", "--ui-output", "stderr"]).unwrap();
+    assert_eq!(config.ui_output(), UiOutput::Stderr);
+    let config = Config::try_parse_from(["roller", "--follow", "--ui-output", "tty"]).unwrap();
+    assert_eq!(config.ui_output(), UiOutput::Tty);
+    assert!(Config::try_parse_from(["roller", "--ui-output", "file"]).is_err());
+
+    // The streams are written to through the same writer type.
+    assert!(matches!(
+        UiOutput::Stderr.open().unwrap(),
+        roller::UiWriter::Stderr(_)
+    ));
+}
@@ -0,0 +1,179 @@
+mod common;
+
+use clap::Parser;
+use ratatui::{
+    backend::TestBackend,
+    style::{Color, Modifier},
+    Terminal,
+};
+use roller::{stack_color, Config, HeaderStyle, Theme};
+#[cfg(not(feature = "no-color"))]
+use std::time::{Duration, Instant};
+
+/// Renders the Tui and returns the cells of the buffer.
+fn render(mut tui: roller::Tui) -> Vec<ratatui::buffer::Cell> {
+    let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    terminal.draw(|f| tui.render(f)).unwrap();
+    terminal.backend().buffer().content().to_vec()
+}
+
+#[test]
+fn monochrome_theme_renders_without_colors() {
+    let mut tui = common::tui();
+    tui.display.theme = Theme::monochrome();
+    tui.display.pending_warn = 0;
+    tui.detail_open = true;
+
+    for cell in render(tui) {
+        assert_eq!(cell.fg, Color::Reset, "{cell:?}");
+        assert_eq!(cell.bg, Color::Reset, "{cell:?}");
+    }
+}
+
+#[test]
+#[cfg(not(feature = "no-color"))]
+fn colored_theme_renders_with_colors() {
+    let mut tui = common::tui();
+    tui.display.theme = Theme::colored();
+
+    assert!(render(tui).iter().any(|cell| cell.fg != Color::Reset));
+}
+
+#[test]
+fn no_color_flag_selects_the_monochrome_theme() {
+    assert_eq!(Theme::detect(true), Theme::monochrome());
+}
+
+#[test]
+#[cfg(feature = "no-color")]
+fn no_color_builds_always_use_the_monochrome_theme() {
+    assert_eq!(Theme::detect(false), Theme::monochrome());
+    assert_eq!(Theme::default(), Theme::monochrome());
+}
+
+#[test]
+#[cfg(not(feature = "no-color"))]
+fn updated_row_flashes_then_fades() {
+    let theme = Theme::colored();
+    // The fg of the first cell of the second row, below the summary, the border and the header.
+    let row_fg = |elapsed: Duration| {
+        let mut tui = common::tui();
+        tui.display.theme = theme;
+        tui.last_updated = Some((tui.networks()[1].name.clone(), Instant::now() - elapsed));
+        let width = 120;
+        render(tui)[4 * width + 1].fg
+    };
+
+    assert_eq!(Some(row_fg(Duration::ZERO)), theme.flash.fg);
+    assert_eq!(
+        Some(row_fg(Duration::from_millis(300))),
+        theme.flash_fading.fg
+    );
+    assert_eq!(Some(row_fg(Duration::from_secs(1))), theme.base.fg);
+}
+
+#[test]
+#[cfg(not(feature = "no-color"))]
+fn unchanged_block_is_flagged_then_highlighted() {
+    let theme = Theme::colored();
+    // The first symbol of the Block cell in the second row, the one of `xai`.
+    let block_cell = |updates: u32| {
+        let mut tui = common::tui();
+        tui.display.theme = theme;
+        tui.display.stale_blocks = 2;
+        for _ in 0..updates {
+            tui.update_networks(common::sse_event(common::event(
+                "xai", 300, 7.0, 5.75, 1.25,
+            )));
+        }
+        // Skip the flash of the updated row.
+        tui.last_updated = None;
+        let cells = render(tui);
+        let block = (120 * 4..120 * 5)
+            .find(|&i| cells[i].symbol() == "3" || cells[i].symbol() == "⚠")
+            .unwrap();
+        (cells[block].symbol().to_string(), cells[block].fg)
+    };
+
+    assert_eq!(block_cell(2), (String::from("3"), theme.base.fg.unwrap()));
+    assert_eq!(
+        block_cell(3),
+        (String::from("⚠"), theme.caution.fg.unwrap())
+    );
+    assert_eq!(
+        block_cell(7),
+        (String::from("⚠"), theme.warning.fg.unwrap())
+    );
+}
+
+#[test]
+#[cfg(not(feature = "no-color"))]
+fn age_bars_fade_from_green_to_red() {
+    let theme = Theme::colored();
+    assert_eq!(theme.age(0.0).fg, Some(Color::Rgb(0, 230, 64)));
+    assert_eq!(theme.age(0.5).fg, Some(Color::Rgb(69, 115, 32)));
+    assert_eq!(theme.age(2.0).fg, Some(Color::Rgb(139, 0, 0)));
+
+    // Without colors, only full bars stand out.
+    let theme = Theme::monochrome();
+    assert_eq!(theme.age(0.5), theme.age_fresh);
+    assert_eq!(theme.age(1.0), theme.age_stale);
+}
+
+#[test]
+fn header_style_is_configurable() {
+    assert_eq!(Modifier::from(HeaderStyle::Bold), Modifier::BOLD);
+    assert_eq!(Modifier::from(HeaderStyle::None), Modifier::empty());
+
+    for (style, expected) in [
+        ("underline", Modifier::UNDERLINED),
+        ("bold", Modifier::BOLD),
+        ("none", Modifier::empty()),
+    ] {
+        let config = Config::try_parse_from(["roller", "--header-style", style]).unwrap();
+        let mut tui = common::tui().with_config(&config);
+        tui.display.theme = Theme::monochrome();
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|f| tui.render(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let header = (0..20)
+            .flat_map(|y| (0..120).map(move |x| (x, y)))
+            .find(|(x, y)| buffer.get(*x, *y).symbol() == "N")
+            .unwrap();
+        assert_eq!(buffer.get(header.0, header.1).modifier, expected, "{style}");
+    }
+    assert_eq!(
+        Config::try_parse_from(["roller"]).unwrap().header_style,
+        HeaderStyle::Underline
+    );
+}
+
+#[test]
+fn known_stacks_have_their_color() {
+    assert_eq!(stack_color("OP Stack"), Color::Red);
+    assert_eq!(stack_color("op"), Color::Red);
+    assert_eq!(stack_color("Arbitrum Nitro"), Color::Blue);
+    assert_eq!(stack_color("orbit"), Color::Blue);
+    assert_eq!(stack_color("zkEVM"), Color::Magenta);
+    assert_eq!(stack_color("Polygon CDK"), Color::Rgb(143, 0, 255));
+    assert_eq!(stack_color("cdk"), Color::Rgb(143, 0, 255));
+    assert_eq!(stack_color("starknet"), Color::Gray);
+    assert_eq!(stack_color(""), Color::Gray);
+}
+
+#[test]
+#[cfg(not(feature = "no-color"))]
+fn stack_cells_are_colored_unless_disabled() {
+    // The color of the "orbit" cell of Xai, which isn't the selected row.
+    let orbit = |args: &[&str]| {
+        let config = Config::try_parse_from(args).unwrap();
+        let mut tui = common::tui().with_config(&config);
+        tui.display.theme = Theme::colored();
+        let cells = render(tui);
+        let text: String = cells.iter().map(|c| c.symbol()).collect();
+        let index = text.find("orbit").unwrap();
+        cells[text[..index].chars().count()].fg
+    };
+    assert_eq!(orbit(&["roller"]), Color::Blue);
+    assert_ne!(orbit(&["roller", "--no-stack-colors"]), Color::Blue);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use roller::{Column, Level, Section, Threshold, Thresholds};
+
+const CONFIG: &str = "\
+[thresholds]
+tps = { warn = 1.0, high = 20.0 }
+gps = { warn = 5.0, high = 100.0 }
+
+[thresholds.networks.base]
+tps = { warn = 10, high = 50 }
+";
+
+#[test]
+fn metrics_have_no_thresholds_by_default() {
+    let thresholds = Thresholds::default();
+    let tui = common::tui();
+    let base = tui.network("base").unwrap();
+    assert_eq!(thresholds.get("base", Column::Tps), None);
+    assert_eq!(thresholds.level(base, Column::Tps), Level::Normal);
+    assert!(thresholds.alerts(base).is_empty());
+}
+
+#[test]
+fn networks_override_the_thresholds() {
+    let mut thresholds = Thresholds::default();
+    thresholds.extend_from_str(CONFIG).unwrap();
+    let threshold = |warn, high| Some(Threshold { warn, high });
+    assert_eq!(thresholds.get("zora", Column::Tps), threshold(1.0, 20.0));
+    assert_eq!(thresholds.get("base", Column::Tps), threshold(10.0, 50.0));
+    // Metrics missing from the section of a network keep the global thresholds.
+    assert_eq!(thresholds.get("base", Column::Gps), threshold(5.0, 100.0));
+    assert_eq!(thresholds.get("base", Column::Dps), None);
+}
+
+#[test]
+fn alerts_fire_at_the_boundaries() {
+    let threshold = Threshold {
+        warn: 1.0,
+        high: 20.0,
+    };
+    assert_eq!(threshold.level(0.99), Level::Normal);
+    assert_eq!(threshold.level(1.0), Level::Warn);
+    assert_eq!(threshold.level(19.99), Level::Warn);
+    assert_eq!(threshold.level(20.0), Level::High);
+
+    let mut thresholds = Thresholds::default();
+    thresholds
+        .extend_from_str("[thresholds]\ntps = { warn = 2.5, high = 12.5 }\n")
+        .unwrap();
+    let tui = common::tui();
+    let alert = |name| {
+        let alerts = thresholds.alerts(tui.network(name).unwrap());
+        alerts.iter().map(ToString::to_string).collect::<Vec<_>>()
+    };
+    // The TPS of Zora is 2.5, of Xai 7 and of Base 12.5.
+    assert_eq!(alert("zora"), ["TPS warn at 2.5"]);
+    assert_eq!(alert("xai"), ["TPS warn at 7"]);
+    assert_eq!(alert("base"), ["TPS high at 12.5"]);
+}
+
+#[test]
+fn invalid_thresholds_are_rejected() {
+    let error = |contents: &str| Thresholds::default().extend_from_str(contents).unwrap_err();
+    assert_eq!(
+        error("[thresholds]\ntps = { warn = 20, high = 1 }\n"),
+        "2: the warn threshold of `tps` must be below its high threshold"
+    );
+    assert_eq!(
+        error("[thresholds.networks.base]\ngps = { warn = -1, high = 1 }\n"),
+        "2: the thresholds of `gps` must be non-negative numbers"
+    );
+    assert_eq!(
+        error("[thresholds]\nblock = { warn = 1, high = 2 }\n"),
+        "2: unknown metric `block`, expected one of tps, gps, dps, gas-per-tx"
+    );
+    assert_eq!(
+        error("[thresholds]\ntps = { warn = 1 }\n"),
+        "2: expected `metric = { warn = number, high = number }`, got `tps = { warn = 1 }`"
+    );
+    assert_eq!(
+        error("[thresholds.networks.]\n"),
+        "1: unknown section `[thresholds.networks.]`"
+    );
+}
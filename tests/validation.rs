@@ -0,0 +1,111 @@
+mod common;
+
+use common::MockServer;
+use roller::{Config, Problem, Severity};
+
+/// Parses the arguments, remembering their sources, and validates them.
+fn validate(args: &[&str]) -> Vec<Problem> {
+    Config::try_load_from(std::iter::once("roller").chain(args.iter().copied()))
+        .unwrap()
+        .validate()
+}
+
+#[test]
+fn defaults_are_valid() {
+    assert_eq!(validate(&[]), []);
+    assert_eq!(
+        validate(&["--top", "5", "--pending-warn", "10", "--pending-txs"]),
+        []
+    );
+}
+
+#[test]
+fn conflicting_filters_and_formats_are_errors() {
+    let problems = validate(&["base", "--exclude", "zora,base"]);
+    assert_eq!(problems.len(), 1);
+    assert_eq!(
+        problems[0].to_string(),
+        "error: networks both included and excluded are never displayed: base (from NETWORK, --exclude)"
+    );
+
+    let problems = validate(&["--follow"]);
+    assert_eq!(problems[0].severity, Severity::Error);
+    assert_eq!(problems[0].sources, ["--follow", "the default of --format"]);
+}
+
+#[test]
+fn options_without_effect_are_warnings() {
+    let problems = validate(&[
+        "--top",
+        "3",
+        "--sort",
+        "tps",
+        "--columns",
+        "network,gps",
+        "--pending-warn",
+        "50",
+        "--snapshot-max-files",
+        "10",
+        "--log-level",
+        "debug",
+        "--log-format",
+        "json",
+        "--connect-timeout",
+        "1m",
+    ]);
+    let messages: Vec<String> = problems.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        messages,
+        [
+            "warning: --top keeps the 3 busiest networks by TPS, which isn't one of the displayed --columns (from --top, --columns, --sort)",
+            "warning: --pending-warn has no effect without the Pending column, add --pending-txs (from --pending-warn, --columns)",
+            "warning: --snapshot-max-files has no effect without --snapshot-dir (from --snapshot-max-files)",
+            "warning: --log-level and --log-format have no effect without --log-file (from --log-level, --log-format)",
+            "warning: --connect-timeout 1m is longer than --request-timeout 30s, which cuts it short (from --connect-timeout, the default of --request-timeout)",
+        ]
+    );
+    assert!(problems.iter().all(|p| !p.is_error()));
+}
+
+#[test]
+fn filters_excluding_every_network_are_a_warning() {
+    let config = Config::try_load_from(["roller", "--exclude", "base,xai,zora"]).unwrap();
+    let problem = config.filters_check(&common::networks()).unwrap();
+    assert_eq!(
+        problem.to_string(),
+        "warning: the filters exclude all 3 networks of the metadata (from --exclude)"
+    );
+
+    let config = Config::try_load_from(["roller", "--exclude", "base"]).unwrap();
+    assert_eq!(config.filters_check(&common::networks()), None);
+}
+
+#[test]
+fn every_error_is_reported_before_connecting() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = common::roller(
+        &server,
+        &[
+            "--follow",
+            "--include",
+            "base",
+            "--exclude",
+            "base",
+            "--log-level",
+            "debug",
+        ],
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("error: --follow requires"), "{stderr}");
+    assert!(stderr.contains("error: networks both included"), "{stderr}");
+    assert!(
+        stderr.contains("warning: --log-level has no effect"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("invalid configuration, 2 error(s) reported above"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use clap::{error::ErrorKind, Parser};
+use common::MockServer;
+use roller::{Config, Tui};
+
+/// Parses the arguments and applies the filters to the networks of `common::metadata()`.
+fn watch(args: &[&str]) -> (Config, Tui) {
+    let config =
+        Config::try_parse_from(std::iter::once("roller").chain(args.iter().copied())).unwrap();
+    let mut networks = common::networks();
+    networks.retain(|n| config.is_included(n));
+    let tui = Tui::new(networks).with_config(&config);
+    (config, tui)
+}
+
+/// Returns the names of the networks displayed by the Tui, in order.
+fn names(tui: &Tui) -> Vec<&str> {
+    tui.networks().iter().map(|n| n.name.as_str()).collect()
+}
+
+#[test]
+fn no_networks_display_everything() {
+    let (config, tui) = watch(&[]);
+
+    assert!(config.networks.is_empty());
+    assert_eq!(tui.networks().len(), 3);
+    assert!(!tui.detail_open);
+}
+
+#[test]
+fn a_single_network_opens_its_detail_pane() {
+    let (config, tui) = watch(&["xai"]);
+
+    assert_eq!(config.networks, ["xai"]);
+    assert_eq!(names(&tui), ["xai"]);
+    assert!(tui.detail_open);
+}
+
+#[test]
+fn several_networks_act_as_include() {
+    let (_, tui) = watch(&["zora", "base", "--exclude", "zora"]);
+
+    assert_eq!(names(&tui), ["base"]);
+    assert!(!tui.detail_open);
+}
+
+#[test]
+fn networks_conflict_with_include() {
+    let error = Config::try_parse_from(["roller", "base", "--include", "zora"]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ArgumentConflict);
+}
+
+#[test]
+fn unknown_networks_warn_at_startup() {
+    let server = MockServer::start(&common::metadata(), common::events());
+    let output = common::roller(&server, &["--once", "--columns", "network", "base", "bsae"]);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "Network\nBase\n");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Unknown networks: bsae."), "{stderr}");
+}